@@ -0,0 +1,62 @@
+use arrow::datatypes::{DataType, TimeUnit};
+use std::{
+    fmt::Debug,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// Source of the current time for rules that compare against "now"
+///
+/// Rules default to [`SystemClock`]; tests can inject a [`FixedClock`] to be
+/// deterministic.
+pub trait Clock: Send + Sync + Debug {
+    fn now(&self) -> SystemTime;
+
+    /// Nanoseconds since the Unix epoch, negative before it
+    fn now_nanos(&self) -> i64 {
+        nanos_since_epoch(self.now())
+    }
+}
+
+/// The system wall clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A clock that always returns the same time
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub SystemTime);
+
+impl FixedClock {
+    /// A clock fixed at `secs` seconds after the Unix epoch
+    pub fn from_unix_secs(secs: u64) -> Self {
+        Self(UNIX_EPOCH + Duration::from_secs(secs))
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> SystemTime {
+        self.0
+    }
+}
+
+pub(crate) fn nanos_since_epoch(time: SystemTime) -> i64 {
+    let saturate = |d: Duration| i64::try_from(d.as_nanos()).unwrap_or(i64::MAX);
+    match time.duration_since(UNIX_EPOCH) {
+        Ok(after) => saturate(after),
+        Err(before) => -saturate(before.duration()),
+    }
+}
+
+pub(crate) fn duration_nanos(duration: Duration) -> i64 {
+    i64::try_from(duration.as_nanos()).unwrap_or(i64::MAX)
+}
+
+/// The timestamp type temporal columns are normalized to before comparing
+pub(crate) fn utc_timestamp() -> DataType {
+    DataType::Timestamp(TimeUnit::Nanosecond, Some("UTC".into()))
+}
@@ -0,0 +1,206 @@
+use crate::ValidationError;
+use datafusion::{execution::SessionState, prelude::*};
+use std::collections::BTreeMap;
+
+/// Run metadata and reference data available to every rule through
+/// [`RuleSet::context`](crate::RuleSet::context)
+///
+/// Rules resolve `${name}` placeholders with [`RuleContext::resolve`], where `name`
+/// is a variable, `run_date` or `environment`.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::{RuleContext, RuleSet};
+/// use datafusion_quality::rules::column::dfq_custom_sql;
+///
+/// let mut ruleset = RuleSet::new();
+/// ruleset
+///     .with_column_rule("event_date", dfq_custom_sql("not_after_run", "event_date <= DATE '${run_date}'"))
+///     .with_context(RuleContext::new().with_run_date("2024-01-31").with_environment("prod"));
+///
+/// let context = ruleset.context();
+/// assert_eq!(context.resolve("${environment}/${run_date}").unwrap(), "prod/2024-01-31");
+/// ```
+#[derive(Clone, Default)]
+pub struct RuleContext {
+    state: Option<SessionState>,
+    reference_tables: BTreeMap<String, DataFrame>,
+    run_date: Option<String>,
+    environment: Option<String>,
+    variables: BTreeMap<String, String>,
+}
+
+impl std::fmt::Debug for RuleContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RuleContext")
+            .field("session_id", &self.state.as_ref().map(|s| s.session_id()))
+            .field(
+                "reference_tables",
+                &self.reference_tables.keys().collect::<Vec<_>>(),
+            )
+            .field("run_date", &self.run_date)
+            .field("environment", &self.environment)
+            .field("variables", &self.variables)
+            .finish()
+    }
+}
+
+impl RuleContext {
+    /// Create an empty RuleContext
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use the tables registered in a session as reference tables
+    pub fn with_session_state(mut self, state: SessionState) -> Self {
+        self.state = Some(state);
+        self
+    }
+
+    /// Register a reference table, taking precedence over the session's tables
+    pub fn with_reference_table(mut self, name: impl Into<String>, table: DataFrame) -> Self {
+        self.reference_tables.insert(name.into(), table);
+        self
+    }
+
+    /// Set the execution date of the run, available as `${run_date}`
+    pub fn with_run_date(mut self, run_date: impl Into<String>) -> Self {
+        self.run_date = Some(run_date.into());
+        self
+    }
+
+    /// Set the environment of the run, e.g. `prod`, available as `${environment}`
+    pub fn with_environment(mut self, environment: impl Into<String>) -> Self {
+        self.environment = Some(environment.into());
+        self
+    }
+
+    /// Set a variable, available as `${name}`
+    pub fn with_variable(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.variables.insert(name.into(), value.into());
+        self
+    }
+
+    /// The session state, if set
+    pub fn session_state(&self) -> Option<&SessionState> {
+        self.state.as_ref()
+    }
+
+    /// The execution date of the run, if set
+    pub fn run_date(&self) -> Option<&str> {
+        self.run_date.as_deref()
+    }
+
+    /// The environment of the run, if set
+    pub fn environment(&self) -> Option<&str> {
+        self.environment.as_deref()
+    }
+
+    /// The value of a variable, including `run_date` and `environment`
+    pub fn variable(&self, name: &str) -> Option<&str> {
+        match name {
+            "run_date" => self.run_date(),
+            "environment" => self.environment(),
+            _ => self.variables.get(name).map(String::as_str),
+        }
+    }
+
+    /// Replace every `${name}` placeholder in `template` with its variable
+    ///
+    /// Returns a configuration error for unknown variables and unterminated placeholders.
+    pub fn resolve(&self, template: &str) -> Result<String, ValidationError> {
+        let mut resolved = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find("${") {
+            resolved.push_str(&rest[..start]);
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| ValidationError::Configuration {
+                    message: format!("Unterminated placeholder in '{}'", template),
+                })?;
+            let name = &rest[start + 2..start + end];
+            let value = self
+                .variable(name)
+                .ok_or_else(|| ValidationError::Configuration {
+                    message: format!("Unknown variable '{}' in '{}'", name, template),
+                })?;
+            resolved.push_str(value);
+            rest = &rest[start + end + 1..];
+        }
+        resolved.push_str(rest);
+        Ok(resolved)
+    }
+
+    /// A reference table registered with [`RuleContext::with_reference_table`]
+    pub fn registered_table(&self, name: &str) -> Option<DataFrame> {
+        self.reference_tables.get(name).cloned()
+    }
+
+    /// Look up a reference table, first among the registered reference tables and
+    /// then among the tables of the session
+    pub async fn reference_table(&self, name: &str) -> Result<DataFrame, ValidationError> {
+        if let Some(table) = self.registered_table(name) {
+            return Ok(table);
+        }
+        match &self.state {
+            Some(state) => Ok(SessionContext::new_with_state(state.clone())
+                .table(name)
+                .await?),
+            None => Err(ValidationError::Configuration {
+                message: format!("Unknown reference table '{}'", name),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rule_context() {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE countries AS VALUES ('FR'), ('DE')")
+            .await
+            .unwrap();
+
+        let context = RuleContext::new()
+            .with_session_state(ctx.state())
+            .with_reference_table("codes", ctx.read_empty().unwrap())
+            .with_run_date("2024-01-31")
+            .with_variable("threshold", "10");
+
+        assert_eq!(
+            context
+                .resolve("day = '${run_date}' AND n > ${threshold}")
+                .unwrap(),
+            "day = '2024-01-31' AND n > 10"
+        );
+        assert_eq!(
+            context.resolve("no placeholders").unwrap(),
+            "no placeholders"
+        );
+        assert!(matches!(
+            context.resolve("${environment}"),
+            Err(ValidationError::Configuration { .. })
+        ));
+        assert!(matches!(
+            context.resolve("${run_date"),
+            Err(ValidationError::Configuration { .. })
+        ));
+
+        assert_eq!(
+            context
+                .reference_table("countries")
+                .await
+                .unwrap()
+                .count()
+                .await
+                .unwrap(),
+            2
+        );
+        assert!(context.reference_table("codes").await.is_ok());
+        assert!(context.reference_table("missing").await.is_err());
+    }
+}
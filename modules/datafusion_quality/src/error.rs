@@ -14,6 +14,13 @@ pub enum ValidationError {
     #[snafu(display("Configuration error: {}", message))]
     Configuration { message: String },
 
+    /// A rule that only implements its asynchronous method was applied synchronously
+    #[snafu(display(
+        "Rule '{}' only applies asynchronously, apply it through a RuleSet",
+        rule_name
+    ))]
+    AsyncOnly { rule_name: String },
+
     #[snafu(display("Column not found: {}", column_name))]
     ColumnNotFound { column_name: String },
 
@@ -0,0 +1,674 @@
+use crate::{
+    RuleMeta, RuleRef, RuleSet, ValidationError, error::DataFusionSnafu, report::DatasetOutcome,
+};
+use datafusion::{
+    common::{DFSchema, ScalarValue},
+    logical_expr::ExprSchemable,
+    prelude::*,
+};
+use snafu::ResultExt;
+use std::collections::HashSet;
+
+/// A rule that was not evaluated because one of its prerequisites failed or was
+/// skipped, see [`RuleSet::with_depends_on`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedRule {
+    /// The id of the skipped rule
+    pub rule_id: String,
+    /// The id of the prerequisite that failed or was skipped
+    pub prerequisite: String,
+    pub(crate) rule: RuleRef,
+}
+
+/// The result of evaluating a RuleSet against a DataFrame
+#[derive(Debug, Clone)]
+pub struct Evaluation {
+    /// The DataFrame with the rule columns and `dfq_pass`, as returned by [`RuleSet::apply`]
+    pub df: DataFrame,
+    /// The rules skipped because a prerequisite failed, in evaluation order
+    pub skipped: Vec<SkippedRule>,
+    /// The dataset rule outcomes, in the order the rules were added
+    pub dataset_outcomes: Vec<DatasetOutcome>,
+}
+
+impl Evaluation {
+    /// Whether the rule at `rule` was skipped
+    pub(crate) fn is_skipped(&self, rule: RuleRef) -> bool {
+        self.skipped.iter().any(|skipped| skipped.rule == rule)
+    }
+}
+
+impl RuleSet {
+    /// Every registered rule, schema rules first, then table, column and dataset
+    /// rules, each in the order they were added
+    pub(crate) fn rule_refs(&self) -> Vec<RuleRef> {
+        (0..self.schema_rules.len())
+            .map(RuleRef::Schema)
+            .chain((0..self.table_rules.len()).map(RuleRef::Table))
+            .chain((0..self.column_rules.len()).map(RuleRef::Column))
+            .chain((0..self.dataset_rules.len()).map(RuleRef::Dataset))
+            .collect()
+    }
+
+    /// The id other rules use to depend on `rule`
+    pub(crate) fn rule_id(&self, rule: RuleRef) -> String {
+        match rule {
+            RuleRef::Schema(i) => self.schema_rules[i].0.name().to_string(),
+            RuleRef::Column(i) => {
+                let (column_name, rule, _) = &self.column_rules[i];
+                rule.new_column_name(column_name)
+            }
+            RuleRef::Table(i) => {
+                let (column_name, rule, _) = &self.table_rules[i];
+                rule.new_column_name(column_name)
+            }
+            RuleRef::Dataset(i) => {
+                let (column_name, rule, _) = &self.dataset_rules[i];
+                rule.new_column_name(column_name)
+            }
+        }
+    }
+
+    /// Check the type of the column `rule` is registered for against its
+    /// [`SupportedTypes`](crate::SupportedTypes)
+    pub(crate) fn check_rule_type(
+        &self,
+        rule: RuleRef,
+        schema: &DFSchema,
+    ) -> Result<(), ValidationError> {
+        match rule {
+            RuleRef::Schema(_) => Ok(()),
+            RuleRef::Column(i) => {
+                let (column_name, rule, _) = &self.column_rules[i];
+                rule.supported_types()
+                    .check(rule.name(), column_name, schema)
+            }
+            RuleRef::Table(i) => {
+                let (column_name, rule, _) = &self.table_rules[i];
+                rule.supported_types()
+                    .check(rule.name(), column_name, schema)
+            }
+            RuleRef::Dataset(i) => {
+                let (column_name, rule, _) = &self.dataset_rules[i];
+                rule.supported_types()
+                    .check(rule.name(), column_name, schema)
+            }
+        }
+    }
+
+    /// Check every rule supports the type of its column in `schema`
+    ///
+    /// [`RuleSet::apply`] checks each rule just before applying it, so rules skipped
+    /// because a prerequisite failed are not checked. This checks all rules up front.
+    /// Columns missing from the schema, such as the results of table rules, are not
+    /// checked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use datafusion::prelude::*;
+    /// use datafusion_quality::RuleSet;
+    /// use datafusion_quality::error::ValidationError;
+    /// use datafusion_quality::rules::column::dfq_str_max_length;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let ctx = SessionContext::new();
+    /// let df = ctx.sql("SELECT 1 AS id, 'Alice' AS name").await?;
+    ///
+    /// let mut ruleset = RuleSet::new();
+    /// ruleset.with_column_rule("id", dfq_str_max_length(64));
+    ///
+    /// let error = ruleset.check_types(df.schema()).unwrap_err();
+    /// assert!(matches!(error, ValidationError::TypeMismatch { .. }));
+    /// assert_eq!(
+    ///     error.to_string(),
+    ///     "Type mismatch: Rule 'max_length' on column 'id' expects a string column, got Int64"
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn check_types(&self, schema: &DFSchema) -> Result<(), ValidationError> {
+        for rule in self.rule_refs() {
+            self.check_rule_type(rule, schema)?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn meta(&self, rule: RuleRef) -> &RuleMeta {
+        match rule {
+            RuleRef::Schema(i) => &self.schema_rules[i].1,
+            RuleRef::Column(i) => &self.column_rules[i].2,
+            RuleRef::Table(i) => &self.table_rules[i].2,
+            RuleRef::Dataset(i) => &self.dataset_rules[i].2,
+        }
+    }
+
+    /// Order the rules so every rule comes after its prerequisites, keeping the
+    /// order of [`RuleSet::rule_refs`] otherwise
+    pub(crate) fn evaluation_order(&self) -> Result<Vec<RuleRef>, ValidationError> {
+        let rules = self.rule_refs();
+        let ids: Vec<String> = rules.iter().map(|rule| self.rule_id(*rule)).collect();
+
+        let mut prerequisites = Vec::with_capacity(rules.len());
+        for (i, rule) in rules.iter().enumerate() {
+            let mut positions = Vec::new();
+            for dependency in &self.meta(*rule).depends_on {
+                let before = positions.len();
+                positions.extend((0..rules.len()).filter(|j| &ids[*j] == dependency));
+                if positions.len() == before {
+                    return Err(ValidationError::Configuration {
+                        message: format!(
+                            "Rule '{}' depends on unknown rule '{}'",
+                            ids[i], dependency
+                        ),
+                    });
+                }
+            }
+            prerequisites.push(positions);
+        }
+
+        let mut done = vec![false; rules.len()];
+        let mut order = Vec::with_capacity(rules.len());
+        while order.len() < rules.len() {
+            let next = (0..rules.len())
+                .find(|i| !done[*i] && prerequisites[*i].iter().all(|j| done[*j]))
+                .ok_or_else(|| {
+                    let cycle: Vec<&str> = (0..rules.len())
+                        .filter(|i| !done[*i])
+                        .map(|i| ids[i].as_str())
+                        .collect();
+                    ValidationError::Configuration {
+                        message: format!("Circular rule dependencies between {:?}", cycle),
+                    }
+                })?;
+            done[next] = true;
+            order.push(rules[next]);
+        }
+        Ok(order)
+    }
+
+    /// Apply all rules like [`RuleSet::apply`], also evaluating the dataset rules
+    /// and reporting the rules skipped because a prerequisite failed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use datafusion_quality::RuleSet;
+    /// use datafusion_quality::rules::column::*;
+    /// use datafusion::prelude::*;
+    ///
+    /// # async fn example(df: DataFrame) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut ruleset = RuleSet::new();
+    /// ruleset
+    ///     .with_column_rule("email", dfq_not_null())
+    ///     .with_column_rule("email", dfq_is_email())
+    ///     .with_depends_on(["email_not_null"]);
+    ///
+    /// let evaluation = ruleset.evaluate(&df).await?;
+    /// for skipped in &evaluation.skipped {
+    ///     println!("{} skipped: {} failed", skipped.rule_id, skipped.prerequisite);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn evaluate(&self, df: &DataFrame) -> Result<Evaluation, ValidationError> {
+        self.evaluate_rules(df, true).await
+    }
+
+    pub(crate) async fn evaluate_rules(
+        &self,
+        df: &DataFrame,
+        with_dataset_outcomes: bool,
+    ) -> Result<Evaluation, ValidationError> {
+        let order = self.evaluation_order()?;
+        let prerequisites: HashSet<&str> = order
+            .iter()
+            .flat_map(|rule| self.meta(*rule).depends_on.iter().map(String::as_str))
+            .collect();
+
+        let mut result_df = df.clone();
+        let mut check_columns = Vec::new();
+        let mut skipped = Vec::new();
+        let mut dataset_outcomes = vec![None; self.dataset_rules.len()];
+        // Ids of the rules that failed or were skipped
+        let mut blocked: HashSet<String> = HashSet::new();
+
+        for rule in order {
+            let id = self.rule_id(rule);
+            let is_prerequisite = prerequisites.contains(id.as_str());
+
+            let failed_prerequisite = self
+                .meta(rule)
+                .depends_on
+                .iter()
+                .find(|dependency| blocked.contains(*dependency));
+            if let Some(prerequisite) = failed_prerequisite {
+                // Keep the result columns, so the output has the same schema either way
+                result_df = self.with_placeholder(result_df, rule)?;
+                match rule {
+                    RuleRef::Dataset(i) => {
+                        let (column_name, dataset_rule, _) = &self.dataset_rules[i];
+                        dataset_outcomes[i] = Some(DatasetOutcome {
+                            column_name: column_name.clone(),
+                            rule_name: dataset_rule.name().to_string(),
+                            outcome_name: id.clone(),
+                            observed: None,
+                            passed: false,
+                            skipped: true,
+                        });
+                    }
+                    RuleRef::Schema(_) | RuleRef::Table(_) | RuleRef::Column(_) => {}
+                }
+                skipped.push(SkippedRule {
+                    rule_id: id.clone(),
+                    prerequisite: prerequisite.clone(),
+                    rule,
+                });
+                blocked.insert(id);
+                continue;
+            }
+
+            let passed = match rule {
+                RuleRef::Schema(i) => {
+                    let schema_rule = &self.schema_rules[i].0;
+                    // Without dependents a failing schema rule fails the whole run
+                    let passed = match schema_rule.validate_schema_async(df.schema(), self).await {
+                        Ok(passed) => passed,
+                        Err(_) if is_prerequisite => false,
+                        Err(e) => return Err(e),
+                    };
+                    if !passed && !is_prerequisite {
+                        return Err(ValidationError::Schema {
+                            message: format!("Schema rule '{}' failed", schema_rule.name()),
+                        });
+                    }
+                    passed
+                }
+                RuleRef::Table(i) => {
+                    let (column_name, table_rule, _) = &self.table_rules[i];
+                    self.check_rule_type(rule, result_df.schema())?;
+                    result_df = table_rule.apply_async(result_df, column_name, self).await?;
+                    true
+                }
+                RuleRef::Column(i) => {
+                    let (column_name, column_rule, _) = &self.column_rules[i];
+                    self.check_rule_type(rule, result_df.schema())?;
+                    result_df = column_rule
+                        .apply_async(result_df, column_name, self)
+                        .await?;
+                    check_columns.push(id.clone());
+                    // A column rule passes as a prerequisite when every row passes
+                    !is_prerequisite
+                        || result_df
+                            .clone()
+                            .filter(ident(&id).is_not_true())?
+                            .limit(0, Some(1))?
+                            .count()
+                            .await
+                            .context(DataFusionSnafu)?
+                            == 0
+                }
+                RuleRef::Dataset(i) => {
+                    let (column_name, dataset_rule, _) = &self.dataset_rules[i];
+                    self.check_rule_type(rule, result_df.schema())?;
+                    // Row-level results of dataset rules are informational only
+                    if let Some(row_rule) = dataset_rule.row_rule() {
+                        result_df = row_rule.apply_async(result_df, column_name, self).await?;
+                    }
+                    if with_dataset_outcomes || is_prerequisite {
+                        let outcome =
+                            DatasetOutcome::evaluate(df, column_name, dataset_rule.as_ref(), self)
+                                .await?;
+                        let passed = outcome.passed;
+                        dataset_outcomes[i] = Some(outcome);
+                        passed
+                    } else {
+                        true
+                    }
+                }
+            };
+
+            if !passed {
+                blocked.insert(id);
+            }
+        }
+
+        let dq_pass_col = pass_expr(check_columns, &result_df)?;
+
+        Ok(Evaluation {
+            df: result_df.with_column("dfq_pass", dq_pass_col)?,
+            skipped,
+            dataset_outcomes: dataset_outcomes.into_iter().flatten().collect(),
+        })
+    }
+
+    /// Add the result column of `rule` with null values, for a rule that is skipped
+    /// or cannot be planned without running a query
+    fn with_placeholder(&self, df: DataFrame, rule: RuleRef) -> Result<DataFrame, ValidationError> {
+        let (column_name, placeholder) = match rule {
+            RuleRef::Schema(_) => return Ok(df),
+            RuleRef::Table(_) => (self.rule_id(rule), ScalarValue::Null),
+            RuleRef::Column(_) => (self.rule_id(rule), ScalarValue::Boolean(None)),
+            RuleRef::Dataset(i) => {
+                let (column_name, dataset_rule, _) = &self.dataset_rules[i];
+                match dataset_rule.row_rule() {
+                    Some(row_rule) => (
+                        row_rule.new_column_name(column_name),
+                        ScalarValue::Boolean(None),
+                    ),
+                    None => return Ok(df),
+                }
+            }
+        };
+        df.with_column(&column_name, lit(placeholder))
+            .context(DataFusionSnafu)
+    }
+
+    /// The columns [`RuleSet::apply`] adds to `df`, planned without running any query
+    ///
+    /// Schema rules are checked against the schema of `df` and rules depending on
+    /// failing ones get the placeholders `apply` uses for skipped rules. Other
+    /// prerequisites are assumed to pass. Rules that only resolve in
+    /// [`ColumnRule::apply_async`], e.g. looking up a session table, also get a
+    /// placeholder, so their results may have other types in the result of `apply`.
+    ///
+    /// [`ColumnRule::apply_async`]: crate::ColumnRule::apply_async
+    pub(crate) fn plan(&self, df: &DataFrame) -> Result<DataFrame, ValidationError> {
+        let order = self.evaluation_order()?;
+        let prerequisites: HashSet<&str> = order
+            .iter()
+            .flat_map(|rule| self.meta(*rule).depends_on.iter().map(String::as_str))
+            .collect();
+
+        let mut result_df = df.clone();
+        let mut check_columns = Vec::new();
+        // Ids of the schema rules that failed and the rules skipped because of them
+        let mut blocked: HashSet<String> = HashSet::new();
+
+        for rule in order {
+            let id = self.rule_id(rule);
+            if let RuleRef::Column(_) = rule {
+                check_columns.push(id.clone());
+            }
+
+            let skipped = self
+                .meta(rule)
+                .depends_on
+                .iter()
+                .any(|dependency| blocked.contains(dependency));
+            if skipped {
+                result_df = self.with_placeholder(result_df, rule)?;
+                blocked.insert(id);
+                continue;
+            }
+
+            let applied = match rule {
+                RuleRef::Schema(i) => {
+                    let schema_rule = &self.schema_rules[i].0;
+                    let is_prerequisite = prerequisites.contains(id.as_str());
+                    let passed = match schema_rule.validate_schema_with_ruleset(df.schema(), self) {
+                        Ok(passed) => passed,
+                        // Only known once applied
+                        Err(ValidationError::AsyncOnly { .. }) => true,
+                        Err(_) if is_prerequisite => false,
+                        Err(e) => return Err(e),
+                    };
+                    if !passed {
+                        if !is_prerequisite {
+                            return Err(ValidationError::Schema {
+                                message: format!("Schema rule '{}' failed", schema_rule.name()),
+                            });
+                        }
+                        blocked.insert(id);
+                    }
+                    continue;
+                }
+                RuleRef::Table(i) => {
+                    let (column_name, table_rule, _) = &self.table_rules[i];
+                    self.check_rule_type(rule, result_df.schema())?;
+                    table_rule.apply_with_ruleset(result_df.clone(), column_name, self)
+                }
+                RuleRef::Column(i) => {
+                    let (column_name, column_rule, _) = &self.column_rules[i];
+                    self.check_rule_type(rule, result_df.schema())?;
+                    column_rule.apply_with_ruleset(result_df.clone(), column_name, self)
+                }
+                RuleRef::Dataset(i) => {
+                    let (column_name, dataset_rule, _) = &self.dataset_rules[i];
+                    self.check_rule_type(rule, result_df.schema())?;
+                    match dataset_rule.row_rule() {
+                        Some(row_rule) => {
+                            row_rule.apply_with_ruleset(result_df.clone(), column_name, self)
+                        }
+                        None => continue,
+                    }
+                }
+            };
+            result_df = match applied {
+                Ok(applied) => applied,
+                Err(ValidationError::AsyncOnly { .. }) => self.with_placeholder(result_df, rule)?,
+                Err(e) => return Err(e),
+            };
+        }
+
+        let dq_pass_col = pass_expr(check_columns, &result_df)?;
+        result_df
+            .with_column("dfq_pass", dq_pass_col)
+            .context(DataFusionSnafu)
+    }
+}
+
+/// The `dfq_pass` expression, true when every check column of `df` is true
+pub(crate) fn pass_expr(
+    check_columns: Vec<String>,
+    df: &DataFrame,
+) -> Result<Expr, ValidationError> {
+    check_columns
+        .into_iter()
+        .map(|col_name| {
+            col(col_name)
+                .cast_to(&arrow::datatypes::DataType::Boolean, df.schema())
+                .map_err(|e| ValidationError::Column {
+                    message: format!("Error casting column to boolean: {}", e),
+                })
+        })
+        .reduce(|acc, col| Ok(acc?.and(col?)))
+        .unwrap_or(Ok(lit(true)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{
+        column::*,
+        combinator::dfq_all,
+        dataset::dfq_mostly,
+        schema::{dfq_column_exists, dfq_column_type},
+        table::*,
+    };
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion::assert_batches_eq;
+    use std::sync::Arc;
+
+    fn create_test_df() -> DataFrame {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("email", DataType::Utf8, true),
+        ]);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3, 4])),
+                Arc::new(StringArray::from(vec![
+                    Some("alice@example.com"),
+                    None,
+                    Some("bob"),
+                    None,
+                ])),
+            ],
+        )
+        .unwrap();
+
+        SessionContext::new().read_batch(batch).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_dependencies() {
+        let df = create_test_df();
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            // Registered before its prerequisite, evaluated after it
+            .with_column_rule("id", dfq_gt(lit(2)))
+            .with_depends_on(["id_not_null"])
+            .with_column_rule("id", dfq_not_null())
+            // The column does not exist, so rules on it would fail to plan
+            .with_schema_rule(dfq_column_exists("nickname"))
+            .with_table_rule("nickname", dfq_null_count(), Some(dfq_eq(lit(0))))
+            .with_depends_on(["column_exists"])
+            .with_dataset_rule("email", dfq_mostly(dfq_not_null(), 0.9))
+            .with_column_rule("email", dfq_is_email())
+            .with_depends_on(["email_not_null_pass_rate"])
+            .with_column_rule("email", dfq_str_max_length(64))
+            .with_depends_on(["email_is_email"]);
+
+        let evaluation = rule_set.evaluate(&df).await.unwrap();
+        let skipped: Vec<(&str, &str)> = evaluation
+            .skipped
+            .iter()
+            .map(|s| (s.rule_id.as_str(), s.prerequisite.as_str()))
+            .collect();
+        assert_eq!(
+            skipped,
+            vec![
+                ("nickname_null_count", "column_exists"),
+                ("nickname_null_count_equals", "column_exists"),
+                ("email_is_email", "email_not_null_pass_rate"),
+                ("email_length", "email_is_email"),
+            ]
+        );
+        assert!(!evaluation.dataset_outcomes[0].passed);
+
+        let result = evaluation
+            .df
+            .drop_columns(&["email", "nickname_null_count"])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+
+        let expected = vec![
+            "+----+-------------+-----------------+----------------------------+----------------+----------------+--------------+----------+",
+            "| id | id_not_null | id_greater_than | nickname_null_count_equals | email_not_null | email_is_email | email_length | dfq_pass |",
+            "+----+-------------+-----------------+----------------------------+----------------+----------------+--------------+----------+",
+            "| 1  | true        | false           |                            | true           |                |              | false    |",
+            "| 2  | true        | false           |                            | false          |                |              | false    |",
+            "| 3  | true        | true            |                            | true           |                |              | true     |",
+            "| 4  | true        | true            |                            | false          |                |              | true     |",
+            "+----+-------------+-----------------+----------------------------+----------------+----------------+--------------+----------+",
+        ];
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        let report = rule_set.report(&df).await.unwrap();
+        let skipped: Vec<(&str, bool, u64)> = report
+            .rules
+            .iter()
+            .map(|r| (r.check_column.as_str(), r.skipped, r.failed))
+            .collect();
+        assert_eq!(
+            skipped,
+            vec![
+                ("id_greater_than", false, 2),
+                ("id_not_null", false, 0),
+                ("nickname_null_count_equals", true, 0),
+                ("email_is_email", true, 0),
+                ("email_length", true, 0),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dependency_errors() {
+        let df = create_test_df();
+
+        let mut unknown = RuleSet::new();
+        unknown
+            .with_column_rule("id", dfq_not_null())
+            .with_depends_on(["id_exists"]);
+        assert!(matches!(
+            unknown.apply(&df).await,
+            Err(ValidationError::Configuration { .. })
+        ));
+
+        let mut cycle = RuleSet::new();
+        cycle
+            .with_column_rule("id", dfq_not_null())
+            .with_depends_on(["id_greater_than"])
+            .with_column_rule("id", dfq_gt(lit(0)))
+            .with_depends_on(["id_not_null"]);
+        assert!(matches!(
+            cycle.apply(&df).await,
+            Err(ValidationError::Configuration { .. })
+        ));
+
+        // Without dependents a failing schema rule still fails the run
+        let mut schema = RuleSet::new();
+        schema.with_schema_rule(dfq_column_exists("nickname"));
+        assert!(matches!(
+            schema.apply(&df).await,
+            Err(ValidationError::ColumnNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_type_checking() {
+        let df = create_test_df();
+
+        let mismatch = |rule_set: &RuleSet| match rule_set.check_types(df.schema()) {
+            Err(ValidationError::TypeMismatch { message }) => message,
+            result => panic!("expected a type mismatch, got {:?}", result),
+        };
+
+        let mut range = RuleSet::new();
+        range.with_column_rule("email", dfq_in_range(0.0, 10.0));
+        assert_eq!(
+            mismatch(&range),
+            "Rule 'in_range' on column 'email' expects a numeric column, got Utf8"
+        );
+        // Applying the rules checks types before planning
+        assert!(matches!(
+            range.apply(&df).await,
+            Err(ValidationError::TypeMismatch { .. })
+        ));
+
+        let mut avg = RuleSet::new();
+        avg.with_table_rule("email", dfq_avg(), None);
+        assert_eq!(
+            mismatch(&avg),
+            "Rule 'calculation' on column 'email' expects a numeric column, got Utf8"
+        );
+
+        let mut combined = RuleSet::new();
+        combined.with_column_rule("id", dfq_all(vec![dfq_not_null(), dfq_str_max_length(8)]));
+        assert_eq!(
+            mismatch(&combined),
+            "Rule 'all_not_null_and_max_length' on column 'id' expects a string column, got Int32"
+        );
+
+        // Rules skipped because their column has the wrong type are not checked
+        let mut guarded = RuleSet::new();
+        guarded
+            .with_schema_rule(dfq_column_type("id", DataType::Utf8))
+            .with_column_rule("id", dfq_str_max_length(8))
+            .with_depends_on(["column_type"])
+            .with_column_rule("id", dfq_in_range(0.0, 10.0))
+            .with_column_rule("email", dfq_is_email());
+        assert!(guarded.check_types(df.schema()).is_err());
+        let evaluation = guarded.evaluate(&df).await.unwrap();
+        assert_eq!(evaluation.skipped.len(), 1);
+        assert_eq!(evaluation.skipped[0].rule_id, "id_length");
+    }
+}
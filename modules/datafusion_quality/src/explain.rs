@@ -0,0 +1,541 @@
+use crate::{RuleRef, RuleSet, ValidationError, evaluation::pass_expr, rules::added_expr};
+use arrow::{datatypes::DataType, record_batch::RecordBatch, util::pretty::pretty_format_batches};
+use datafusion::{
+    common::{ScalarValue, SchemaError},
+    error::DataFusionError,
+    prelude::*,
+};
+
+/// The kind of a rule in an [`Explanation`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleKind {
+    Schema,
+    Column,
+    Table,
+    Dataset,
+}
+
+impl std::fmt::Display for RuleKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            RuleKind::Schema => "schema",
+            RuleKind::Column => "column",
+            RuleKind::Table => "table",
+            RuleKind::Dataset => "dataset",
+        };
+        f.write_str(kind)
+    }
+}
+
+/// How a single rule resolves against the input schema
+#[derive(Debug, Clone)]
+pub struct RuleExplanation {
+    /// The id other rules use to depend on the rule, see [`RuleSet::with_depends_on`]
+    pub rule_id: String,
+    pub kind: RuleKind,
+    pub rule_name: String,
+    /// The column the rule targets, `None` for schema rules
+    pub column: Option<String>,
+    /// The column the rule adds, `None` for schema rules and dataset rules
+    /// without a row-level rule
+    pub output_column: Option<String>,
+    /// The expression computing the output column, when the rule adds it directly
+    /// rather than through a join or subquery
+    pub expr: Option<Expr>,
+    /// The type of the output column
+    pub output_type: Option<DataType>,
+    /// Columns the rule needs that are not in the input
+    pub missing_columns: Vec<String>,
+    /// Why the rule does not resolve otherwise, e.g. a type incompatibility
+    pub error: Option<String>,
+}
+
+impl RuleExplanation {
+    /// Whether the rule resolves against the input schema
+    pub fn is_valid(&self) -> bool {
+        self.missing_columns.is_empty() && self.error.is_none()
+    }
+
+    fn new(rule_id: String, kind: RuleKind, rule_name: &str, column: Option<&String>) -> Self {
+        Self {
+            rule_id,
+            kind,
+            rule_name: rule_name.to_string(),
+            column: column.cloned(),
+            output_column: None,
+            expr: None,
+            output_type: None,
+            missing_columns: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Record the outcome of applying the rule to `input`, returning the DataFrame
+    /// the next rule is applied to
+    fn resolve(
+        &mut self,
+        input: DataFrame,
+        applied: Result<DataFrame, ValidationError>,
+        output_column: String,
+        placeholder: ScalarValue,
+    ) -> Result<DataFrame, ValidationError> {
+        // Type coercion errors only show up once the plan is analyzed
+        let applied = match applied {
+            Ok(result) => match result.clone().into_optimized_plan() {
+                Ok(_) => Ok(result),
+                Err(e) => Err(e.into()),
+            },
+            Err(e) => Err(e),
+        };
+
+        let result = match applied {
+            Ok(result) => {
+                self.expr = added_expr(&result, &input, &output_column);
+                self.output_type = result
+                    .schema()
+                    .field_with_unqualified_name(&output_column)
+                    .ok()
+                    .map(|field| field.data_type().clone());
+                result
+            }
+            Err(e) => {
+                self.add_error(e);
+                input.with_column(&output_column, lit(placeholder))?
+            }
+        };
+        self.output_column = Some(output_column);
+        Ok(result)
+    }
+
+    fn add_error(&mut self, e: ValidationError) {
+        match e {
+            ValidationError::ColumnNotFound { column_name } => {
+                self.missing_columns.push(column_name)
+            }
+            ValidationError::DataFusion { source } => match source.find_root() {
+                DataFusionError::SchemaError(SchemaError::FieldNotFound { field, .. }, _) => {
+                    self.missing_columns.push(field.name().to_string())
+                }
+                root => self.error = Some(root.to_string()),
+            },
+            e => self.error = Some(e.to_string()),
+        }
+    }
+}
+
+/// The result of [`RuleSet::explain`]
+#[derive(Debug, Clone)]
+pub struct Explanation {
+    /// Every rule, in evaluation order
+    pub rules: Vec<RuleExplanation>,
+    /// The output of DataFusion's `explain` for the combined logical and physical
+    /// plan, `None` when a rule does not resolve
+    pub plan: Option<Vec<RecordBatch>>,
+}
+
+impl Explanation {
+    /// Whether every rule resolves against the input schema
+    pub fn is_valid(&self) -> bool {
+        self.rules.iter().all(RuleExplanation::is_valid)
+    }
+
+    /// The rules that do not resolve against the input schema
+    pub fn invalid_rules(&self) -> impl Iterator<Item = &RuleExplanation> {
+        self.rules.iter().filter(|rule| !rule.is_valid())
+    }
+}
+
+impl std::fmt::Display for Explanation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for rule in &self.rules {
+            write!(
+                f,
+                "{} {} rule '{}'",
+                rule.kind, rule.rule_name, rule.rule_id
+            )?;
+            if let Some(column) = &rule.column {
+                write!(f, " on {}", column)?;
+            }
+            if let Some(output_column) = &rule.output_column {
+                write!(f, " -> {}", output_column)?;
+            }
+            if let Some(output_type) = &rule.output_type {
+                write!(f, ": {}", output_type)?;
+            }
+            if let Some(expr) = &rule.expr {
+                write!(f, " = {}", expr)?;
+            }
+            writeln!(f)?;
+            if !rule.missing_columns.is_empty() {
+                writeln!(f, "  missing columns: {}", rule.missing_columns.join(", "))?;
+            }
+            if let Some(error) = &rule.error {
+                writeln!(f, "  error: {}", error)?;
+            }
+        }
+        if let Some(plan) = &self.plan {
+            writeln!(
+                f,
+                "{}",
+                pretty_format_batches(plan).map_err(|_| std::fmt::Error)?
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl RuleSet {
+    /// Resolve every rule against the schema of `df` without executing the validation
+    ///
+    /// Reports each rule's output column, generated expression, missing columns and
+    /// type incompatibilities, and the combined plan from DataFusion's `explain` when
+    /// every rule resolves. Rules are not checked against their prerequisites, and a
+    /// rule that does not resolve is replaced by a null column for the rules after it.
+    /// Nothing is executed, except queries rules run themselves in
+    /// [`ColumnRule::apply_async`](crate::ColumnRule::apply_async), e.g. to collect
+    /// reference data.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use datafusion_quality::RuleSet;
+    /// use datafusion_quality::rules::column::*;
+    /// use datafusion::prelude::*;
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let df = SessionContext::new().sql("SELECT 1 AS id, 'Alice' AS name").await?;
+    ///
+    /// let mut ruleset = RuleSet::new();
+    /// ruleset
+    ///     .with_column_rule("name", dfq_not_null())
+    ///     .with_column_rule("id", dfq_is_email())
+    ///     .with_column_rule("email", dfq_is_email());
+    ///
+    /// let explanation = ruleset.explain(&df).await?;
+    /// assert!(explanation.rules[0].is_valid());
+    /// assert!(explanation.rules[1].error.is_some());
+    /// assert_eq!(explanation.rules[2].missing_columns, vec!["email"]);
+    /// println!("{}", explanation);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn explain(&self, df: &DataFrame) -> Result<Explanation, ValidationError> {
+        let mut result_df = df.clone();
+        let mut check_columns = Vec::new();
+        let mut rules = Vec::new();
+
+        for rule in self.evaluation_order()? {
+            let rule_id = self.rule_id(rule);
+            let (kind, rule_name, column, applied) = match rule {
+                RuleRef::Schema(i) => {
+                    let schema_rule = &self.schema_rules[i].0;
+                    let mut explanation =
+                        RuleExplanation::new(rule_id, RuleKind::Schema, schema_rule.name(), None);
+                    match schema_rule.validate_schema_async(df.schema(), self).await {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            explanation.error =
+                                Some(format!("Schema rule '{}' failed", schema_rule.name()))
+                        }
+                        Err(e) => explanation.add_error(e),
+                    }
+                    rules.push(explanation);
+                    continue;
+                }
+                RuleRef::Table(i) => {
+                    let (column_name, table_rule, _) = &self.table_rules[i];
+                    let applied = match self.check_column(rule, &result_df, column_name) {
+                        Some(missing) => Err(missing),
+                        None => {
+                            table_rule
+                                .apply_async(result_df.clone(), column_name, self)
+                                .await
+                        }
+                    };
+                    (RuleKind::Table, table_rule.name(), column_name, applied)
+                }
+                RuleRef::Column(i) => {
+                    let (column_name, column_rule, _) = &self.column_rules[i];
+                    let applied = match self.check_column(rule, &result_df, column_name) {
+                        Some(missing) => Err(missing),
+                        None => {
+                            column_rule
+                                .apply_async(result_df.clone(), column_name, self)
+                                .await
+                        }
+                    };
+                    check_columns.push(rule_id.clone());
+                    (RuleKind::Column, column_rule.name(), column_name, applied)
+                }
+                RuleRef::Dataset(i) => {
+                    let (column_name, dataset_rule, _) = &self.dataset_rules[i];
+                    let mut explanation = RuleExplanation::new(
+                        rule_id,
+                        RuleKind::Dataset,
+                        dataset_rule.name(),
+                        Some(column_name),
+                    );
+                    // Only the row-level rule adds a column
+                    if let Some(row_rule) = dataset_rule.row_rule() {
+                        let output_column = row_rule.new_column_name(column_name);
+                        let applied = match self.check_column(rule, &result_df, column_name) {
+                            Some(missing) => Err(missing),
+                            None => {
+                                row_rule
+                                    .apply_async(result_df.clone(), column_name, self)
+                                    .await
+                            }
+                        };
+                        result_df = explanation.resolve(
+                            result_df,
+                            applied,
+                            output_column,
+                            ScalarValue::Boolean(None),
+                        )?;
+                    }
+                    // The outcome is a separate query over the input, planned but not run.
+                    // The column may only label the outcome, e.g. for row_count_between,
+                    // so a missing column is left to the plan to report.
+                    if explanation.is_valid() {
+                        let planned = match self.check_rule_type(rule, df.schema()) {
+                            Err(e) => Err(e),
+                            Ok(()) => match dataset_rule
+                                .evaluate_async(df.clone(), column_name, self)
+                                .await
+                            {
+                                Ok(outcome) => outcome
+                                    .into_optimized_plan()
+                                    .map(|_| ())
+                                    .map_err(ValidationError::from),
+                                Err(e) => Err(e),
+                            },
+                        };
+                        if let Err(e) = planned {
+                            explanation.add_error(e);
+                        }
+                    }
+                    rules.push(explanation);
+                    continue;
+                }
+            };
+
+            let mut explanation = RuleExplanation::new(rule_id, kind, rule_name, Some(column));
+            let output_column = explanation.rule_id.clone();
+            let placeholder = match kind {
+                RuleKind::Column => ScalarValue::Boolean(None),
+                _ => ScalarValue::Null,
+            };
+            result_df = explanation.resolve(result_df, applied, output_column, placeholder)?;
+            if kind == RuleKind::Column
+                && explanation.is_valid()
+                && explanation.output_type != Some(DataType::Boolean)
+            {
+                explanation.error = Some(format!(
+                    "Column rule produces {}, expected Boolean",
+                    explanation.output_type.as_ref().unwrap_or(&DataType::Null)
+                ));
+            }
+            rules.push(explanation);
+        }
+
+        let mut explanation = Explanation { rules, plan: None };
+        if explanation.is_valid() {
+            let pass = pass_expr(check_columns, &result_df)?;
+            let plan = result_df
+                .with_column("dfq_pass", pass)?
+                .explain(false, false)?
+                .collect()
+                .await?;
+            explanation.plan = Some(plan);
+        }
+        Ok(explanation)
+    }
+
+    /// The error for a target column of `rule` that is not in `df` or has a type
+    /// the rule does not support
+    fn check_column(
+        &self,
+        rule: RuleRef,
+        df: &DataFrame,
+        column_name: &str,
+    ) -> Option<ValidationError> {
+        missing_column(df, column_name).or_else(|| self.check_rule_type(rule, df.schema()).err())
+    }
+}
+
+/// The error for a target column that is not in `df`; nested paths are resolved
+/// by their rules
+fn missing_column(df: &DataFrame, column_name: &str) -> Option<ValidationError> {
+    let nested = column_name.contains(['.', '[']);
+    (!nested && !df.schema().has_column_with_unqualified_name(column_name)).then(|| {
+        ValidationError::ColumnNotFound {
+            column_name: column_name.to_string(),
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{column::*, dataset::*, schema::dfq_column_exists, table::*};
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::{Field, Schema};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    fn create_test_df() -> DataFrame {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+        ]);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2])),
+                Arc::new(StringArray::from(vec![Some("Alice"), None])),
+            ],
+        )
+        .unwrap();
+
+        SessionContext::new().read_batch(batch).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_explain() {
+        let df = create_test_df();
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("name", dfq_not_null())
+            .with_table_rule("id", dfq_count(), Some(dfq_gte(lit(1))));
+        let explanation = rule_set.explain(&df).await.unwrap();
+        assert!(explanation.is_valid());
+
+        let outputs: Vec<(RuleKind, Option<&str>, Option<&DataType>)> = explanation
+            .rules
+            .iter()
+            .map(|rule| {
+                (
+                    rule.kind,
+                    rule.output_column.as_deref(),
+                    rule.output_type.as_ref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            outputs,
+            vec![
+                (RuleKind::Table, Some("id_count"), Some(&DataType::Int64)),
+                (
+                    RuleKind::Column,
+                    Some("name_not_null"),
+                    Some(&DataType::Boolean)
+                ),
+                (
+                    RuleKind::Column,
+                    Some("id_count_greater_than_equals"),
+                    Some(&DataType::Boolean)
+                ),
+            ]
+        );
+        assert!(
+            explanation.rules[1]
+                .expr
+                .as_ref()
+                .unwrap()
+                .to_string()
+                .ends_with("name IS NOT NULL")
+        );
+        let plan = pretty_format_batches(explanation.plan.as_ref().unwrap())
+            .unwrap()
+            .to_string();
+        assert!(plan.contains("logical_plan"));
+        assert!(plan.contains("physical_plan"));
+
+        rule_set
+            .with_schema_rule(dfq_column_exists("email"))
+            .with_column_rule("email", dfq_is_email())
+            .with_column_rule("id", dfq_is_email())
+            .with_table_rule("email", dfq_max(), Some(dfq_not_null()));
+        let explanation = rule_set.explain(&df).await.unwrap();
+        assert!(!explanation.is_valid());
+        assert!(explanation.plan.is_none());
+
+        let invalid: Vec<(&str, &[String], bool)> = explanation
+            .invalid_rules()
+            .map(|rule| {
+                (
+                    rule.rule_id.as_str(),
+                    rule.missing_columns.as_slice(),
+                    rule.error.is_some(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            invalid,
+            vec![
+                ("column_exists", &["email".to_string()][..], false),
+                ("email_max", &["email".to_string()][..], false),
+                ("email_is_email", &["email".to_string()][..], false),
+                ("id_is_email", &[][..], true),
+            ]
+        );
+        // Rules on the output of a rule that does not resolve still resolve
+        assert!(
+            explanation
+                .rules
+                .iter()
+                .any(|rule| rule.rule_id == "email_max_not_null" && rule.is_valid())
+        );
+        assert!(explanation.to_string().contains("missing columns: email"));
+        // Types are checked before the rule is planned
+        let id_is_email = explanation
+            .rules
+            .iter()
+            .find(|rule| rule.rule_id == "id_is_email")
+            .unwrap();
+        assert_eq!(
+            id_is_email.error.as_deref(),
+            Some(
+                "Type mismatch: Rule 'is_email' on column 'id' expects a string column, got Int32"
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_explain_dataset_rules() {
+        let df = create_test_df();
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_dataset_rule("table", dfq_row_count_between(1, 10))
+            .with_dataset_rule("updated_at", dfq_freshness(Duration::from_secs(60)))
+            .with_dataset_rule("name", dfq_freshness(Duration::from_secs(60)))
+            .with_dataset_rule("name", dfq_mostly(dfq_not_null(), 0.5));
+        let explanation = rule_set.explain(&df).await.unwrap();
+
+        let invalid: Vec<(&str, &[String], bool)> = explanation
+            .invalid_rules()
+            .map(|rule| {
+                (
+                    rule.rule_id.as_str(),
+                    rule.missing_columns.as_slice(),
+                    rule.error.is_some(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            invalid,
+            vec![
+                (
+                    "updated_at_freshness",
+                    &["updated_at".to_string()][..],
+                    false
+                ),
+                ("name_freshness", &[][..], true),
+            ]
+        );
+    }
+}
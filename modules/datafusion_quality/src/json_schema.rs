@@ -0,0 +1,338 @@
+use crate::ValidationError;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Keywords that only describe a schema and never affect validation
+const ANNOTATIONS: &[&str] = &[
+    "$schema",
+    "$id",
+    "$comment",
+    "title",
+    "description",
+    "default",
+    "examples",
+];
+
+/// A JSON type as named by the `type` keyword
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JsonType {
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    String,
+    Array,
+    Object,
+}
+
+impl JsonType {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "null" => Some(JsonType::Null),
+            "boolean" => Some(JsonType::Boolean),
+            "integer" => Some(JsonType::Integer),
+            "number" => Some(JsonType::Number),
+            "string" => Some(JsonType::String),
+            "array" => Some(JsonType::Array),
+            "object" => Some(JsonType::Object),
+            _ => None,
+        }
+    }
+
+    fn matches(self, value: &Value) -> bool {
+        match (self, value) {
+            (JsonType::Null, Value::Null)
+            | (JsonType::Boolean, Value::Bool(_))
+            | (JsonType::Number, Value::Number(_))
+            | (JsonType::String, Value::String(_))
+            | (JsonType::Array, Value::Array(_))
+            | (JsonType::Object, Value::Object(_)) => true,
+            (JsonType::Integer, Value::Number(n)) => {
+                n.is_i64() || n.is_u64() || n.as_f64().is_some_and(|f| f.fract() == 0.0)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// A compiled JSON Schema, supporting the subset of the specification needed to
+/// check the shape of JSON blobs.
+///
+/// Supported keywords are `type`, `enum`, `const`, `required`, `properties`,
+/// `additionalProperties`, `items`, `minItems`, `maxItems`, `minimum`, `maximum`,
+/// `exclusiveMinimum`, `exclusiveMaximum`, `minLength` and `maxLength`, plus
+/// `true`/`false` schemas. Annotations such as `title` are ignored; any other
+/// keyword is rejected rather than silently skipped.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::json_schema::JsonSchema;
+/// use serde_json::json;
+///
+/// let schema = JsonSchema::new(&json!({
+///     "type": "object",
+///     "required": ["id"],
+///     "properties": {"id": {"type": "integer"}, "kind": {"enum": ["a", "b"]}}
+/// }))
+/// .unwrap();
+///
+/// assert!(schema.is_valid(&json!({"id": 1, "kind": "a"})));
+/// assert!(!schema.is_valid(&json!({"kind": "c"})));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonSchema {
+    /// `true` accepts every value, `false` none
+    Bool(bool),
+    Constraints(Box<Constraints>),
+}
+
+/// The keywords of a JSON Schema object, see [`JsonSchema`]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Constraints {
+    types: Option<Vec<JsonType>>,
+    enum_values: Option<Vec<Value>>,
+    const_value: Option<Value>,
+    required: Vec<String>,
+    properties: BTreeMap<String, JsonSchema>,
+    additional_properties: Option<JsonSchema>,
+    items: Option<JsonSchema>,
+    min_items: Option<usize>,
+    max_items: Option<usize>,
+    minimum: Option<f64>,
+    maximum: Option<f64>,
+    exclusive_minimum: Option<f64>,
+    exclusive_maximum: Option<f64>,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+}
+
+impl JsonSchema {
+    /// Compile a JSON Schema, returning a configuration error for malformed or
+    /// unsupported keywords
+    pub fn new(schema: &Value) -> Result<Self, ValidationError> {
+        Self::compile(schema, "#")
+    }
+
+    /// Parse and compile a JSON Schema from a string
+    pub fn parse(schema: &str) -> Result<Self, ValidationError> {
+        let schema: Value =
+            serde_json::from_str(schema).map_err(|e| ValidationError::Configuration {
+                message: format!("Invalid JSON Schema: {}", e),
+            })?;
+        Self::new(&schema)
+    }
+
+    fn compile(schema: &Value, path: &str) -> Result<Self, ValidationError> {
+        let keywords = match schema {
+            Value::Bool(accept) => return Ok(JsonSchema::Bool(*accept)),
+            Value::Object(keywords) => keywords,
+            _ => return Err(invalid(path, "a schema must be an object or a boolean")),
+        };
+
+        let mut constraints = Constraints::default();
+        for (keyword, value) in keywords {
+            let at = format!("{}/{}", path, keyword);
+            match keyword.as_str() {
+                "type" => {
+                    let names = match value {
+                        Value::String(name) => vec![name.as_str()],
+                        Value::Array(names) => names
+                            .iter()
+                            .map(|name| {
+                                name.as_str()
+                                    .ok_or_else(|| invalid(&at, "expected a type name"))
+                            })
+                            .collect::<Result<_, _>>()?,
+                        _ => return Err(invalid(&at, "expected a type name or an array of them")),
+                    };
+                    let types = names
+                        .into_iter()
+                        .map(|name| {
+                            JsonType::parse(name)
+                                .ok_or_else(|| invalid(&at, &format!("unknown type '{}'", name)))
+                        })
+                        .collect::<Result<_, _>>()?;
+                    constraints.types = Some(types);
+                }
+                "enum" => {
+                    let values = value
+                        .as_array()
+                        .ok_or_else(|| invalid(&at, "expected an array"))?;
+                    constraints.enum_values = Some(values.clone());
+                }
+                "const" => constraints.const_value = Some(value.clone()),
+                "required" => {
+                    constraints.required = value
+                        .as_array()
+                        .ok_or_else(|| invalid(&at, "expected an array of property names"))?
+                        .iter()
+                        .map(|name| {
+                            name.as_str()
+                                .map(str::to_string)
+                                .ok_or_else(|| invalid(&at, "expected a property name"))
+                        })
+                        .collect::<Result<_, _>>()?;
+                }
+                "properties" => {
+                    let properties = value
+                        .as_object()
+                        .ok_or_else(|| invalid(&at, "expected an object"))?;
+                    for (name, property) in properties {
+                        let schema = Self::compile(property, &format!("{}/{}", at, name))?;
+                        constraints.properties.insert(name.clone(), schema);
+                    }
+                }
+                "additionalProperties" => {
+                    constraints.additional_properties = Some(Self::compile(value, &at)?)
+                }
+                "items" => constraints.items = Some(Self::compile(value, &at)?),
+                "minItems" => constraints.min_items = Some(count(value, &at)?),
+                "maxItems" => constraints.max_items = Some(count(value, &at)?),
+                "minLength" => constraints.min_length = Some(count(value, &at)?),
+                "maxLength" => constraints.max_length = Some(count(value, &at)?),
+                "minimum" => constraints.minimum = Some(number(value, &at)?),
+                "maximum" => constraints.maximum = Some(number(value, &at)?),
+                "exclusiveMinimum" => constraints.exclusive_minimum = Some(number(value, &at)?),
+                "exclusiveMaximum" => constraints.exclusive_maximum = Some(number(value, &at)?),
+                keyword if ANNOTATIONS.contains(&keyword) => {}
+                _ => return Err(invalid(&at, "unsupported keyword")),
+            }
+        }
+        Ok(JsonSchema::Constraints(Box::new(constraints)))
+    }
+
+    /// Whether `value` conforms to the schema
+    pub fn is_valid(&self, value: &Value) -> bool {
+        match self {
+            JsonSchema::Bool(accept) => *accept,
+            JsonSchema::Constraints(constraints) => constraints.is_valid(value),
+        }
+    }
+}
+
+impl Constraints {
+    fn is_valid(&self, value: &Value) -> bool {
+        let type_ok = self
+            .types
+            .as_ref()
+            .is_none_or(|types| types.iter().any(|t| t.matches(value)));
+        let enum_ok = self
+            .enum_values
+            .as_ref()
+            .is_none_or(|values| values.contains(value));
+        let const_ok = self
+            .const_value
+            .as_ref()
+            .is_none_or(|expected| expected == value);
+        if !(type_ok && enum_ok && const_ok) {
+            return false;
+        }
+
+        match value {
+            Value::Object(object) => self.is_valid_object(object),
+            Value::Array(items) => {
+                self.min_items.is_none_or(|min| items.len() >= min)
+                    && self.max_items.is_none_or(|max| items.len() <= max)
+                    && self
+                        .items
+                        .as_ref()
+                        .is_none_or(|schema| items.iter().all(|item| schema.is_valid(item)))
+            }
+            Value::String(s) => {
+                let length = s.chars().count();
+                self.min_length.is_none_or(|min| length >= min)
+                    && self.max_length.is_none_or(|max| length <= max)
+            }
+            Value::Number(n) => {
+                let n = n.as_f64().unwrap_or(f64::NAN);
+                self.minimum.is_none_or(|min| n >= min)
+                    && self.maximum.is_none_or(|max| n <= max)
+                    && self.exclusive_minimum.is_none_or(|min| n > min)
+                    && self.exclusive_maximum.is_none_or(|max| n < max)
+            }
+            Value::Null | Value::Bool(_) => true,
+        }
+    }
+
+    fn is_valid_object(&self, object: &Map<String, Value>) -> bool {
+        self.required.iter().all(|name| object.contains_key(name))
+            && object.iter().all(|(name, value)| {
+                match (self.properties.get(name), &self.additional_properties) {
+                    (Some(schema), _) | (None, Some(schema)) => schema.is_valid(value),
+                    (None, None) => true,
+                }
+            })
+    }
+}
+
+fn invalid(path: &str, reason: &str) -> ValidationError {
+    ValidationError::Configuration {
+        message: format!("Invalid JSON Schema at '{}': {}", path, reason),
+    }
+}
+
+fn count(value: &Value, path: &str) -> Result<usize, ValidationError> {
+    value
+        .as_u64()
+        .and_then(|n| usize::try_from(n).ok())
+        .ok_or_else(|| invalid(path, "expected a non-negative integer"))
+}
+
+fn number(value: &Value, path: &str) -> Result<f64, ValidationError> {
+    value
+        .as_f64()
+        .ok_or_else(|| invalid(path, "expected a number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_json_schema() {
+        let schema = JsonSchema::new(&json!({
+            "title": "event",
+            "type": "object",
+            "required": ["id", "kind"],
+            "properties": {
+                "id": {"type": "integer", "minimum": 1},
+                "kind": {"enum": ["click", "view"]},
+                "tags": {"type": "array", "items": {"type": "string", "maxLength": 3}},
+                "score": {"type": ["number", "null"], "exclusiveMaximum": 1}
+            },
+            "additionalProperties": false
+        }))
+        .unwrap();
+
+        assert!(schema.is_valid(&json!({"id": 1, "kind": "click"})));
+        assert!(schema.is_valid(&json!({"id": 2.0, "kind": "view", "tags": ["a"], "score": null})));
+        // Missing required key
+        assert!(!schema.is_valid(&json!({"id": 1})));
+        // Wrong types and out of range values
+        assert!(!schema.is_valid(&json!({"id": "1", "kind": "click"})));
+        assert!(!schema.is_valid(&json!({"id": 1.5, "kind": "click"})));
+        assert!(!schema.is_valid(&json!({"id": 0, "kind": "click"})));
+        assert!(!schema.is_valid(&json!({"id": 1, "kind": "scroll"})));
+        assert!(!schema.is_valid(&json!({"id": 1, "kind": "click", "tags": ["long"]})));
+        assert!(!schema.is_valid(&json!({"id": 1, "kind": "click", "score": 1})));
+        // Unknown property
+        assert!(!schema.is_valid(&json!({"id": 1, "kind": "click", "other": true})));
+        assert!(!schema.is_valid(&json!([1, 2])));
+
+        assert!(
+            JsonSchema::new(&json!(true))
+                .unwrap()
+                .is_valid(&json!(null))
+        );
+        assert!(matches!(
+            JsonSchema::new(&json!({"type": "object", "pattern": "^a"})),
+            Err(ValidationError::Configuration { .. })
+        ));
+        assert!(matches!(
+            JsonSchema::new(&json!({"type": "float"})),
+            Err(ValidationError::Configuration { .. })
+        ));
+    }
+}
@@ -1,18 +1,118 @@
+pub mod clock;
+pub mod context;
 pub mod error;
+pub mod evaluation;
+pub mod explain;
+pub mod json_schema;
+pub mod metrics;
+pub mod reconcile;
+pub mod registry;
+pub mod report;
 pub mod rules;
+pub mod sample;
+pub mod sink;
+pub mod spec;
+pub mod sql;
+pub mod template;
+pub mod types;
+pub mod udf;
 
+pub use crate::context::RuleContext;
 use crate::error::ValidationError;
-use datafusion::{common::DFSchema, logical_expr::ExprSchemable, prelude::*};
+pub use crate::types::SupportedTypes;
+use datafusion::{common::DFSchema, prelude::*};
 use error::DataFusionSnafu;
+use futures::future::BoxFuture;
 use snafu::ResultExt;
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 /// The main RuleSet struct that holds the context and rules
+///
+/// Every registered rule carries tags and dependencies, see [`RuleSet::with_tags`]
+/// and [`RuleSet::with_depends_on`].
 #[derive(Clone, Default)]
 pub struct RuleSet {
-    pub(crate) schema_rules: Vec<Arc<dyn SchemaRule>>,
-    pub(crate) column_rules: Vec<(String, Arc<dyn ColumnRule>)>,
-    pub(crate) table_rules: Vec<(String, Arc<dyn TableRule>)>,
+    pub(crate) schema_rules: Vec<(Arc<dyn SchemaRule>, RuleMeta)>,
+    pub(crate) column_rules: Vec<(String, Arc<dyn ColumnRule>, RuleMeta)>,
+    pub(crate) table_rules: Vec<(String, Arc<dyn TableRule>, RuleMeta)>,
+    pub(crate) dataset_rules: Vec<(String, Arc<dyn DatasetRule>, RuleMeta)>,
+    /// Run metadata and reference data passed to every rule
+    context: RuleContext,
+    /// The rules registered by the last `with_*` call, updated by `with_tags`
+    /// and `with_depends_on`
+    last_added: Vec<RuleRef>,
+}
+
+/// Tags and dependencies of a registered rule
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct RuleMeta {
+    pub(crate) tags: Vec<String>,
+    /// Ids of the rules that must pass before this rule is evaluated
+    pub(crate) depends_on: Vec<String>,
+}
+
+/// The position of a registered rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum RuleRef {
+    Schema(usize),
+    Column(usize),
+    Table(usize),
+    Dataset(usize),
+}
+
+/// Which rules [`RuleSet::apply_with`] runs
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::ApplyOptions;
+///
+/// // Cheap checks owned by the finance team
+/// let options = ApplyOptions::new()
+///     .with_tags(["finance"])
+///     .without_tags(["expensive"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApplyOptions {
+    tags: Option<Vec<String>>,
+    exclude_tags: Vec<String>,
+}
+
+impl ApplyOptions {
+    /// Run every rule
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only run rules with at least one of `tags`
+    pub fn with_tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.tags
+            .get_or_insert_with(Vec::new)
+            .extend(tags.into_iter().map(Into::into));
+        self
+    }
+
+    /// Skip rules with any of `tags`, even if selected by [`ApplyOptions::with_tags`]
+    pub fn without_tags<I, S>(mut self, tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.exclude_tags.extend(tags.into_iter().map(Into::into));
+        self
+    }
+
+    fn selects(&self, rule_tags: &[String]) -> bool {
+        let included = self
+            .tags
+            .as_ref()
+            .is_none_or(|tags| tags.iter().any(|tag| rule_tags.contains(tag)));
+        included && !self.exclude_tags.iter().any(|tag| rule_tags.contains(tag))
+    }
 }
 
 impl std::fmt::Debug for RuleSet {
@@ -21,6 +121,8 @@ impl std::fmt::Debug for RuleSet {
             .field("schema_rules", &self.schema_rules)
             .field("column_rules", &self.column_rules)
             .field("table_rules", &self.table_rules)
+            .field("dataset_rules", &self.dataset_rules)
+            .field("context", &self.context)
             .finish_non_exhaustive()
     }
 }
@@ -29,7 +131,9 @@ impl std::fmt::Debug for RuleSet {
 pub trait SchemaRule: Send + Sync + std::fmt::Debug {
     /// Validate the schema
     fn validate_schema(&self, _schema: &DFSchema) -> Result<bool, ValidationError> {
-        unimplemented!("validate_schema must be implemented")
+        Err(ValidationError::AsyncOnly {
+            rule_name: self.name().to_string(),
+        })
     }
 
     /// Validate the schema with access to the RuleSet
@@ -41,6 +145,16 @@ pub trait SchemaRule: Send + Sync + std::fmt::Debug {
         self.validate_schema(schema)
     }
 
+    /// Validate the schema asynchronously, e.g. against a schema fetched with a query.
+    /// This is what [`RuleSet::apply`] calls.
+    fn validate_schema_async<'a>(
+        &'a self,
+        schema: &'a DFSchema,
+        rule_set: &'a RuleSet,
+    ) -> BoxFuture<'a, Result<bool, ValidationError>> {
+        Box::pin(async move { self.validate_schema_with_ruleset(schema, rule_set) })
+    }
+
     /// Get the name of the rule
     fn name(&self) -> &str;
 
@@ -52,7 +166,9 @@ pub trait SchemaRule: Send + Sync + std::fmt::Debug {
 pub trait ColumnRule: Send + Sync + std::fmt::Debug {
     /// Apply the rule to a DataFrame, adding a new column
     fn apply(&self, _df: DataFrame, _column_name: &str) -> Result<DataFrame, ValidationError> {
-        unimplemented!("apply must be implemented")
+        Err(ValidationError::AsyncOnly {
+            rule_name: self.name().to_string(),
+        })
     }
 
     /// Apply the rule to a DataFrame with access to the RuleSet
@@ -65,6 +181,23 @@ pub trait ColumnRule: Send + Sync + std::fmt::Debug {
         self.apply(df, column_name)
     }
 
+    /// Apply the rule asynchronously, so it can `collect()` intermediate results,
+    /// e.g. a reference table, while building its check. This is what
+    /// [`RuleSet::apply`] calls.
+    ///
+    /// Rules wrapping other rules, such as [`dfq_when`](crate::rules::conditional::dfq_when)
+    /// or [`dfq_not`](crate::rules::combinator::dfq_not), await this method of the
+    /// wrapped rules, so a rule may implement only `apply_async`. Its synchronous
+    /// methods then return [`ValidationError::AsyncOnly`].
+    fn apply_async<'a>(
+        &'a self,
+        df: DataFrame,
+        column_name: &'a str,
+        rule_set: &'a RuleSet,
+    ) -> BoxFuture<'a, Result<DataFrame, ValidationError>> {
+        Box::pin(async move { self.apply_with_ruleset(df, column_name, rule_set) })
+    }
+
     /// Get the name of the rule
     fn name(&self) -> &str;
 
@@ -73,13 +206,20 @@ pub trait ColumnRule: Send + Sync + std::fmt::Debug {
 
     /// Get the description of the rule
     fn description(&self) -> &str;
+
+    /// The types of the column the rule supports, checked before it is applied
+    fn supported_types(&self) -> SupportedTypes {
+        SupportedTypes::Any
+    }
 }
 
 /// Trait for table-level aggregate rules
 pub trait TableRule: Send + Sync + std::fmt::Debug {
     /// Apply the rule to a DataFrame, adding a new column with aggregated results
     fn apply(&self, _df: DataFrame, _column_name: &str) -> Result<DataFrame, ValidationError> {
-        unimplemented!("apply must be implemented")
+        Err(ValidationError::AsyncOnly {
+            rule_name: self.name().to_string(),
+        })
     }
 
     /// Apply the rule to a DataFrame with access to the RuleSet
@@ -92,6 +232,16 @@ pub trait TableRule: Send + Sync + std::fmt::Debug {
         self.apply(df, column_name)
     }
 
+    /// Apply the rule asynchronously, see [`ColumnRule::apply_async`]
+    fn apply_async<'a>(
+        &'a self,
+        df: DataFrame,
+        column_name: &'a str,
+        rule_set: &'a RuleSet,
+    ) -> BoxFuture<'a, Result<DataFrame, ValidationError>> {
+        Box::pin(async move { self.apply_with_ruleset(df, column_name, rule_set) })
+    }
+
     /// Get the name of the rule
     fn name(&self) -> &str;
 
@@ -100,6 +250,63 @@ pub trait TableRule: Send + Sync + std::fmt::Debug {
 
     /// Get the description of the rule
     fn description(&self) -> &str;
+
+    /// The types of the column the rule supports, see [`ColumnRule::supported_types`]
+    fn supported_types(&self) -> SupportedTypes {
+        SupportedTypes::Any
+    }
+}
+
+/// Trait for dataset-level rules, evaluated once per DataFrame
+///
+/// Dataset rules are reported separately from the row-level checks by
+/// [`RuleSet::dataset_outcomes`] and are not part of `dfq_pass`.
+pub trait DatasetRule: Send + Sync + std::fmt::Debug {
+    /// Evaluate the rule, returning a single row with a Utf8 `observed` column
+    /// and a Boolean `passed` column
+    fn evaluate(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError>;
+
+    /// Evaluate the rule with access to the RuleSet, e.g. its [`RuleContext`]
+    fn evaluate_with_ruleset(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        _rule_set: &RuleSet,
+    ) -> Result<DataFrame, ValidationError> {
+        self.evaluate(df, column_name)
+    }
+
+    /// Evaluate the rule asynchronously, see [`ColumnRule::apply_async`]. This is
+    /// what [`RuleSet::evaluate`] calls.
+    fn evaluate_async<'a>(
+        &'a self,
+        df: DataFrame,
+        column_name: &'a str,
+        rule_set: &'a RuleSet,
+    ) -> BoxFuture<'a, Result<DataFrame, ValidationError>> {
+        Box::pin(async move { self.evaluate_with_ruleset(df, column_name, rule_set) })
+    }
+
+    /// Get the name of the rule
+    fn name(&self) -> &str;
+
+    /// Get the name of the outcome
+    fn new_column_name(&self, column_name: &str) -> String;
+
+    /// Get the description of the rule
+    fn description(&self) -> &str;
+
+    /// A column rule whose row-level result [`RuleSet::apply`] adds next to the
+    /// other checks, without making it part of `dfq_pass`
+    fn row_rule(&self) -> Option<Arc<dyn ColumnRule>> {
+        None
+    }
+
+    /// The types of the column the rule supports, by default those of its row rule
+    fn supported_types(&self) -> SupportedTypes {
+        self.row_rule()
+            .map_or(SupportedTypes::Any, |rule| rule.supported_types())
+    }
 }
 
 impl RuleSet {
@@ -109,12 +316,43 @@ impl RuleSet {
             schema_rules: Vec::new(),
             column_rules: Vec::new(),
             table_rules: Vec::new(),
+            dataset_rules: Vec::new(),
+            context: RuleContext::default(),
+            last_added: Vec::new(),
         }
     }
 
+    /// Set the context passed to every rule
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use datafusion::prelude::*;
+    /// use datafusion_quality::{RuleContext, RuleSet};
+    ///
+    /// let ctx = SessionContext::new();
+    /// let mut ruleset = RuleSet::new();
+    /// ruleset.with_context(
+    ///     RuleContext::new()
+    ///         .with_session_state(ctx.state())
+    ///         .with_run_date("2024-01-31")
+    ///         .with_variable("region", "eu"),
+    /// );
+    /// ```
+    pub fn with_context(&mut self, context: RuleContext) -> &mut Self {
+        self.context = context;
+        self
+    }
+
+    /// The context passed to every rule
+    pub fn context(&self) -> &RuleContext {
+        &self.context
+    }
+
     /// Add a schema rule
     pub fn with_schema_rule(&mut self, rule: Arc<dyn SchemaRule>) -> &mut Self {
-        self.schema_rules.push(rule);
+        self.last_added = vec![RuleRef::Schema(self.schema_rules.len())];
+        self.schema_rules.push((rule, RuleMeta::default()));
         self
     }
 
@@ -125,7 +363,9 @@ impl RuleSet {
         rule: Arc<dyn ColumnRule>,
     ) -> &mut Self {
         let column_name = column_name.as_ref().to_string();
-        self.column_rules.push((column_name, rule));
+        self.last_added = vec![RuleRef::Column(self.column_rules.len())];
+        self.column_rules
+            .push((column_name, rule, RuleMeta::default()));
         self
     }
 
@@ -137,62 +377,262 @@ impl RuleSet {
         check: Option<Arc<dyn ColumnRule>>,
     ) -> &mut Self {
         let column_name = column_name.as_ref().to_string();
+        self.last_added = vec![RuleRef::Table(self.table_rules.len())];
         if let Some(check) = check {
             let column_name = table_rule.new_column_name(&column_name);
-            self.column_rules.push((column_name, check));
+            self.last_added
+                .push(RuleRef::Column(self.column_rules.len()));
+            self.column_rules
+                .push((column_name, check, RuleMeta::default()));
         }
-        self.table_rules.push((column_name, table_rule));
+        self.table_rules
+            .push((column_name, table_rule, RuleMeta::default()));
         self
     }
 
-    pub async fn apply_table_rules(&self, df: DataFrame) -> Result<DataFrame, ValidationError> {
-        let mut result_df = df;
-        for (column_name, rule) in &self.table_rules {
-            result_df = rule.apply_with_ruleset(result_df, column_name, self)?;
-        }
-        Ok(result_df)
+    /// Add a dataset rule
+    pub fn with_dataset_rule(
+        &mut self,
+        column_name: impl AsRef<str>,
+        rule: Arc<dyn DatasetRule>,
+    ) -> &mut Self {
+        let column_name = column_name.as_ref().to_string();
+        self.last_added = vec![RuleRef::Dataset(self.dataset_rules.len())];
+        self.dataset_rules
+            .push((column_name, rule, RuleMeta::default()));
+        self
     }
 
-    /// Apply all rules to a DataFrame
-    pub async fn apply(&self, df: &DataFrame) -> Result<DataFrame, ValidationError> {
-        // First validate schema
-        for rule in &self.schema_rules {
-            if !rule.validate_schema(df.schema())? {
-                return Err(ValidationError::Schema {
-                    message: format!("Schema rule '{}' failed", rule.name()),
-                });
-            }
+    /// Tag the rules added by the previous `with_*` call. A table rule and its
+    /// check are tagged together.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use datafusion_quality::RuleSet;
+    /// use datafusion_quality::rules::{column::*, table::*};
+    /// use datafusion::prelude::*;
+    ///
+    /// let mut ruleset = RuleSet::new();
+    /// ruleset
+    ///     .with_column_rule("email", dfq_is_email())
+    ///     .with_tags(["pii", "smoke"])
+    ///     .with_table_rule("amount", dfq_stddev(), Some(dfq_lt(lit(1000.0))))
+    ///     .with_tags(["finance", "expensive"]);
+    /// ```
+    pub fn with_tags<I, S>(&mut self, tags: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let tags: Vec<String> = tags.into_iter().map(Into::into).collect();
+        for rule in self.last_added.clone() {
+            self.meta_mut(rule).tags.extend(tags.iter().cloned());
         }
+        self
+    }
 
-        let mut result_df = df.clone();
-        // Apply table calculations
-        result_df = self.apply_table_rules(result_df).await?;
+    /// Declare that the rules added by the previous `with_*` call only run when
+    /// the rules with the given ids pass.
+    ///
+    /// A rule's id is its result column, e.g. `email_not_null`, `score_avg` or
+    /// `email_not_null_pass_rate` for a dataset rule, and the rule name for schema
+    /// rules, e.g. `column_exists`. A dependency on an id shared by several rules
+    /// requires all of them to pass. A column rule passes when every row passes.
+    ///
+    /// Rules whose prerequisites fail or are skipped are skipped themselves: their
+    /// result column is null and not part of `dfq_pass`, and they are reported as
+    /// skipped by [`RuleSet::evaluate`] and [`RuleSet::report`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use datafusion_quality::RuleSet;
+    /// use datafusion_quality::rules::{column::*, dataset::dfq_mostly, schema::*};
+    ///
+    /// let mut ruleset = RuleSet::new();
+    /// ruleset
+    ///     .with_schema_rule(dfq_column_exists("email"))
+    ///     .with_dataset_rule("email", dfq_mostly(dfq_not_null(), 0.1))
+    ///     .with_depends_on(["column_exists"])
+    ///     // No point checking the format of a mostly null column
+    ///     .with_column_rule("email", dfq_is_email())
+    ///     .with_depends_on(["email_not_null_pass_rate"]);
+    /// ```
+    pub fn with_depends_on<I, S>(&mut self, rule_ids: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let rule_ids: Vec<String> = rule_ids.into_iter().map(Into::into).collect();
+        for rule in self.last_added.clone() {
+            self.meta_mut(rule)
+                .depends_on
+                .extend(rule_ids.iter().cloned());
+        }
+        self
+    }
 
-        let mut check_columns = Vec::new();
+    fn meta_mut(&mut self, rule: RuleRef) -> &mut RuleMeta {
+        match rule {
+            RuleRef::Schema(i) => &mut self.schema_rules[i].1,
+            RuleRef::Column(i) => &mut self.column_rules[i].2,
+            RuleRef::Table(i) => &mut self.table_rules[i].2,
+            RuleRef::Dataset(i) => &mut self.dataset_rules[i].2,
+        }
+    }
 
-        // Then apply column rules
-        for (column_name, rule) in &self.column_rules {
-            result_df = rule.apply_with_ruleset(result_df, column_name, self)?;
-            check_columns.push(rule.new_column_name(column_name));
+    /// Tag every rule in the RuleSet, e.g. with the name of the team owning it
+    /// before merging it into a larger RuleSet with [`RuleSet::extend`]
+    pub fn tag_all<I, S>(&mut self, tags: I) -> &mut Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let tags: Vec<String> = tags.into_iter().map(Into::into).collect();
+        let metas = self
+            .schema_rules
+            .iter_mut()
+            .map(|(_, meta)| meta)
+            .chain(self.column_rules.iter_mut().map(|(_, _, meta)| meta))
+            .chain(self.table_rules.iter_mut().map(|(_, _, meta)| meta))
+            .chain(self.dataset_rules.iter_mut().map(|(_, _, meta)| meta));
+        for meta in metas {
+            meta.tags.extend(tags.iter().cloned());
         }
+        self
+    }
+
+    /// Append the rules of another RuleSet, keeping their tags and this RuleSet's context
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use datafusion_quality::RuleSet;
+    /// use datafusion_quality::rules::column::*;
+    /// use datafusion::prelude::*;
+    ///
+    /// let mut finance = RuleSet::new();
+    /// finance.with_column_rule("amount", dfq_gt(lit(0)));
+    /// finance.tag_all(["finance"]);
+    ///
+    /// let mut ruleset = RuleSet::new();
+    /// ruleset.with_column_rule("id", dfq_not_null());
+    /// ruleset.extend(&finance);
+    /// ```
+    pub fn extend(&mut self, other: &RuleSet) -> &mut Self {
+        self.schema_rules.extend(other.schema_rules.iter().cloned());
+        self.column_rules.extend(other.column_rules.iter().cloned());
+        self.table_rules.extend(other.table_rules.iter().cloned());
+        self.dataset_rules
+            .extend(other.dataset_rules.iter().cloned());
+        self.last_added.clear();
+        self
+    }
 
-        let dq_pass_col = check_columns
+    /// A RuleSet with only the rules selected by `options`
+    ///
+    /// The rules that selected rules depend on are kept too, transitively, even
+    /// if `options` does not select them.
+    pub fn select(&self, options: &ApplyOptions) -> RuleSet {
+        let mut keep: HashSet<RuleRef> = self
+            .rule_refs()
             .into_iter()
-            .map(|col_name| {
-                col(col_name)
-                    .cast_to(&arrow::datatypes::DataType::Boolean, result_df.schema())
-                    .map_err(|e| ValidationError::Column {
-                        message: format!("Error casting column to boolean: {}", e),
-                    })
-            })
-            .reduce(|acc, col| Ok(acc?.and(col?)))
-            .unwrap_or(Ok(lit(true)))?;
+            .filter(|&rule| options.selects(&self.meta(rule).tags))
+            .collect();
+        let mut pending: Vec<RuleRef> = keep.iter().copied().collect();
+        while let Some(rule) = pending.pop() {
+            for id in &self.meta(rule).depends_on {
+                for dependency in self.rule_refs() {
+                    if self.rule_id(dependency) == *id && keep.insert(dependency) {
+                        pending.push(dependency);
+                    }
+                }
+            }
+        }
 
-        result_df = result_df.with_column("dfq_pass", dq_pass_col)?;
+        RuleSet {
+            schema_rules: self
+                .schema_rules
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| keep.contains(&RuleRef::Schema(*i)))
+                .map(|(_, rule)| rule.clone())
+                .collect(),
+            column_rules: self
+                .column_rules
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| keep.contains(&RuleRef::Column(*i)))
+                .map(|(_, rule)| rule.clone())
+                .collect(),
+            table_rules: self
+                .table_rules
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| keep.contains(&RuleRef::Table(*i)))
+                .map(|(_, rule)| rule.clone())
+                .collect(),
+            dataset_rules: self
+                .dataset_rules
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| keep.contains(&RuleRef::Dataset(*i)))
+                .map(|(_, rule)| rule.clone())
+                .collect(),
+            context: self.context.clone(),
+            last_added: Vec::new(),
+        }
+    }
 
+    /// A RuleSet with only the rules having at least one of `tags`
+    pub fn filter_by_tags<I, S>(&self, tags: I) -> RuleSet
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.select(&ApplyOptions::new().with_tags(tags))
+    }
+
+    pub async fn apply_table_rules(&self, df: DataFrame) -> Result<DataFrame, ValidationError> {
+        let mut result_df = df;
+        for (column_name, rule, _) in &self.table_rules {
+            result_df = rule.apply_async(result_df, column_name, self).await?;
+        }
         Ok(result_df)
     }
 
+    /// Apply all rules to a DataFrame
+    ///
+    /// Rules run in the order of their dependencies, see [`RuleSet::with_depends_on`].
+    /// Otherwise schema rules run first, then table rules, column rules and the
+    /// row-level results of dataset rules, each in the order they were added.
+    pub async fn apply(&self, df: &DataFrame) -> Result<DataFrame, ValidationError> {
+        Ok(self.evaluate_rules(df, false).await?.df)
+    }
+
+    /// Apply only the rules selected by `options`, e.g. smoke checks in CI
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use datafusion_quality::{ApplyOptions, RuleSet};
+    /// use datafusion::prelude::*;
+    ///
+    /// # async fn example(ruleset: RuleSet, df: DataFrame) -> Result<(), Box<dyn std::error::Error>> {
+    /// let options = ApplyOptions::new().with_tags(["smoke"]).without_tags(["expensive"]);
+    /// let validated = ruleset.apply_with(&df, &options).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn apply_with(
+        &self,
+        df: &DataFrame,
+        options: &ApplyOptions,
+    ) -> Result<DataFrame, ValidationError> {
+        self.select(options).apply(df).await
+    }
+
     pub async fn partition(
         &self,
         df: &DataFrame,
@@ -223,7 +663,7 @@ impl RuleSet {
             table_rules_names.extend(extra_columns.iter().map(|s| col(*s)));
         }
 
-        for (column_name, rule) in &self.table_rules {
+        for (column_name, rule, _) in &self.table_rules {
             table_rules_names.push(col(rule.new_column_name(column_name)));
         }
 
@@ -236,6 +676,7 @@ mod tests {
     use super::*;
     use crate::rules::column::*;
     use crate::rules::table::*;
+    use arrow::array::AsArray;
     use arrow::record_batch::RecordBatch;
     use datafusion::arrow::array::{Float64Array, Int32Array, StringArray};
     use datafusion::arrow::datatypes::{DataType, Field, Schema};
@@ -378,4 +819,249 @@ mod tests {
 
         assert_batches_eq!(&expected, &stats_df.collect().await.unwrap());
     }
+
+    #[tokio::test]
+    async fn test_tags() {
+        let (_, df) = create_test_df().await;
+
+        let mut finance = RuleSet::new();
+        finance
+            .with_table_rule("score", dfq_avg(), Some(dfq_gt(lit(90.0))))
+            .with_tags(["expensive"])
+            .with_column_rule("score", dfq_in_range(80.0, 100.0));
+        finance.tag_all(["finance"]);
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("name", dfq_not_null())
+            .with_tags(["pii", "smoke"])
+            .with_column_rule("age", dfq_gte(lit(18)))
+            .extend(&finance);
+
+        let columns = |rule_set: &RuleSet| -> Vec<String> {
+            rule_set
+                .column_rules
+                .iter()
+                .map(|(column_name, rule, _)| rule.new_column_name(column_name))
+                .collect()
+        };
+
+        assert_eq!(
+            columns(&rule_set.filter_by_tags(["smoke"])),
+            vec!["name_not_null"]
+        );
+        assert_eq!(
+            columns(&rule_set.filter_by_tags(["finance"])),
+            vec!["score_avg_greater_than", "score_in_range"]
+        );
+
+        // Cheap finance checks only: the table rule and its check are both skipped
+        let options = ApplyOptions::new()
+            .with_tags(["finance"])
+            .without_tags(["expensive"]);
+        let selected = rule_set.select(&options);
+        assert!(selected.table_rules.is_empty());
+        assert_eq!(columns(&selected), vec!["score_in_range"]);
+
+        let result = rule_set
+            .apply_with(&df, &options)
+            .await
+            .unwrap()
+            .select_columns(&["id", "score_in_range", "dfq_pass"])
+            .unwrap();
+
+        let expected = vec![
+            "+----+----------------+----------+",
+            "| id | score_in_range | dfq_pass |",
+            "+----+----------------+----------+",
+            "| 1  | true           | true     |",
+            "| 2  | true           | true     |",
+            "| 3  | false          | false    |",
+            "| 4  | true           | true     |",
+            "| 5  | true           | true     |",
+            "+----+----------------+----------+",
+        ];
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+    }
+
+    #[test]
+    fn test_select_keeps_dependencies() {
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_schema_rule(crate::rules::schema::dfq_column_exists("email"))
+            .with_column_rule("email", dfq_not_null())
+            .with_depends_on(["column_exists"])
+            .with_column_rule("email", dfq_is_email())
+            .with_tags(["smoke"])
+            .with_depends_on(["email_not_null"])
+            .with_column_rule("name", dfq_not_null());
+
+        let selected = rule_set.filter_by_tags(["smoke"]);
+        let ids: Vec<String> = selected
+            .rule_refs()
+            .into_iter()
+            .map(|rule| selected.rule_id(rule))
+            .collect();
+        assert_eq!(
+            ids,
+            vec!["column_exists", "email_not_null", "email_is_email"]
+        );
+        assert!(selected.evaluation_order().is_ok());
+    }
+
+    /// Checks values exist in a reference table, collected while building the check
+    #[derive(Debug)]
+    struct InReferenceRule {
+        reference: DataFrame,
+    }
+
+    impl ColumnRule for InReferenceRule {
+        fn apply_async<'a>(
+            &'a self,
+            df: DataFrame,
+            column_name: &'a str,
+            _rule_set: &'a RuleSet,
+        ) -> BoxFuture<'a, Result<DataFrame, ValidationError>> {
+            Box::pin(async move {
+                let batches = self.reference.clone().collect().await?;
+                let mut values = Vec::new();
+                for batch in &batches {
+                    let names = batch.column(0).as_string::<i32>();
+                    values.extend(names.iter().flatten().map(lit));
+                }
+                df.with_column(
+                    &self.new_column_name(column_name),
+                    col(column_name).in_list(values, false),
+                )
+                .context(DataFusionSnafu)
+            })
+        }
+
+        fn name(&self) -> &str {
+            "in_reference"
+        }
+
+        fn new_column_name(&self, column_name: &str) -> String {
+            format!("{}_{}", column_name, self.name())
+        }
+
+        fn description(&self) -> &str {
+            "Checks values exist in a reference table"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_async_rule() {
+        let (ctx, df) = create_test_df().await;
+        let reference = ctx
+            .sql("SELECT * FROM (VALUES ('Alice'), ('Charlie')) AS t(name)")
+            .await
+            .unwrap();
+
+        let mut rule_set = RuleSet::new();
+        rule_set.with_column_rule("name", Arc::new(InReferenceRule { reference }));
+
+        let result = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .select_columns(&["id", "name_in_reference", "dfq_pass"])
+            .unwrap();
+
+        let expected = vec![
+            "+----+-------------------+----------+",
+            "| id | name_in_reference | dfq_pass |",
+            "+----+-------------------+----------+",
+            "| 1  | true              | true     |",
+            "| 2  | false             | false    |",
+            "| 3  |                   |          |",
+            "| 4  | true              | true     |",
+            "| 5  | false             | false    |",
+            "+----+-------------------+----------+",
+        ];
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_wrapped_async_rule() {
+        use crate::rules::{
+            combinator::{dfq_all, dfq_not},
+            conditional::dfq_when,
+            dataset::dfq_mostly,
+            nested::dfq_nested,
+        };
+
+        let (ctx, df) = create_test_df().await;
+        let reference = ctx
+            .sql("SELECT * FROM (VALUES ('Alice'), ('Charlie')) AS t(name)")
+            .await
+            .unwrap();
+        let in_reference = || -> Arc<dyn ColumnRule> {
+            Arc::new(InReferenceRule {
+                reference: reference.clone(),
+            })
+        };
+
+        // The rule only implements apply_async
+        assert!(matches!(
+            in_reference().apply(df.clone(), "name"),
+            Err(ValidationError::AsyncOnly { .. })
+        ));
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("name", dfq_not(in_reference()))
+            .with_column_rule("name", dfq_when(col("age").gt(lit(20)), in_reference()))
+            .with_dataset_rule("name", dfq_mostly(dfq_all(vec![in_reference()]), 0.5));
+        let evaluation = rule_set.evaluate(&df).await.unwrap();
+        assert_eq!(
+            evaluation.dataset_outcomes[0].observed.as_deref(),
+            Some("0.4")
+        );
+        assert!(!evaluation.dataset_outcomes[0].passed);
+
+        let mut nested = RuleSet::new();
+        nested.with_column_rule("name", dfq_nested(in_reference()));
+        let result = evaluation
+            .df
+            .join(
+                nested
+                    .apply(&df)
+                    .await
+                    .unwrap()
+                    .select(vec![
+                        col("id").alias("nested_id"),
+                        col("name_in_reference").alias("nested"),
+                    ])
+                    .unwrap(),
+                JoinType::Inner,
+                &["id"],
+                &["nested_id"],
+                None,
+            )
+            .unwrap()
+            .select_columns(&[
+                "id",
+                "name_not_in_reference",
+                "name_in_reference",
+                "name_all_in_reference",
+                "nested",
+            ])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+
+        let expected = vec![
+            "+----+-----------------------+-------------------+-----------------------+--------+",
+            "| id | name_not_in_reference | name_in_reference | name_all_in_reference | nested |",
+            "+----+-----------------------+-------------------+-----------------------+--------+",
+            "| 1  | false                 | true              | true                  | true   |",
+            "| 2  | true                  | false             | false                 | false  |",
+            "| 3  |                       | true              |                       |        |",
+            "| 4  | false                 | true              | true                  | true   |",
+            "| 5  | true                  | true              | false                 | false  |",
+            "+----+-----------------------+-------------------+-----------------------+--------+",
+        ];
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+    }
 }
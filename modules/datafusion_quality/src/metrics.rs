@@ -0,0 +1,553 @@
+use crate::{ColumnRule, RuleSet, ValidationError, error::DataFusionSnafu};
+use arrow::{
+    array::{Array, AsArray},
+    datatypes::{DataType, Float64Type},
+};
+use datafusion::{functions::math::expr_fn::abs, prelude::*};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+use std::{
+    fmt::Debug,
+    fs::OpenOptions,
+    io::{BufRead, BufReader, Write},
+    path::PathBuf,
+    sync::{Arc, Mutex, PoisonError},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// A single table metric recorded for one run
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricRecord {
+    /// The dataset the metric was computed on
+    pub dataset: String,
+    /// The table rule output column, e.g. `score_avg`
+    pub metric: String,
+    /// The name of the table rule
+    pub rule_name: String,
+    /// The metric value, cast to Float64; None if the aggregate was null
+    pub value: Option<f64>,
+    /// Milliseconds since the Unix epoch when the run was recorded
+    pub timestamp_ms: i64,
+    pub run_id: Option<String>,
+}
+
+/// Storage for table metrics across runs
+pub trait MetricsStore: Send + Sync + Debug {
+    /// Persist the metrics of a run
+    fn append(&self, records: &[MetricRecord]) -> Result<(), ValidationError>;
+
+    /// The most recent `lookback` records of a metric, oldest first
+    fn history(
+        &self,
+        dataset: &str,
+        metric: &str,
+        lookback: usize,
+    ) -> Result<Vec<MetricRecord>, ValidationError>;
+}
+
+fn latest(mut records: Vec<MetricRecord>, lookback: usize) -> Vec<MetricRecord> {
+    records.sort_by_key(|r| r.timestamp_ms);
+    let skip = records.len().saturating_sub(lookback);
+    records.split_off(skip)
+}
+
+/// A MetricsStore kept in memory, e.g. for tests
+#[derive(Debug, Default)]
+pub struct InMemoryMetricsStore {
+    records: Mutex<Vec<MetricRecord>>,
+}
+
+impl InMemoryMetricsStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl MetricsStore for InMemoryMetricsStore {
+    fn append(&self, records: &[MetricRecord]) -> Result<(), ValidationError> {
+        self.records
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .extend_from_slice(records);
+        Ok(())
+    }
+
+    fn history(
+        &self,
+        dataset: &str,
+        metric: &str,
+        lookback: usize,
+    ) -> Result<Vec<MetricRecord>, ValidationError> {
+        let records = self
+            .records
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .filter(|r| r.dataset == dataset && r.metric == metric)
+            .cloned()
+            .collect();
+        Ok(latest(records, lookback))
+    }
+}
+
+/// A MetricsStore appending one JSON record per line to a local file
+#[derive(Debug)]
+pub struct JsonFileMetricsStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl JsonFileMetricsStore {
+    /// Creates a store backed by `path`; the file is created on the first append
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    fn io_error(&self, e: std::io::Error) -> ValidationError {
+        ValidationError::Configuration {
+            message: format!("Metrics store '{}': {}", self.path.display(), e),
+        }
+    }
+}
+
+impl MetricsStore for JsonFileMetricsStore {
+    fn append(&self, records: &[MetricRecord]) -> Result<(), ValidationError> {
+        let _guard = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .map_err(|e| self.io_error(e))?;
+
+        for record in records {
+            let line = serde_json::to_string(record).map_err(|e| ValidationError::Validation {
+                message: format!("Error serializing metric: {}", e),
+            })?;
+            writeln!(file, "{}", line).map_err(|e| self.io_error(e))?;
+        }
+        Ok(())
+    }
+
+    fn history(
+        &self,
+        dataset: &str,
+        metric: &str,
+        lookback: usize,
+    ) -> Result<Vec<MetricRecord>, ValidationError> {
+        let _guard = self.lock.lock().unwrap_or_else(PoisonError::into_inner);
+        let file = match std::fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(self.io_error(e)),
+        };
+
+        let mut records = Vec::new();
+        for line in BufReader::new(file).lines() {
+            let line = line.map_err(|e| self.io_error(e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: MetricRecord =
+                serde_json::from_str(&line).map_err(|e| ValidationError::Configuration {
+                    message: format!(
+                        "Metrics store '{}' has an invalid record: {}",
+                        self.path.display(),
+                        e
+                    ),
+                })?;
+            if record.dataset == dataset && record.metric == metric {
+                records.push(record);
+            }
+        }
+        Ok(latest(records, lookback))
+    }
+}
+
+impl RuleSet {
+    /// Compute the table rule metrics of a run and persist them to a MetricsStore.
+    ///
+    /// Each table rule output column (e.g. `score_avg`) is recorded as a metric of
+    /// `dataset`, cast to Float64.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use datafusion_quality::RuleSet;
+    /// use datafusion_quality::metrics::JsonFileMetricsStore;
+    /// use datafusion_quality::rules::table::dfq_avg;
+    /// use datafusion::prelude::*;
+    ///
+    /// # async fn example(df: DataFrame) -> Result<(), Box<dyn std::error::Error>> {
+    /// let store = JsonFileMetricsStore::new("metrics.jsonl");
+    /// let mut ruleset = RuleSet::new();
+    /// ruleset.with_table_rule("score", dfq_avg(), None);
+    ///
+    /// ruleset.record_metrics(&df, &store, "orders", Some("2024-01-01")).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn record_metrics(
+        &self,
+        df: &DataFrame,
+        store: &dyn MetricsStore,
+        dataset: &str,
+        run_id: Option<&str>,
+    ) -> Result<Vec<MetricRecord>, ValidationError> {
+        if self.table_rules.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let table_df = self.apply_table_rules(df.clone()).await?;
+        let metrics: Vec<(String, &str)> = self
+            .table_rules
+            .iter()
+            .map(|(column_name, rule, _)| (rule.new_column_name(column_name), rule.name()))
+            .collect();
+
+        let batches = table_df
+            .select(
+                metrics
+                    .iter()
+                    .map(|(metric, _)| cast(ident(metric), DataType::Float64))
+                    .collect(),
+            )?
+            .limit(0, Some(1))?
+            .collect()
+            .await
+            .context(DataFusionSnafu)?;
+
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| i64::try_from(d.as_millis()).unwrap_or(i64::MAX))
+            .unwrap_or_default();
+
+        let batch = batches.iter().find(|b| b.num_rows() > 0);
+        let records: Vec<MetricRecord> = metrics
+            .into_iter()
+            .enumerate()
+            .map(|(i, (metric, rule_name))| {
+                let value = batch.and_then(|batch| {
+                    let values = batch.column(i).as_primitive::<Float64Type>();
+                    (!values.is_null(0)).then(|| values.value(0))
+                });
+                MetricRecord {
+                    dataset: dataset.to_string(),
+                    metric,
+                    rule_name: rule_name.to_string(),
+                    value,
+                    timestamp_ms,
+                    run_id: run_id.map(str::to_string),
+                }
+            })
+            .collect();
+
+        store.append(&records)?;
+        Ok(records)
+    }
+}
+
+/// Rule that checks a table metric against its mean over previous runs
+#[derive(Debug, Clone)]
+pub struct MetricChangeRule {
+    store: Arc<dyn MetricsStore>,
+    dataset: String,
+    max_change_pct: f64,
+    lookback: usize,
+}
+
+impl MetricChangeRule {
+    /// Creates a new MetricChangeRule
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - The store holding previous runs
+    /// * `dataset` - The dataset the metrics were recorded for
+    /// * `max_change_pct` - The allowed change from the baseline, in percent
+    /// * `lookback` - The number of previous runs averaged into the baseline
+    pub fn new(
+        store: Arc<dyn MetricsStore>,
+        dataset: &str,
+        max_change_pct: f64,
+        lookback: usize,
+    ) -> Self {
+        Self {
+            store,
+            dataset: dataset.to_string(),
+            max_change_pct,
+            lookback,
+        }
+    }
+}
+
+impl ColumnRule for MetricChangeRule {
+    // The history is read from the store, see apply_async
+    fn apply_async<'a>(
+        &'a self,
+        df: DataFrame,
+        column_name: &'a str,
+        _rule_set: &'a RuleSet,
+    ) -> BoxFuture<'a, Result<DataFrame, ValidationError>> {
+        Box::pin(async move {
+            let baseline: Vec<f64> = self
+                .store
+                .history(&self.dataset, column_name, self.lookback)?
+                .into_iter()
+                .filter_map(|r| r.value)
+                .collect();
+
+            // Without history there is nothing to drift from
+            let expr = if baseline.is_empty() {
+                lit(true)
+            } else {
+                let runs = f64::from(u32::try_from(baseline.len()).unwrap_or(u32::MAX));
+                let mean = baseline.iter().sum::<f64>() / runs;
+                abs(cast(ident(column_name), DataType::Float64) - lit(mean))
+                    .lt_eq(lit(mean.abs() * self.max_change_pct / 100.0))
+            };
+
+            df.with_column(&self.new_column_name(column_name), expr)
+                .context(DataFusionSnafu)
+        })
+    }
+
+    fn name(&self) -> &str {
+        "metric_change_within"
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", column_name, self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Checks that a table metric is within a percentage of its mean over previous runs"
+    }
+}
+
+/// Creates a rule that fails when a table metric drifts more than `max_change_pct`
+/// percent from its mean over the last `lookback` recorded runs.
+///
+/// Register it as the check of a table rule so it compares that rule's output
+/// column, e.g. `score_avg`, against the history written by
+/// [`RuleSet::record_metrics`]. The rule passes when there is no history.
+///
+/// The history is read when the rule set is applied, so a metric recorded earlier
+/// in the same run counts towards its own baseline. Call `record_metrics` after
+/// applying the rule set to compare a run against previous runs only.
+///
+/// # Arguments
+///
+/// * `store` - The store holding previous runs
+/// * `dataset` - The dataset the metrics were recorded for
+/// * `max_change_pct` - The allowed change from the baseline, in percent
+/// * `lookback` - The number of previous runs averaged into the baseline
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::metrics::{dfq_metric_change_within, InMemoryMetricsStore};
+/// use datafusion_quality::rules::table::dfq_avg;
+/// use datafusion_quality::RuleSet;
+/// use std::sync::Arc;
+///
+/// let store = Arc::new(InMemoryMetricsStore::new());
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_table_rule(
+///     "score",
+///     dfq_avg(),
+///     Some(dfq_metric_change_within(store, "orders", 10.0, 7)),
+/// );
+/// ```
+pub fn dfq_metric_change_within(
+    store: Arc<dyn MetricsStore>,
+    dataset: &str,
+    max_change_pct: f64,
+    lookback: usize,
+) -> Arc<MetricChangeRule> {
+    Arc::new(MetricChangeRule::new(
+        store,
+        dataset,
+        max_change_pct,
+        lookback,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::table::*;
+    use arrow::array::{Float64Array, Int32Array};
+    use arrow::datatypes::{Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion::assert_batches_eq;
+
+    fn create_test_df(scores: Vec<f64>) -> DataFrame {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("score", DataType::Float64, true),
+        ]);
+
+        let ids = (1..=i32::try_from(scores.len()).unwrap()).collect::<Vec<_>>();
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(ids)),
+                Arc::new(Float64Array::from(scores)),
+            ],
+        )
+        .unwrap();
+
+        SessionContext::new().read_batch(batch).unwrap()
+    }
+
+    fn record(metric: &str, value: f64, timestamp_ms: i64) -> MetricRecord {
+        MetricRecord {
+            dataset: "orders".to_string(),
+            metric: metric.to_string(),
+            rule_name: "avg".to_string(),
+            value: Some(value),
+            timestamp_ms,
+            run_id: None,
+        }
+    }
+
+    async fn metric_change_passes(rule_set: &RuleSet, score: f64) -> bool {
+        let result = rule_set
+            .apply(&create_test_df(vec![score]))
+            .await
+            .unwrap()
+            .select_columns(&["score_avg_metric_change_within"])
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        result[0].column(0).as_boolean().value(0)
+    }
+
+    #[tokio::test]
+    async fn test_record_metrics() {
+        let path = std::env::temp_dir().join(format!("dfq_metrics_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let store = JsonFileMetricsStore::new(&path);
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_table_rule("score", dfq_avg(), None)
+            .with_table_rule("id", dfq_count(), None);
+
+        let df = create_test_df(vec![10.0, 20.0, 30.0]);
+        rule_set
+            .record_metrics(&df, &store, "orders", Some("run-1"))
+            .await
+            .unwrap();
+        rule_set
+            .record_metrics(&df, &store, "customers", None)
+            .await
+            .unwrap();
+
+        let history = store.history("orders", "score_avg", 10).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].value, Some(20.0));
+        assert_eq!(history[0].run_id.as_deref(), Some("run-1"));
+
+        let history = store.history("orders", "id_count", 10).unwrap();
+        assert_eq!(history[0].value, Some(3.0));
+
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_history_lookback() {
+        let store = InMemoryMetricsStore::new();
+        store
+            .append(&[
+                record("score_avg", 3.0, 3),
+                record("score_avg", 1.0, 1),
+                record("score_avg", 2.0, 2),
+                record("id_count", 9.0, 4),
+            ])
+            .unwrap();
+
+        let values: Vec<_> = store
+            .history("orders", "score_avg", 2)
+            .unwrap()
+            .into_iter()
+            .map(|r| r.value.unwrap())
+            .collect();
+        assert_eq!(values, vec![2.0, 3.0]);
+    }
+
+    #[tokio::test]
+    async fn test_metric_change_within() {
+        let store = Arc::new(InMemoryMetricsStore::new());
+
+        let mut rule_set = RuleSet::new();
+        rule_set.with_table_rule(
+            "score",
+            dfq_avg(),
+            Some(dfq_metric_change_within(store.clone(), "orders", 10.0, 3)),
+        );
+
+        // No history yet
+        let result = rule_set
+            .apply(&create_test_df(vec![100.0]))
+            .await
+            .unwrap()
+            .select_columns(&["score_avg", "score_avg_metric_change_within"])
+            .unwrap();
+        let expected = vec![
+            "+-----------+--------------------------------+",
+            "| score_avg | score_avg_metric_change_within |",
+            "+-----------+--------------------------------+",
+            "| 100.0     | true                           |",
+            "+-----------+--------------------------------+",
+        ];
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        store
+            .append(&[record("score_avg", 18.0, 1), record("score_avg", 22.0, 2)])
+            .unwrap();
+
+        for (scores, pass) in [(vec![21.0, 21.0], true), (vec![10.0, 40.0], false)] {
+            let result = rule_set
+                .apply(&create_test_df(scores))
+                .await
+                .unwrap()
+                .select_columns(&["score_avg_metric_change_within"])
+                .unwrap()
+                .limit(0, Some(1))
+                .unwrap()
+                .collect()
+                .await
+                .unwrap();
+            let values = result[0].column(0).as_boolean();
+            assert_eq!(values.value(0), pass);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metric_change_within_same_run() {
+        let store = Arc::new(InMemoryMetricsStore::new());
+        store.append(&[record("score_avg", 22.0, 1)]).unwrap();
+
+        let mut rule_set = RuleSet::new();
+        rule_set.with_table_rule(
+            "score",
+            dfq_avg(),
+            Some(dfq_metric_change_within(store.clone(), "orders", 10.0, 1)),
+        );
+
+        // Compared against the previous run only
+        assert!(!metric_change_passes(&rule_set, 30.0).await);
+
+        // Recorded first, the run becomes its own baseline
+        rule_set
+            .record_metrics(&create_test_df(vec![30.0]), store.as_ref(), "orders", None)
+            .await
+            .unwrap();
+        assert!(metric_change_passes(&rule_set, 30.0).await);
+    }
+}
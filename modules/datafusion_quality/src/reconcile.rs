@@ -0,0 +1,467 @@
+use crate::{ValidationError, error::DataFusionSnafu, udf::dfq_hash};
+use arrow::datatypes::DataType;
+use datafusion::{
+    common::{Column, JoinType, ScalarValue},
+    functions_aggregate::expr_fn::{count, sum},
+    logical_expr::{Operator, binary_expr},
+    prelude::*,
+};
+use snafu::ResultExt;
+
+const SOURCE: &str = "__dfq_source";
+const TARGET: &str = "__dfq_target";
+
+/// The discrepancies between a source and a target DataFrame, see [`reconcile`]
+#[derive(Debug, Clone)]
+pub struct Reconciliation {
+    /// Source rows whose key has no match in the target
+    pub missing_in_target: DataFrame,
+    /// Target rows whose key has no match in the source
+    pub missing_in_source: DataFrame,
+    /// One row per key and differing column: the key columns, `column_name`,
+    /// `source_value` and `target_value`, with values cast to strings
+    pub mismatched: DataFrame,
+    /// One row per compared column: `column_name`, `source_count`, `target_count`,
+    /// `source_sum`, `target_sum` (numeric columns only, as strings), `source_hash`,
+    /// `target_hash` and `matches`. Integer and decimal sums are exact and part of
+    /// `matches`; float sums are rounded and only informative
+    pub checksums: DataFrame,
+}
+
+impl Reconciliation {
+    /// Whether the target equals the source: nothing missing, nothing mismatched
+    /// and all checksums matching
+    pub async fn is_match(&self) -> Result<bool, ValidationError> {
+        for df in [
+            &self.missing_in_target,
+            &self.missing_in_source,
+            &self.mismatched,
+        ] {
+            if df.clone().count().await.context(DataFusionSnafu)? > 0 {
+                return Ok(false);
+            }
+        }
+        let failed_checksums = self
+            .checksums
+            .clone()
+            .filter(col("matches").is_not_true())?
+            .count()
+            .await
+            .context(DataFusionSnafu)?;
+        Ok(failed_checksums == 0)
+    }
+}
+
+fn qualified(relation: &str, name: &str) -> Expr {
+    Expr::Column(Column::new(Some(relation), name))
+}
+
+fn is_distinct_from(left: Expr, right: Expr) -> Expr {
+    binary_expr(left, Operator::IsDistinctFrom, right)
+}
+
+/// Compare two DataFrames row-for-row by key.
+///
+/// The non-key columns present in both DataFrames are compared; nulls compare
+/// equal to nulls. Rows with a null key never match. Each discrepancy class is
+/// returned as a lazy DataFrame, so nothing is executed until it is collected.
+///
+/// # Arguments
+///
+/// * `source` - The expected data
+/// * `target` - The data to check against the source
+/// * `keys` - The columns identifying a row in both DataFrames
+///
+/// # Examples
+///
+/// ```no_run
+/// use datafusion_quality::reconcile::reconcile;
+/// use datafusion::prelude::*;
+///
+/// # async fn example(source: DataFrame, target: DataFrame) -> Result<(), Box<dyn std::error::Error>> {
+/// let reconciliation = reconcile(&source, &target, &["id"]).await?;
+/// if !reconciliation.is_match().await? {
+///     reconciliation.mismatched.show().await?;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn reconcile(
+    source: &DataFrame,
+    target: &DataFrame,
+    keys: &[&str],
+) -> Result<Reconciliation, ValidationError> {
+    if keys.is_empty() {
+        return Err(ValidationError::Configuration {
+            message: "Reconciliation requires at least one key column".to_string(),
+        });
+    }
+    for df in [source, target] {
+        for key in keys {
+            if !df.schema().has_column_with_unqualified_name(key) {
+                return Err(ValidationError::ColumnNotFound {
+                    column_name: key.to_string(),
+                });
+            }
+        }
+    }
+
+    let compared: Vec<(String, DataType)> = source
+        .schema()
+        .fields()
+        .iter()
+        .filter(|field| !keys.contains(&field.name().as_str()))
+        .filter(|field| {
+            target
+                .schema()
+                .has_column_with_unqualified_name(field.name())
+        })
+        .map(|field| (field.name().clone(), field.data_type().clone()))
+        .collect();
+
+    let source_df = source.clone().alias(SOURCE)?;
+    let target_df = target.clone().alias(TARGET)?;
+    let key_match = |left: &str, right: &str| -> Vec<Expr> {
+        keys.iter()
+            .map(|key| qualified(left, key).eq(qualified(right, key)))
+            .collect()
+    };
+
+    let missing_in_target = source_df.clone().join_on(
+        target_df.clone(),
+        JoinType::LeftAnti,
+        key_match(SOURCE, TARGET),
+    )?;
+    let missing_in_source = target_df.clone().join_on(
+        source_df.clone(),
+        JoinType::LeftAnti,
+        key_match(TARGET, SOURCE),
+    )?;
+
+    let matched = source_df.join_on(target_df, JoinType::Inner, key_match(SOURCE, TARGET))?;
+    let mismatch = |column_name: &str| -> Result<DataFrame, ValidationError> {
+        let source_value = qualified(SOURCE, column_name);
+        let target_value = qualified(TARGET, column_name);
+        let mut select: Vec<Expr> = keys
+            .iter()
+            .map(|key| qualified(SOURCE, key).alias(*key))
+            .collect();
+        select.extend([
+            lit(column_name).alias("column_name"),
+            cast(source_value.clone(), DataType::Utf8).alias("source_value"),
+            cast(target_value.clone(), DataType::Utf8).alias("target_value"),
+        ]);
+        matched
+            .clone()
+            .filter(is_distinct_from(source_value, target_value))?
+            .select(select)
+            .context(DataFusionSnafu)
+    };
+
+    let mismatched = match compared.split_first() {
+        Some(((first, _), rest)) => rest
+            .iter()
+            .try_fold(mismatch(first)?, |acc, (column_name, _)| {
+                acc.union(mismatch(column_name)?).context(DataFusionSnafu)
+            })?,
+        // Nothing to compare, but keep the schema
+        None => {
+            let mut select: Vec<Expr> = keys
+                .iter()
+                .map(|key| qualified(SOURCE, key).alias(*key))
+                .collect();
+            select.extend(
+                ["column_name", "source_value", "target_value"]
+                    .map(|name| lit(ScalarValue::Utf8(None)).alias(name)),
+            );
+            matched.filter(lit(false))?.select(select)?
+        }
+    };
+
+    let checksums = match compared.split_first() {
+        Some((first, rest)) => {
+            let checksum = |(column_name, data_type): &(String, DataType)| {
+                column_checksum(source, target, column_name, data_type)
+            };
+            rest.iter().try_fold(checksum(first)?, |acc, column| {
+                acc.union(checksum(column)?).context(DataFusionSnafu)
+            })?
+        }
+        None => column_checksum(source, target, keys[0], &DataType::Null)?.filter(lit(false))?,
+    };
+
+    Ok(Reconciliation {
+        missing_in_target,
+        missing_in_source,
+        mismatched,
+        checksums,
+    })
+}
+
+/// Count, sum and order-independent checksum of hashes of a column on both sides
+fn column_checksum(
+    source: &DataFrame,
+    target: &DataFrame,
+    column_name: &str,
+    data_type: &DataType,
+) -> Result<DataFrame, ValidationError> {
+    // Float sums depend on the order of the rows, so only exact sums are compared
+    let exact_sum = data_type.is_integer()
+        || matches!(
+            data_type,
+            DataType::Decimal128(..) | DataType::Decimal256(..)
+        );
+
+    let aggregates = |df: &DataFrame, side: &str| -> Result<DataFrame, ValidationError> {
+        let value = col(Column::from_name(column_name));
+        let total = match sum_type(data_type) {
+            Some(sum_type) => sum(cast(value.clone(), sum_type)),
+            // sum() of a null literal still plans as an aggregate
+            None => sum(lit(ScalarValue::Float64(None))),
+        };
+        df.clone()
+            .aggregate(
+                vec![],
+                vec![
+                    count(value.clone()).alias(format!("{}_count", side)),
+                    total.alias(format!("{}_sum", side)),
+                    // Unlike bit_xor, the sum does not cancel out duplicated values.
+                    // Decimal128(38, 0) holds the sum of ~10^19 Int64 hashes exactly
+                    sum(cast(dfq_hash(value), DataType::Decimal128(38, 0)))
+                        .alias(format!("{}_hash", side)),
+                ],
+            )
+            .context(DataFusionSnafu)
+    };
+
+    let mut matches = col("source_count")
+        .eq(col("target_count"))
+        .and(is_distinct_from(col("source_hash"), col("target_hash")).not());
+    if exact_sum {
+        matches = matches.and(is_distinct_from(col("source_sum"), col("target_sum")).not());
+    }
+
+    aggregates(source, "source")?
+        .join(
+            aggregates(target, "target")?,
+            JoinType::Inner,
+            &[],
+            &[],
+            None,
+        )?
+        .select(vec![
+            lit(column_name).alias("column_name"),
+            col("source_count"),
+            col("target_count"),
+            // Sums of different columns have different types
+            cast(col("source_sum"), DataType::Utf8).alias("source_sum"),
+            cast(col("target_sum"), DataType::Utf8).alias("target_sum"),
+            col("source_hash"),
+            col("target_hash"),
+            matches.alias("matches"),
+        ])
+        .context(DataFusionSnafu)
+}
+
+/// The type a numeric column is summed as; integers are summed exactly as
+/// Decimal128(38, 0) and decimal sums widen their precision
+fn sum_type(data_type: &DataType) -> Option<DataType> {
+    match data_type {
+        t if t.is_integer() => Some(DataType::Decimal128(38, 0)),
+        DataType::Decimal128(..) | DataType::Decimal256(..) => Some(data_type.clone()),
+        t if t.is_numeric() => Some(DataType::Float64),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Float64Array, Int32Array, StringArray};
+    use arrow::datatypes::{Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion::assert_batches_sorted_eq;
+    use std::sync::Arc;
+
+    fn create_df(
+        ctx: &SessionContext,
+        ids: Vec<i32>,
+        names: Vec<Option<&str>>,
+        scores: Vec<f64>,
+    ) -> DataFrame {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("score", DataType::Float64, true),
+        ]);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(ids)),
+                Arc::new(StringArray::from(names)),
+                Arc::new(Float64Array::from(scores)),
+            ],
+        )
+        .unwrap();
+
+        ctx.read_batch(batch).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_reconcile() {
+        let ctx = SessionContext::new();
+        let source = create_df(
+            &ctx,
+            vec![1, 2, 3, 4],
+            vec![Some("Alice"), Some("Bob"), None, Some("Dave")],
+            vec![85.5, 92.0, 78.5, 60.0],
+        );
+        let target = create_df(
+            &ctx,
+            vec![1, 2, 3, 5],
+            vec![Some("Alice"), Some("Robert"), None, Some("Eve")],
+            vec![85.5, 92.0, 79.0, 70.0],
+        );
+
+        let reconciliation = reconcile(&source, &target, &["id"]).await.unwrap();
+        assert!(!reconciliation.is_match().await.unwrap());
+
+        let expected = vec![
+            "+----+------+-------+",
+            "| id | name | score |",
+            "+----+------+-------+",
+            "| 4  | Dave | 60.0  |",
+            "+----+------+-------+",
+        ];
+        assert_batches_sorted_eq!(
+            &expected,
+            &reconciliation.missing_in_target.collect().await.unwrap()
+        );
+
+        let expected = vec![
+            "+----+------+-------+",
+            "| id | name | score |",
+            "+----+------+-------+",
+            "| 5  | Eve  | 70.0  |",
+            "+----+------+-------+",
+        ];
+        assert_batches_sorted_eq!(
+            &expected,
+            &reconciliation.missing_in_source.collect().await.unwrap()
+        );
+
+        let expected = vec![
+            "+----+-------------+--------------+--------------+",
+            "| id | column_name | source_value | target_value |",
+            "+----+-------------+--------------+--------------+",
+            "| 2  | name        | Bob          | Robert       |",
+            "| 3  | score       | 78.5         | 79.0         |",
+            "+----+-------------+--------------+--------------+",
+        ];
+        assert_batches_sorted_eq!(
+            &expected,
+            &reconciliation.mismatched.collect().await.unwrap()
+        );
+
+        let checksums = reconciliation
+            .checksums
+            .select_columns(&["column_name", "source_count", "target_count", "matches"])
+            .unwrap();
+        let expected = vec![
+            "+-------------+--------------+--------------+---------+",
+            "| column_name | source_count | target_count | matches |",
+            "+-------------+--------------+--------------+---------+",
+            "| name        | 3            | 3            | false   |",
+            "| score       | 4            | 4            | false   |",
+            "+-------------+--------------+--------------+---------+",
+        ];
+        assert_batches_sorted_eq!(&expected, &checksums.collect().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_identical() {
+        let ctx = SessionContext::new();
+        let source = create_df(
+            &ctx,
+            vec![1, 2],
+            vec![Some("Alice"), None],
+            vec![85.5, 92.0],
+        );
+        // Same rows in a different order
+        let target = create_df(
+            &ctx,
+            vec![2, 1],
+            vec![None, Some("Alice")],
+            vec![92.0, 85.5],
+        );
+
+        let reconciliation = reconcile(&source, &target, &["id"]).await.unwrap();
+        assert!(reconciliation.is_match().await.unwrap());
+
+        assert!(matches!(
+            reconcile(&source, &target, &["missing"]).await,
+            Err(ValidationError::ColumnNotFound { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_duplicates() {
+        // Duplicated values must not cancel out in the checksum
+        let ctx = SessionContext::new();
+        let source = create_df(&ctx, vec![1, 2], vec![Some("x"), Some("x")], vec![1.0, 1.0]);
+        let target = create_df(&ctx, vec![1, 2], vec![Some("y"), Some("y")], vec![1.0, 1.0]);
+
+        let reconciliation = reconcile(&source, &target, &["id"]).await.unwrap();
+        let checksums = reconciliation
+            .checksums
+            .select_columns(&["column_name", "matches"])
+            .unwrap();
+        let expected = vec![
+            "+-------------+---------+",
+            "| column_name | matches |",
+            "+-------------+---------+",
+            "| name        | false   |",
+            "| score       | true    |",
+            "+-------------+---------+",
+        ];
+        assert_batches_sorted_eq!(&expected, &checksums.collect().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_reconcile_sums() {
+        let ctx = SessionContext::new();
+        let source = ctx
+            .sql(
+                "SELECT * FROM (VALUES (1, 9007199254740993, 0.1), (2, 1, 0.2), (3, 0, 0.3)) \
+                 AS t(id, amount, score)",
+            )
+            .await
+            .unwrap();
+        // The same rows in another order
+        let target = ctx
+            .sql(
+                "SELECT * FROM (VALUES (3, 0, 0.3), (2, 1, 0.2), (1, 9007199254740993, 0.1)) \
+                 AS t(id, amount, score)",
+            )
+            .await
+            .unwrap();
+
+        let reconciliation = reconcile(&source, &target, &["id"]).await.unwrap();
+        assert!(reconciliation.is_match().await.unwrap());
+
+        let checksums = reconciliation
+            .checksums
+            .select_columns(&["column_name", "source_sum", "target_sum", "matches"])
+            .unwrap();
+        let expected = vec![
+            "+-------------+--------------------+--------------------+---------+",
+            "| column_name | source_sum         | target_sum         | matches |",
+            "+-------------+--------------------+--------------------+---------+",
+            "| amount      | 9007199254740994   | 9007199254740994   | true    |",
+            "| score       | 0.6000000000000001 | 0.6000000000000001 | true    |",
+            "+-------------+--------------------+--------------------+---------+",
+        ];
+        assert_batches_sorted_eq!(&expected, &checksums.collect().await.unwrap());
+    }
+}
@@ -0,0 +1,727 @@
+use crate::{
+    ColumnRule, DatasetRule, SchemaRule, TableRule, ValidationError,
+    spec::{ColumnRuleSpec, DatasetRuleKind, SchemaRuleSpec, TableRuleKind},
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::DeserializeOwned};
+use serde_json::{Map, Value};
+use std::{collections::BTreeMap, sync::Arc};
+use strum::VariantNames;
+
+/// Builds a rule from its spec parameters, with access to the registry for nested rules
+pub type RuleConstructor<T> =
+    Arc<dyn Fn(&Value, &RuleRegistry) -> Result<Arc<T>, ValidationError> + Send + Sync>;
+
+/// Maps rule names used in spec files to constructors
+///
+/// [`RuleRegistry::new`] is pre-populated with every built-in `dfq_*` rule, named
+/// after its function without the prefix as in [`RuleSetSpec`](crate::spec::RuleSetSpec).
+/// Registering a name again replaces its constructor, including built-in ones.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::registry::{RuleRegistry, parse_params};
+/// use datafusion_quality::rules::column::dfq_str_length;
+/// use datafusion_quality::spec::RuleSetSpec;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct SkuParams {
+///     length: u32,
+/// }
+///
+/// let mut registry = RuleRegistry::new();
+/// registry.register_column_rule("sku", |params, _| {
+///     let params: SkuParams = parse_params("sku", params)?;
+///     Ok(dfq_str_length(Some(params.length), Some(params.length)))
+/// });
+///
+/// let spec = RuleSetSpec::from_yaml(r#"
+/// columns:
+///   - column: sku
+///     rules:
+///       - not_null
+///       - sku: { length: 8 }
+/// "#).unwrap();
+/// let ruleset = spec.to_rule_set_with(&registry).unwrap();
+/// ```
+#[derive(Clone)]
+pub struct RuleRegistry {
+    schema_rules: BTreeMap<String, RuleConstructor<dyn SchemaRule>>,
+    column_rules: BTreeMap<String, RuleConstructor<dyn ColumnRule>>,
+    table_rules: BTreeMap<String, RuleConstructor<dyn TableRule>>,
+    dataset_rules: BTreeMap<String, RuleConstructor<dyn DatasetRule>>,
+}
+
+impl std::fmt::Debug for RuleRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RuleRegistry")
+            .field("schema_rules", &self.schema_rules.keys())
+            .field("column_rules", &self.column_rules.keys())
+            .field("table_rules", &self.table_rules.keys())
+            .field("dataset_rules", &self.dataset_rules.keys())
+            .finish()
+    }
+}
+
+impl Default for RuleRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RuleRegistry {
+    /// Create a registry with every built-in rule
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        for name in builtin_names(SchemaRuleSpec::VARIANTS) {
+            registry.register_schema_rule(name, move |params, registry| {
+                builtin_spec::<SchemaRuleSpec>(name, params)?.builtin_rule(registry)
+            });
+        }
+        for name in builtin_names(ColumnRuleSpec::VARIANTS) {
+            registry.register_column_rule(name, move |params, registry| {
+                builtin_spec::<ColumnRuleSpec>(name, params)?.builtin_rule(registry)
+            });
+        }
+        for name in builtin_names(TableRuleKind::VARIANTS) {
+            registry.register_table_rule(name, move |params, registry| {
+                builtin_spec::<TableRuleKind>(name, params)?.builtin_rule(registry)
+            });
+        }
+        for name in builtin_names(DatasetRuleKind::VARIANTS) {
+            registry.register_dataset_rule(name, move |params, registry| {
+                builtin_spec::<DatasetRuleKind>(name, params)?.builtin_rule(registry)
+            });
+        }
+        registry
+    }
+
+    /// Create a registry without any rules
+    pub fn empty() -> Self {
+        Self {
+            schema_rules: BTreeMap::new(),
+            column_rules: BTreeMap::new(),
+            table_rules: BTreeMap::new(),
+            dataset_rules: BTreeMap::new(),
+        }
+    }
+
+    /// Register a schema rule constructor under `name`
+    pub fn register_schema_rule<F>(&mut self, name: impl Into<String>, constructor: F) -> &mut Self
+    where
+        F: Fn(&Value, &RuleRegistry) -> Result<Arc<dyn SchemaRule>, ValidationError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.schema_rules.insert(name.into(), Arc::new(constructor));
+        self
+    }
+
+    /// Register a column rule constructor under `name`
+    pub fn register_column_rule<F>(&mut self, name: impl Into<String>, constructor: F) -> &mut Self
+    where
+        F: Fn(&Value, &RuleRegistry) -> Result<Arc<dyn ColumnRule>, ValidationError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.column_rules.insert(name.into(), Arc::new(constructor));
+        self
+    }
+
+    /// Register a table rule constructor under `name`
+    pub fn register_table_rule<F>(&mut self, name: impl Into<String>, constructor: F) -> &mut Self
+    where
+        F: Fn(&Value, &RuleRegistry) -> Result<Arc<dyn TableRule>, ValidationError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.table_rules.insert(name.into(), Arc::new(constructor));
+        self
+    }
+
+    /// Register a dataset rule constructor under `name`
+    pub fn register_dataset_rule<F>(&mut self, name: impl Into<String>, constructor: F) -> &mut Self
+    where
+        F: Fn(&Value, &RuleRegistry) -> Result<Arc<dyn DatasetRule>, ValidationError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.dataset_rules
+            .insert(name.into(), Arc::new(constructor));
+        self
+    }
+
+    /// Build the schema rule registered under `name`
+    pub fn schema_rule(
+        &self,
+        name: &str,
+        params: &Value,
+    ) -> Result<Arc<dyn SchemaRule>, ValidationError> {
+        construct(&self.schema_rules, "schema", name, params, self)
+    }
+
+    /// Build the column rule registered under `name`
+    pub fn column_rule(
+        &self,
+        name: &str,
+        params: &Value,
+    ) -> Result<Arc<dyn ColumnRule>, ValidationError> {
+        construct(&self.column_rules, "column", name, params, self)
+    }
+
+    /// Build the table rule registered under `name`
+    pub fn table_rule(
+        &self,
+        name: &str,
+        params: &Value,
+    ) -> Result<Arc<dyn TableRule>, ValidationError> {
+        construct(&self.table_rules, "table", name, params, self)
+    }
+
+    /// Build the dataset rule registered under `name`
+    pub fn dataset_rule(
+        &self,
+        name: &str,
+        params: &Value,
+    ) -> Result<Arc<dyn DatasetRule>, ValidationError> {
+        construct(&self.dataset_rules, "dataset", name, params, self)
+    }
+}
+
+/// The names of the built-in variants of a spec enum, without its `Registered` fallback
+fn builtin_names(variants: &'static [&'static str]) -> impl Iterator<Item = &'static str> {
+    variants
+        .iter()
+        .copied()
+        .filter(|name| *name != "registered")
+}
+
+fn construct<T: ?Sized>(
+    constructors: &BTreeMap<String, RuleConstructor<T>>,
+    kind: &str,
+    name: &str,
+    params: &Value,
+    registry: &RuleRegistry,
+) -> Result<Arc<T>, ValidationError> {
+    let constructor = constructors
+        .get(name)
+        .ok_or_else(|| ValidationError::Configuration {
+            message: format!("Unknown {} rule '{}'", kind, name),
+        })?;
+    constructor(params, registry)
+}
+
+/// Parse the parameters of the rule `name`, e.g. into a `#[derive(Deserialize)]` struct
+pub fn parse_params<T: DeserializeOwned>(name: &str, params: &Value) -> Result<T, ValidationError> {
+    serde_json::from_value(params.clone()).map_err(|e| ValidationError::Configuration {
+        message: format!("Invalid parameters for rule '{}': {}", name, e),
+    })
+}
+
+/// Parse the spec of the built-in rule `name`
+fn builtin_spec<T: BuiltinSpec>(name: &str, params: &Value) -> Result<T, ValidationError> {
+    RegisteredRule {
+        name: name.to_string(),
+        params: params.clone(),
+    }
+    .to_builtin()
+}
+
+/// A spec enum of built-in rules with a [`RegisteredRule`] fallback, e.g. [`ColumnRuleSpec`]
+pub(crate) trait BuiltinSpec: Sized {
+    /// Deserialize one of the built-in rules, without falling back to a [`RegisteredRule`]
+    fn deserialize_builtin(value: &Value) -> Result<Self, serde_json::Error>;
+}
+
+/// A rule in a spec file that is not a built-in spec variant, resolved by name
+/// through a [`RuleRegistry`]
+///
+/// Written as `name` without parameters or `name: params`, like built-in rules.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegisteredRule {
+    pub name: String,
+    /// The parameters, `null` when written without
+    pub params: Value,
+}
+
+impl RegisteredRule {
+    /// The name and parameters of a spec, e.g. a [`ColumnRuleSpec`]
+    pub(crate) fn from_spec(spec: &impl Serialize) -> Result<Self, ValidationError> {
+        serde_json::to_value(spec)
+            .and_then(serde_json::from_value)
+            .map_err(|e| ValidationError::Configuration {
+                message: format!("Invalid rule spec: {}", e),
+            })
+    }
+
+    /// Parse as the built-in rule of the same name, keeping serde's error, e.g.
+    /// "missing field `max`"
+    pub(crate) fn to_builtin<T: BuiltinSpec>(&self) -> Result<T, ValidationError> {
+        serde_json::to_value(self)
+            .and_then(|spec| T::deserialize_builtin(&spec))
+            .map_err(|e| ValidationError::Configuration {
+                message: format!("Invalid parameters for rule '{}': {}", self.name, e),
+            })
+    }
+
+    /// The error for a built-in rule whose parameters did not match
+    pub(crate) fn invalid_params<T: BuiltinSpec>(&self) -> ValidationError {
+        match self.to_builtin::<T>() {
+            Err(e) => e,
+            Ok(_) => ValidationError::Configuration {
+                message: format!(
+                    "Invalid parameters for rule '{}': {}",
+                    self.name, self.params
+                ),
+            },
+        }
+    }
+}
+
+impl Serialize for RegisteredRule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.params.is_null() {
+            serializer.serialize_str(&self.name)
+        } else {
+            let mut rule = Map::new();
+            rule.insert(self.name.clone(), self.params.clone());
+            rule.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RegisteredRule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Value::deserialize(deserializer)? {
+            Value::String(name) => Ok(Self {
+                name,
+                params: Value::Null,
+            }),
+            Value::Object(rule) if rule.len() == 1 => {
+                let Some((name, params)) = rule.into_iter().next() else {
+                    return Err(serde::de::Error::custom("expected a `name: params` entry"));
+                };
+                Ok(Self { name, params })
+            }
+            value => Err(serde::de::Error::custom(format!(
+                "expected a rule name or a single `name: params` entry, got {}",
+                value
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::column::dfq_str_length;
+    use crate::spec::RuleSetSpec;
+    use arrow::array::StringArray;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion::assert_batches_eq;
+    use datafusion::prelude::*;
+    use serde_json::json;
+    use std::collections::BTreeSet;
+
+    #[derive(Deserialize)]
+    struct SkuParams {
+        length: u32,
+    }
+
+    #[tokio::test]
+    async fn test_registry() {
+        let mut registry = RuleRegistry::new();
+        registry.register_column_rule("sku", |params, _| {
+            let params: SkuParams = parse_params("sku", params)?;
+            Ok(dfq_str_length(Some(params.length), Some(params.length)))
+        });
+        assert!(registry.column_rule("registered", &Value::Null).is_err());
+
+        let spec = RuleSetSpec::from_yaml(
+            r#"
+columns:
+  - column: sku
+    rules:
+      - not_null
+      - sku: { length: 3 }
+dataset:
+  - column: sku
+    rule: { mostly: { rule: { sku: { length: 3 } }, ratio: 0.5 } }
+"#,
+        )
+        .unwrap();
+
+        let batch = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![Field::new("sku", DataType::Utf8, true)])),
+            vec![Arc::new(StringArray::from(vec![
+                Some("ABC"),
+                Some("ABCD"),
+                None,
+            ]))],
+        )
+        .unwrap();
+        let df = SessionContext::new().read_batch(batch).unwrap();
+
+        let rule_set = spec.to_rule_set_with(&registry).unwrap();
+        let outcomes = rule_set.dataset_outcomes(&df).await.unwrap();
+        assert_eq!(outcomes[0].outcome_name, "sku_length_pass_rate");
+        assert!(!outcomes[0].passed);
+
+        let result = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .select_columns(&["sku", "sku_not_null", "sku_length", "dfq_pass"])
+            .unwrap();
+        let expected = vec![
+            "+------+--------------+------------+----------+",
+            "| sku  | sku_not_null | sku_length | dfq_pass |",
+            "+------+--------------+------------+----------+",
+            "| ABC  | true         | true       | true     |",
+            "| ABCD | true         | false      | false    |",
+            "|      | false        |            | false    |",
+            "+------+--------------+------------+----------+",
+        ];
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        // The default registry only knows the built-in rules
+        assert!(matches!(
+            spec.to_rule_set(),
+            Err(ValidationError::Configuration { .. })
+        ));
+
+        // Built-in rules with invalid parameters and invalid custom parameters are
+        // rejected with serde's reason
+        for (rules, reason) in [
+            ("[in_range: { min: 0 }]", "missing field `max`"),
+            ("[sku: { size: 3 }]", "missing field `length`"),
+        ] {
+            let spec = RuleSetSpec::from_yaml(&format!(
+                "columns:\n  - column: sku\n    rules: {}\n",
+                rules
+            ))
+            .unwrap();
+            match spec.to_rule_set_with(&registry) {
+                Err(ValidationError::Configuration { message }) => {
+                    assert!(message.contains(reason), "{}", message)
+                }
+                result => panic!("expected a configuration error, got {:?}", result),
+            }
+        }
+
+        assert!(registry.table_rule("count", &Value::Null).is_ok());
+        assert!(
+            registry
+                .schema_rule("column_exists", &Value::from("sku"))
+                .is_ok()
+        );
+        assert!(
+            RuleRegistry::empty()
+                .column_rule("not_null", &Value::Null)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_builtin_constructors_registered() {
+        // Every public `dfq_*` rule constructor, with the name and parameters it
+        // has in rule specs
+        let builtins = [
+            ("dfq_column_exists", "schema", "column_exists", json!("id")),
+            (
+                "dfq_column_type",
+                "schema",
+                "column_type",
+                json!({ "column": "id", "data_type": "Int32" }),
+            ),
+            (
+                "dfq_column_nullable",
+                "schema",
+                "column_nullable",
+                json!("id"),
+            ),
+            (
+                "dfq_column_not_nullable",
+                "schema",
+                "column_not_nullable",
+                json!("id"),
+            ),
+            ("dfq_not_null", "column", "not_null", Value::Null),
+            ("dfq_null", "column", "null", Value::Null),
+            (
+                "dfq_in_range",
+                "column",
+                "in_range",
+                json!({ "min": 0, "max": 1 }),
+            ),
+            (
+                "dfq_not_in_range",
+                "column",
+                "not_in_range",
+                json!({ "min": 0, "max": 1 }),
+            ),
+            ("dfq_range", "column", "range", json!({ "min": 0 })),
+            (
+                "dfq_range_expr",
+                "column",
+                "range",
+                json!({ "min": 0, "max_exclusive": true, "max": 1 }),
+            ),
+            ("dfq_like", "column", "like", json!("a%")),
+            ("dfq_not_like", "column", "not_like", json!("a%")),
+            ("dfq_ilike", "column", "ilike", json!("a%")),
+            ("dfq_not_ilike", "column", "not_ilike", json!("a%")),
+            ("dfq_lt", "column", "lt", json!(1)),
+            ("dfq_lte", "column", "lte", json!(1)),
+            ("dfq_not_lt", "column", "not_lt", json!(1)),
+            ("dfq_not_lte", "column", "not_lte", json!(1)),
+            ("dfq_gt", "column", "gt", json!(1)),
+            ("dfq_gte", "column", "gte", json!(1)),
+            ("dfq_not_gt", "column", "not_gt", json!(1)),
+            ("dfq_not_gte", "column", "not_gte", json!(1)),
+            ("dfq_eq", "column", "eq", json!("a")),
+            ("dfq_not_eq", "column", "not_eq", json!("a")),
+            (
+                "dfq_str_length",
+                "column",
+                "str_length",
+                json!({ "min": 1 }),
+            ),
+            ("dfq_str_min_length", "column", "str_min_length", json!(1)),
+            ("dfq_str_max_length", "column", "str_max_length", json!(8)),
+            ("dfq_str_empty", "column", "str_empty", Value::Null),
+            ("dfq_str_not_empty", "column", "str_not_empty", Value::Null),
+            ("dfq_is_email", "column", "is_email", Value::Null),
+            ("dfq_is_uuid", "column", "is_uuid", Value::Null),
+            ("dfq_is_ipv4", "column", "is_ipv4", Value::Null),
+            ("dfq_is_json", "column", "is_json", Value::Null),
+            (
+                "dfq_json_schema",
+                "column",
+                "json_schema",
+                json!({ "type": "object" }),
+            ),
+            (
+                "dfq_custom",
+                "column",
+                "sql",
+                json!({ "name": "positive", "expr": "a > 0" }),
+            ),
+            (
+                "dfq_custom_sql",
+                "column",
+                "sql",
+                json!({ "name": "positive", "expr": "a > 0" }),
+            ),
+            (
+                "dfq_when",
+                "column",
+                "when",
+                json!({ "predicate": "b = 'x'", "rule": "not_null" }),
+            ),
+            (
+                "dfq_in_reference",
+                "column",
+                "in_reference",
+                json!({ "table": "t", "column": "c" }),
+            ),
+            (
+                "dfq_date_between",
+                "column",
+                "date_between",
+                json!({ "start": "2024-01-01", "end": "2024-12-31" }),
+            ),
+            ("dfq_not_in_future", "column", "not_in_future", Value::Null),
+            (
+                "dfq_monotonic_increasing",
+                "column",
+                "monotonic_increasing",
+                json!({ "order_by": "ts" }),
+            ),
+            ("dfq_not", "column", "not", json!("null")),
+            ("dfq_all", "column", "all", json!(["not_null", { "gt": 0 }])),
+            ("dfq_any", "column", "any", json!(["null", { "gt": 0 }])),
+            (
+                "dfq_at_least",
+                "column",
+                "at_least",
+                json!({ "k": 1, "rules": ["not_null", { "gt": 0 }] }),
+            ),
+            ("dfq_nested", "column", "nested", json!("not_null")),
+            ("dfq_list_all", "column", "list_all", json!({ "gt": 0 })),
+            (
+                "dfq_list_all_not_null",
+                "column",
+                "list_all_not_null",
+                Value::Null,
+            ),
+            (
+                "dfq_list_all_in_range",
+                "column",
+                "list_all_in_range",
+                json!({ "min": 0, "max": 1 }),
+            ),
+            (
+                "dfq_list_length",
+                "column",
+                "list_length",
+                json!({ "max": 3 }),
+            ),
+            ("dfq_list_distinct", "column", "list_distinct", Value::Null),
+            (
+                "dfq_metric_change_within",
+                "column",
+                "metric_change_within",
+                json!({ "store": "metrics.jsonl", "dataset": "orders", "max_change_pct": 10.0, "lookback": 5 }),
+            ),
+            ("dfq_null_count", "table", "null_count", Value::Null),
+            ("dfq_not_null_count", "table", "not_null_count", Value::Null),
+            ("dfq_count", "table", "count", Value::Null),
+            ("dfq_count_distinct", "table", "count_distinct", Value::Null),
+            ("dfq_avg", "table", "avg", Value::Null),
+            ("dfq_stddev", "table", "std_dev", Value::Null),
+            ("dfq_max", "table", "max", Value::Null),
+            ("dfq_min", "table", "min", Value::Null),
+            ("dfq_sum", "table", "sum", Value::Null),
+            ("dfq_median", "table", "median", Value::Null),
+            ("dfq_last_value", "table", "last_value", Value::Null),
+            ("dfq_stddev_pop", "table", "stddev_pop", Value::Null),
+            ("dfq_var_pop", "table", "var_pop", Value::Null),
+            ("dfq_var_samp", "table", "var_samp", Value::Null),
+            ("dfq_covar_pop", "table", "covar_pop", json!({ "y": "b" })),
+            ("dfq_covar_samp", "table", "covar_samp", json!({ "y": "b" })),
+            ("dfq_regr_avgx", "table", "regr_avgx", json!({ "x": "b" })),
+            ("dfq_regr_avgy", "table", "regr_avgy", json!({ "x": "b" })),
+            ("dfq_regr_count", "table", "regr_count", json!({ "x": "b" })),
+            (
+                "dfq_regr_intercept",
+                "table",
+                "regr_intercept",
+                json!({ "x": "b" }),
+            ),
+            ("dfq_regr_r2", "table", "regr_r2", json!({ "x": "b" })),
+            ("dfq_regr_slope", "table", "regr_slope", json!({ "x": "b" })),
+            ("dfq_regr_sxx", "table", "regr_sxx", json!({ "x": "b" })),
+            ("dfq_regr_sxy", "table", "regr_sxy", json!({ "x": "b" })),
+            ("dfq_regr_syy", "table", "regr_syy", json!({ "x": "b" })),
+            (
+                "dfq_nth_value",
+                "table",
+                "nth_value",
+                json!({ "n": 2, "order_by": ["ts"] }),
+            ),
+            ("dfq_first_value", "table", "first_value", json!({})),
+            (
+                "dfq_custom_agg",
+                "table",
+                "sql",
+                json!({ "name": "total", "expr": "sum(a) > 0" }),
+            ),
+            (
+                "dfq_custom_agg_sql",
+                "table",
+                "sql",
+                json!({ "name": "total", "expr": "sum(a) > 0" }),
+            ),
+            (
+                "dfq_time_gaps",
+                "table",
+                "time_gaps",
+                json!({ "interval_seconds": 3600 }),
+            ),
+            (
+                "dfq_ks_statistic",
+                "table",
+                "ks_statistic",
+                json!({ "reference": "t" }),
+            ),
+            ("dfq_psi", "table", "psi", json!({ "reference": "t" })),
+            (
+                "dfq_chi_squared",
+                "table",
+                "chi_squared",
+                json!({ "reference": "t", "bins": 10 }),
+            ),
+            (
+                "dfq_js_divergence",
+                "table",
+                "js_divergence",
+                json!({ "reference": "t", "bins": 10 }),
+            ),
+            (
+                "dfq_row_count_between",
+                "dataset",
+                "row_count_between",
+                json!({ "min": 1, "max": 10 }),
+            ),
+            (
+                "dfq_freshness",
+                "dataset",
+                "freshness",
+                json!({ "max_age_seconds": 3600 }),
+            ),
+            (
+                "dfq_mostly",
+                "dataset",
+                "mostly",
+                json!({ "rule": "not_null", "ratio": 0.9 }),
+            ),
+        ];
+
+        let sources = [
+            include_str!("metrics.rs"),
+            include_str!("rules/column.rs"),
+            include_str!("rules/combinator.rs"),
+            include_str!("rules/conditional.rs"),
+            include_str!("rules/dataset.rs"),
+            include_str!("rules/distribution.rs"),
+            include_str!("rules/nested.rs"),
+            include_str!("rules/reference.rs"),
+            include_str!("rules/schema.rs"),
+            include_str!("rules/table.rs"),
+            include_str!("rules/temporal.rs"),
+        ];
+        let constructors: BTreeSet<&str> = sources
+            .iter()
+            .flat_map(|source| source.lines())
+            .filter_map(|line| {
+                let line = line.trim_start();
+                let rest = line
+                    .strip_prefix("pub fn ")
+                    .or_else(|| line.split_once("_variant!(").map(|(_, rest)| rest))?;
+                let end = rest
+                    .find(|c: char| !c.is_alphanumeric() && c != '_')
+                    .unwrap_or(rest.len());
+                Some(&rest[..end]).filter(|name| name.starts_with("dfq_"))
+            })
+            .collect();
+        let listed: BTreeSet<&str> = builtins
+            .iter()
+            .map(|(constructor, ..)| *constructor)
+            .collect();
+        assert_eq!(constructors, listed);
+
+        let registry = RuleRegistry::new();
+        for (constructor, kind, name, params) in &builtins {
+            let result = match *kind {
+                "schema" => registry.schema_rule(name, params).map(|_| ()),
+                "column" => registry.column_rule(name, params).map(|_| ()),
+                "table" => registry.table_rule(name, params).map(|_| ()),
+                _ => registry.dataset_rule(name, params).map(|_| ()),
+            };
+            assert!(
+                result.is_ok(),
+                "{} as '{}': {:?}",
+                constructor,
+                name,
+                result
+            );
+        }
+    }
+}
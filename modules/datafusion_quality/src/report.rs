@@ -0,0 +1,361 @@
+use crate::{
+    DatasetRule, RuleRef, RuleSet, ValidationError, error::DataFusionSnafu, evaluation::Evaluation,
+};
+use arrow::{
+    array::{Array, ArrayRef, AsArray, BooleanArray, StringArray, UInt64Array},
+    datatypes::{DataType, Field, Int64Type, Schema},
+    record_batch::RecordBatch,
+};
+use datafusion::{
+    functions_aggregate::{count::count_all, expr_fn::sum},
+    prelude::*,
+};
+use serde::Serialize;
+use snafu::ResultExt;
+use std::sync::Arc;
+
+/// Pass/fail counts for a single column rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RuleReport {
+    /// The column the rule was registered against
+    pub column_name: String,
+    /// The name of the rule
+    pub rule_name: String,
+    /// The name of the column holding the rule result
+    pub check_column: String,
+    /// Rows where the rule result is true
+    pub passed: u64,
+    /// Rows where the rule result is false or null
+    pub failed: u64,
+    /// Whether the rule was skipped because a prerequisite failed, in which case
+    /// no rows are counted
+    pub skipped: bool,
+}
+
+/// Outcome of a single dataset rule
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DatasetOutcome {
+    /// The column the rule was registered against
+    pub column_name: String,
+    /// The name of the rule
+    pub rule_name: String,
+    /// The name of the outcome, e.g. `event_time_freshness`
+    pub outcome_name: String,
+    /// The observed value, e.g. the row count or latest timestamp
+    pub observed: Option<String>,
+    pub passed: bool,
+    /// Whether the rule was skipped because a prerequisite failed
+    pub skipped: bool,
+}
+
+impl DatasetOutcome {
+    /// Evaluate a single dataset rule
+    pub(crate) async fn evaluate(
+        df: &DataFrame,
+        column_name: &str,
+        rule: &dyn DatasetRule,
+        rule_set: &RuleSet,
+    ) -> Result<Self, ValidationError> {
+        let batches = rule
+            .evaluate_async(df.clone(), column_name, rule_set)
+            .await?
+            .collect()
+            .await
+            .context(DataFusionSnafu)?;
+        let batch = batches.iter().find(|b| b.num_rows() > 0).ok_or_else(|| {
+            ValidationError::Validation {
+                message: format!("Dataset rule '{}' returned no rows", rule.name()),
+            }
+        })?;
+
+        let observed = batch.column(0).as_string::<i32>();
+        let passed = batch.column(1).as_boolean();
+        Ok(DatasetOutcome {
+            column_name: column_name.to_string(),
+            rule_name: rule.name().to_string(),
+            outcome_name: rule.new_column_name(column_name),
+            observed: (!observed.is_null(0)).then(|| observed.value(0).to_string()),
+            passed: passed.is_valid(0) && passed.value(0),
+            skipped: false,
+        })
+    }
+}
+
+/// Summary of applying a RuleSet to a DataFrame
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationReport {
+    pub total_rows: u64,
+    /// Rows where `dfq_pass` is true
+    pub passed_rows: u64,
+    pub failed_rows: u64,
+    pub rules: Vec<RuleReport>,
+    /// Dataset rule outcomes, which are not part of `dfq_pass`
+    pub dataset_rules: Vec<DatasetOutcome>,
+}
+
+impl ValidationReport {
+    /// Whether every row passed every rule and every dataset rule passed or was skipped
+    pub fn success(&self) -> bool {
+        self.failed_rows == 0
+            && self
+                .dataset_rules
+                .iter()
+                .all(|outcome| outcome.passed || outcome.skipped)
+    }
+
+    /// One row per rule with its pass/fail counts
+    pub fn to_record_batch(&self) -> Result<RecordBatch, ValidationError> {
+        let schema = Schema::new(vec![
+            Field::new("column_name", DataType::Utf8, false),
+            Field::new("rule_name", DataType::Utf8, false),
+            Field::new("check_column", DataType::Utf8, false),
+            Field::new("passed", DataType::UInt64, false),
+            Field::new("failed", DataType::UInt64, false),
+            Field::new("skipped", DataType::Boolean, false),
+        ]);
+
+        let strings = |f: fn(&RuleReport) -> &str| -> ArrayRef {
+            Arc::new(StringArray::from_iter_values(self.rules.iter().map(f)))
+        };
+        let counts = |f: fn(&RuleReport) -> u64| -> ArrayRef {
+            Arc::new(UInt64Array::from_iter_values(self.rules.iter().map(f)))
+        };
+
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                strings(|r| &r.column_name),
+                strings(|r| &r.rule_name),
+                strings(|r| &r.check_column),
+                counts(|r| r.passed),
+                counts(|r| r.failed),
+                Arc::new(BooleanArray::from_iter(
+                    self.rules.iter().map(|r| Some(r.skipped)),
+                )),
+            ],
+        )
+        .map_err(|e| ValidationError::Validation {
+            message: format!("Error building report batch: {}", e),
+        })
+    }
+
+    /// One row per dataset rule with its observed value and outcome
+    pub fn dataset_record_batch(&self) -> Result<RecordBatch, ValidationError> {
+        let schema = Schema::new(vec![
+            Field::new("column_name", DataType::Utf8, false),
+            Field::new("rule_name", DataType::Utf8, false),
+            Field::new("outcome_name", DataType::Utf8, false),
+            Field::new("observed", DataType::Utf8, true),
+            Field::new("passed", DataType::Boolean, false),
+            Field::new("skipped", DataType::Boolean, false),
+        ]);
+
+        let strings = |f: fn(&DatasetOutcome) -> &str| -> ArrayRef {
+            Arc::new(StringArray::from_iter_values(
+                self.dataset_rules.iter().map(f),
+            ))
+        };
+
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                strings(|o| &o.column_name),
+                strings(|o| &o.rule_name),
+                strings(|o| &o.outcome_name),
+                Arc::new(StringArray::from_iter(
+                    self.dataset_rules.iter().map(|o| o.observed.as_deref()),
+                )),
+                Arc::new(BooleanArray::from_iter(
+                    self.dataset_rules.iter().map(|o| Some(o.passed)),
+                )),
+                Arc::new(BooleanArray::from_iter(
+                    self.dataset_rules.iter().map(|o| Some(o.skipped)),
+                )),
+            ],
+        )
+        .map_err(|e| ValidationError::Validation {
+            message: format!("Error building report batch: {}", e),
+        })
+    }
+}
+
+impl RuleSet {
+    /// Apply all rules and summarize the pass/fail counts per rule in a single query.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use datafusion_quality::RuleSet;
+    /// use datafusion_quality::rules::column::dfq_not_null;
+    /// use datafusion::prelude::*;
+    ///
+    /// # async fn example(df: DataFrame) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut ruleset = RuleSet::new();
+    /// ruleset.with_column_rule("name", dfq_not_null());
+    ///
+    /// let report = ruleset.report(&df).await?;
+    /// assert!(report.success());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn report(&self, df: &DataFrame) -> Result<ValidationReport, ValidationError> {
+        self.report_evaluation(&self.evaluate(df).await?).await
+    }
+
+    /// Summarize an [`Evaluation`] of this RuleSet, e.g. one whose DataFrame is
+    /// cached to also write it with [`PartitionSink::write`](crate::sink::PartitionSink::write)
+    pub async fn report_evaluation(
+        &self,
+        evaluation: &Evaluation,
+    ) -> Result<ValidationReport, ValidationError> {
+        let count_true = |name: &str| sum(cast(ident(name).is_true(), DataType::Int64));
+
+        let mut aggregates = vec![
+            count_all().alias("__dfq_total"),
+            count_true("dfq_pass").alias("__dfq_passed"),
+        ];
+        for (i, (column_name, rule, _)) in self.column_rules.iter().enumerate() {
+            aggregates.push(
+                count_true(&rule.new_column_name(column_name)).alias(format!("__dfq_rule_{}", i)),
+            );
+        }
+
+        let batches = evaluation
+            .df
+            .clone()
+            .aggregate(vec![], aggregates)?
+            .collect()
+            .await
+            .context(DataFusionSnafu)?;
+        let batch = batches.first().ok_or_else(|| ValidationError::Validation {
+            message: "Report query returned no rows".to_string(),
+        })?;
+
+        // Every aggregate is an Int64; sums over zero rows are null
+        let value = |i: usize| -> u64 {
+            let column = batch.column(i);
+            let column = column.as_primitive::<Int64Type>();
+            if column.is_null(0) {
+                0
+            } else {
+                u64::try_from(column.value(0)).unwrap_or_default()
+            }
+        };
+
+        let total_rows = value(0);
+        let passed_rows = value(1);
+        let rules = self
+            .column_rules
+            .iter()
+            .enumerate()
+            .map(|(i, (column_name, rule, _))| {
+                let skipped = evaluation.is_skipped(RuleRef::Column(i));
+                let passed = value(i + 2);
+                RuleReport {
+                    column_name: column_name.clone(),
+                    rule_name: rule.name().to_string(),
+                    check_column: rule.new_column_name(column_name),
+                    passed,
+                    failed: if skipped { 0 } else { total_rows - passed },
+                    skipped,
+                }
+            })
+            .collect();
+
+        Ok(ValidationReport {
+            total_rows,
+            passed_rows,
+            failed_rows: total_rows - passed_rows,
+            rules,
+            dataset_rules: evaluation.dataset_outcomes.clone(),
+        })
+    }
+
+    /// Evaluate the dataset rules, in the order they were added
+    ///
+    /// The other rules are applied too when dataset rules depend on them, see
+    /// [`RuleSet::with_depends_on`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use datafusion_quality::RuleSet;
+    /// use datafusion_quality::rules::dataset::dfq_row_count_between;
+    /// use datafusion::prelude::*;
+    ///
+    /// # async fn example(df: DataFrame) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut ruleset = RuleSet::new();
+    /// ruleset.with_dataset_rule("orders", dfq_row_count_between(1, 1000));
+    ///
+    /// for outcome in ruleset.dataset_outcomes(&df).await? {
+    ///     println!("{}: {}", outcome.outcome_name, outcome.passed);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn dataset_outcomes(
+        &self,
+        df: &DataFrame,
+    ) -> Result<Vec<DatasetOutcome>, ValidationError> {
+        Ok(self.evaluate(df).await?.dataset_outcomes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::column::*;
+    use arrow::array::{Float64Array, Int32Array};
+    use datafusion::assert_batches_eq;
+
+    #[tokio::test]
+    async fn test_report() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("score", DataType::Float64, true),
+        ]);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3, 4])),
+                Arc::new(StringArray::from(vec![
+                    Some("Alice"),
+                    None,
+                    Some("Charlie"),
+                    Some("Dave"),
+                ])),
+                Arc::new(Float64Array::from(vec![
+                    Some(85.5),
+                    Some(92.0),
+                    None,
+                    Some(20.0),
+                ])),
+            ],
+        )
+        .unwrap();
+        let df = SessionContext::new().read_batch(batch).unwrap();
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("name", dfq_not_null())
+            .with_column_rule("score", dfq_in_range(50.0, 100.0));
+
+        let report = rule_set.report(&df).await.unwrap();
+        assert_eq!(report.total_rows, 4);
+        assert_eq!(report.passed_rows, 1);
+        assert_eq!(report.failed_rows, 3);
+        assert!(!report.success());
+
+        let expected = vec![
+            "+-------------+-----------+----------------+--------+--------+---------+",
+            "| column_name | rule_name | check_column   | passed | failed | skipped |",
+            "+-------------+-----------+----------------+--------+--------+---------+",
+            "| name        | not_null  | name_not_null  | 3      | 1      | false   |",
+            "| score       | in_range  | score_in_range | 2      | 2      | false   |",
+            "+-------------+-----------+----------------+--------+--------+---------+",
+        ];
+        assert_batches_eq!(&expected, &[report.to_record_batch().unwrap()]);
+    }
+}
@@ -1,7 +1,16 @@
-use crate::{ColumnRule, ValidationError, error::DataFusionSnafu};
-use datafusion::{logical_expr::Between, prelude::*};
+use crate::{
+    ColumnRule, RuleSet, SupportedTypes, ValidationError, error::DataFusionSnafu,
+    json_schema::JsonSchema, rules::parse_rule_sql, udf::json_udf,
+};
+use arrow::datatypes::DataType;
+use datafusion::{
+    common::{Column, DataFusionError, ScalarValue, SchemaError},
+    functions::expr_fn::regexp_like,
+    logical_expr::Between,
+    prelude::*,
+};
 use snafu::ResultExt;
-use std::sync::Arc;
+use std::{ops::Bound, sync::Arc};
 
 /// Rule that checks if values in a column are not null
 #[derive(Debug, Clone, Default)]
@@ -90,26 +99,122 @@ pub fn dfq_null() -> Arc<NullRule> {
 /// Rule that checks if values in a column fall within a specified range
 #[derive(Debug, Clone)]
 pub struct RangeRule {
-    min: f64,
-    max: f64,
+    lower: Bound<Expr>,
+    upper: Bound<Expr>,
     negated: Option<bool>,
 }
 
 impl RangeRule {
     pub fn new(min: f64, max: f64, negated: Option<bool>) -> Self {
-        Self { min, max, negated }
+        Self::with_bounds(
+            Bound::Included(ScalarValue::from(min)),
+            Bound::Included(ScalarValue::from(max)),
+            negated,
+        )
+    }
+
+    /// Creates a new RangeRule with typed bounds
+    ///
+    /// Bounds are cast to the column's type when that is lossless, so Int64 and
+    /// Decimal128 columns are compared exactly.
+    ///
+    /// # Arguments
+    ///
+    /// * `lower` - The lower bound, inclusive, exclusive or unbounded
+    /// * `upper` - The upper bound, inclusive, exclusive or unbounded
+    /// * `negated` - Whether to check values fall outside the range instead
+    pub fn with_bounds(
+        lower: Bound<ScalarValue>,
+        upper: Bound<ScalarValue>,
+        negated: Option<bool>,
+    ) -> Self {
+        Self::with_expr_bounds(lower.map(lit), upper.map(lit), negated)
+    }
+
+    /// Creates a new RangeRule with expression bounds, e.g. another column
+    ///
+    /// Literal bounds are cast like in [`RangeRule::with_bounds`], other expressions
+    /// are compared with the column as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `lower` - The lower bound, inclusive, exclusive or unbounded
+    /// * `upper` - The upper bound, inclusive, exclusive or unbounded
+    /// * `negated` - Whether to check values fall outside the range instead
+    pub fn with_expr_bounds(lower: Bound<Expr>, upper: Bound<Expr>, negated: Option<bool>) -> Self {
+        Self {
+            lower,
+            upper,
+            negated,
+        }
+    }
+
+    /// Cast a literal bound to the column type, unless that would change its value
+    fn bound_expr(bound: &Expr, data_type: &DataType) -> Expr {
+        let Expr::Literal(value) = bound else {
+            return bound.clone();
+        };
+        let cast = value.cast_to(data_type).ok().filter(|cast| {
+            cast.cast_to(&value.data_type())
+                .is_ok_and(|round_trip| &round_trip == value)
+        });
+        lit(cast.unwrap_or_else(|| value.clone()))
     }
 }
 
 impl ColumnRule for RangeRule {
     fn apply(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        // Qualified names such as `t.score` resolve like `col(column_name)` does
+        let data_type = match df
+            .schema()
+            .qualified_field_from_column(&Column::from(column_name))
+        {
+            Ok((_, field)) => field.data_type().clone(),
+            Err(DataFusionError::SchemaError(SchemaError::FieldNotFound { .. }, _)) => {
+                return Err(ValidationError::ColumnNotFound {
+                    column_name: column_name.to_string(),
+                });
+            }
+            Err(source) => return Err(ValidationError::DataFusion { source }),
+        };
         let col = col(column_name);
-        let in_range = Expr::Between(Between {
-            expr: Box::new(col),
-            negated: self.negated.unwrap_or(false),
-            low: Box::new(lit(self.min)),
-            high: Box::new(lit(self.max)),
-        });
+
+        let in_range = match (&self.lower, &self.upper) {
+            // Keep the plain BETWEEN for the common inclusive range
+            (Bound::Included(min), Bound::Included(max)) => Expr::Between(Between {
+                expr: Box::new(col),
+                negated: self.negated.unwrap_or(false),
+                low: Box::new(Self::bound_expr(min, &data_type)),
+                high: Box::new(Self::bound_expr(max, &data_type)),
+            }),
+            (lower, upper) => {
+                let lower = match lower {
+                    Bound::Included(min) => {
+                        Some(col.clone().gt_eq(Self::bound_expr(min, &data_type)))
+                    }
+                    Bound::Excluded(min) => Some(col.clone().gt(Self::bound_expr(min, &data_type))),
+                    Bound::Unbounded => None,
+                };
+                let upper = match upper {
+                    Bound::Included(max) => {
+                        Some(col.clone().lt_eq(Self::bound_expr(max, &data_type)))
+                    }
+                    Bound::Excluded(max) => Some(col.clone().lt(Self::bound_expr(max, &data_type))),
+                    Bound::Unbounded => None,
+                };
+                let in_range = match (lower, upper) {
+                    (Some(lower), Some(upper)) => lower.and(upper),
+                    (Some(bound), None) | (None, Some(bound)) => bound,
+                    // Only nulls are outside an unbounded range
+                    (None, None) => col.is_not_null().or(lit(ScalarValue::Boolean(None))),
+                };
+                if self.negated.unwrap_or(false) {
+                    in_range.not()
+                } else {
+                    in_range
+                }
+            }
+        };
 
         df.with_column(&self.new_column_name(column_name), in_range)
             .context(DataFusionSnafu)
@@ -130,6 +235,27 @@ impl ColumnRule for RangeRule {
     fn description(&self) -> &str {
         "Checks if values in a column (does not) fall within a specified range"
     }
+
+    fn supported_types(&self) -> SupportedTypes {
+        // The type of expression bounds is only known once planned
+        let bound = match (&self.lower, &self.upper) {
+            (Bound::Included(Expr::Literal(value)) | Bound::Excluded(Expr::Literal(value)), _)
+            | (_, Bound::Included(Expr::Literal(value)) | Bound::Excluded(Expr::Literal(value))) => {
+                value.data_type()
+            }
+            _ => return SupportedTypes::Any,
+        };
+        // Bounds are compared with the column's values
+        if bound.is_numeric() {
+            SupportedTypes::Numeric
+        } else if SupportedTypes::String.supports(&bound) {
+            SupportedTypes::String
+        } else if SupportedTypes::Temporal.supports(&bound) {
+            SupportedTypes::Temporal
+        } else {
+            SupportedTypes::Any
+        }
+    }
 }
 
 /// Creates a rule that checks if values in a column fall within a specified range.
@@ -176,6 +302,76 @@ pub fn dfq_not_in_range(min: f64, max: f64) -> Arc<RangeRule> {
     Arc::new(RangeRule::new(min, max, Some(true)))
 }
 
+/// Creates a rule that checks if values in a column fall within a range with typed
+/// bounds, each of which may be inclusive, exclusive or unbounded.
+///
+/// Unlike [`dfq_in_range`], bounds keep their type, so large Int64 IDs and
+/// Decimal128 amounts are compared without going through `f64`. The rule is
+/// named `in_range` like [`dfq_in_range`].
+///
+/// # Arguments
+///
+/// * `lower` - The lower bound
+/// * `upper` - The upper bound
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::column::dfq_range;
+/// use datafusion_quality::RuleSet;
+/// use datafusion::common::ScalarValue;
+/// use std::ops::Bound;
+///
+/// let mut ruleset = RuleSet::new();
+/// // 0 < amount <= 1000.00 on a Decimal128(10, 2) column
+/// ruleset.with_column_rule(
+///     "amount",
+///     dfq_range(
+///         Bound::Excluded(ScalarValue::Decimal128(Some(0), 10, 2)),
+///         Bound::Included(ScalarValue::Decimal128(Some(100_000), 10, 2)),
+///     ),
+/// );
+/// // id >= 2^60, with no upper bound
+/// ruleset.with_column_rule("id", dfq_range(Bound::Included(1_i64 << 60), Bound::Unbounded));
+/// ```
+pub fn dfq_range<T: Into<ScalarValue>>(lower: Bound<T>, upper: Bound<T>) -> Arc<RangeRule> {
+    Arc::new(RangeRule::with_bounds(
+        lower.map(Into::into),
+        upper.map(Into::into),
+        None,
+    ))
+}
+
+/// Creates a rule that checks if values in a column fall within a range whose
+/// bounds are expressions, such as other columns of the same row.
+///
+/// Literal bounds are cast to the column type like in [`dfq_range`]. The rule is
+/// named `in_range` like [`dfq_in_range`].
+///
+/// # Arguments
+///
+/// * `lower` - The lower bound
+/// * `upper` - The upper bound
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::column::dfq_range_expr;
+/// use datafusion_quality::RuleSet;
+/// use datafusion::prelude::*;
+/// use std::ops::Bound;
+///
+/// let mut ruleset = RuleSet::new();
+/// // 0 <= discount < price
+/// ruleset.with_column_rule(
+///     "discount",
+///     dfq_range_expr(Bound::Included(lit(0)), Bound::Excluded(col("price"))),
+/// );
+/// ```
+pub fn dfq_range_expr(lower: Bound<Expr>, upper: Bound<Expr>) -> Arc<RangeRule> {
+    Arc::new(RangeRule::with_expr_bounds(lower, upper, None))
+}
+
 /// Rule that checks if values in a column match a pattern
 #[derive(Debug, Clone)]
 pub struct PatternRule {
@@ -230,6 +426,10 @@ impl ColumnRule for PatternRule {
     fn description(&self) -> &str {
         "Checks if values in a column match a pattern"
     }
+
+    fn supported_types(&self) -> SupportedTypes {
+        SupportedTypes::String
+    }
 }
 
 /// Creates a rule that checks if values in a column match a pattern (case-sensitive).
@@ -711,6 +911,10 @@ impl ColumnRule for LengthRule {
     fn description(&self) -> &str {
         "Checks if the length of a column is between a minimum and maximum value"
     }
+
+    fn supported_types(&self) -> SupportedTypes {
+        SupportedTypes::String
+    }
 }
 
 /// Creates a rule that checks if the length of a string column is between a minimum and maximum value.
@@ -811,6 +1015,212 @@ pub fn dfq_str_not_empty() -> Arc<LengthRule> {
     Arc::new(LengthRule::new(Some(1), None))
 }
 
+/// String formats that can be checked with a [`FormatRule`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StringFormat {
+    Email,
+    Uuid,
+    Ipv4,
+}
+
+impl StringFormat {
+    /// The regular expression a value must match to be in this format
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            Self::Email => r"^[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}$",
+            Self::Uuid => {
+                r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$"
+            }
+            Self::Ipv4 => {
+                r"^((25[0-5]|2[0-4][0-9]|1[0-9]{2}|[1-9]?[0-9])\.){3}(25[0-5]|2[0-4][0-9]|1[0-9]{2}|[1-9]?[0-9])$"
+            }
+        }
+    }
+}
+
+/// Rule that checks if string values in a column are in a well known format
+#[derive(Debug, Clone)]
+pub struct FormatRule {
+    format: StringFormat,
+}
+
+impl FormatRule {
+    pub fn new(format: StringFormat) -> Self {
+        Self { format }
+    }
+}
+
+impl ColumnRule for FormatRule {
+    fn apply(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        let expr = regexp_like(col(column_name), lit(self.format.pattern()), None);
+        df.with_column(&self.new_column_name(column_name), expr)
+            .context(DataFusionSnafu)
+    }
+
+    fn name(&self) -> &str {
+        match self.format {
+            StringFormat::Email => "is_email",
+            StringFormat::Uuid => "is_uuid",
+            StringFormat::Ipv4 => "is_ipv4",
+        }
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", column_name, self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Checks if string values in a column are in a well known format"
+    }
+
+    fn supported_types(&self) -> SupportedTypes {
+        SupportedTypes::String
+    }
+}
+
+/// Creates a rule that checks if values in a column are email addresses.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::column::dfq_is_email;
+/// use datafusion_quality::RuleSet;
+///
+/// // Create a rule to check if email holds email addresses
+/// let rule = dfq_is_email();
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule("email", rule);
+/// ```
+pub fn dfq_is_email() -> Arc<FormatRule> {
+    Arc::new(FormatRule::new(StringFormat::Email))
+}
+
+/// Creates a rule that checks if values in a column are UUIDs.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::column::dfq_is_uuid;
+/// use datafusion_quality::RuleSet;
+///
+/// // Create a rule to check if order_id holds UUIDs
+/// let rule = dfq_is_uuid();
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule("order_id", rule);
+/// ```
+pub fn dfq_is_uuid() -> Arc<FormatRule> {
+    Arc::new(FormatRule::new(StringFormat::Uuid))
+}
+
+/// Creates a rule that checks if values in a column are IPv4 addresses.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::column::dfq_is_ipv4;
+/// use datafusion_quality::RuleSet;
+///
+/// // Create a rule to check if client_ip holds IPv4 addresses
+/// let rule = dfq_is_ipv4();
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule("client_ip", rule);
+/// ```
+pub fn dfq_is_ipv4() -> Arc<FormatRule> {
+    Arc::new(FormatRule::new(StringFormat::Ipv4))
+}
+
+/// Rule that checks if string values in a column are JSON, optionally conforming
+/// to a JSON Schema
+#[derive(Debug, Clone)]
+pub struct JsonRule {
+    schema: Option<serde_json::Value>,
+}
+
+impl JsonRule {
+    pub fn new(schema: Option<serde_json::Value>) -> Self {
+        Self { schema }
+    }
+}
+
+impl ColumnRule for JsonRule {
+    fn apply(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        let expr = match &self.schema {
+            Some(schema) => {
+                // Report an invalid schema now rather than when the plan runs
+                JsonSchema::new(schema)?;
+                json_udf(true).call(vec![col(column_name), lit(schema.to_string())])
+            }
+            None => json_udf(false).call(vec![col(column_name)]),
+        };
+        df.with_column(&self.new_column_name(column_name), expr)
+            .context(DataFusionSnafu)
+    }
+
+    fn name(&self) -> &str {
+        if self.schema.is_some() {
+            "json_schema"
+        } else {
+            "is_json"
+        }
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", column_name, self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Checks if string values in a column are JSON conforming to a JSON Schema"
+    }
+
+    fn supported_types(&self) -> SupportedTypes {
+        SupportedTypes::String
+    }
+}
+
+/// Creates a rule that checks if values in a column parse as JSON.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::column::dfq_is_json;
+/// use datafusion_quality::RuleSet;
+///
+/// // Create a rule to check if payload holds JSON
+/// let rule = dfq_is_json();
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule("payload", rule);
+/// ```
+pub fn dfq_is_json() -> Arc<JsonRule> {
+    Arc::new(JsonRule::new(None))
+}
+
+/// Creates a rule that checks if values in a column are JSON conforming to a JSON
+/// Schema. See [`JsonSchema`] for the supported keywords.
+///
+/// # Arguments
+///
+/// * `schema` - The JSON Schema
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::column::dfq_json_schema;
+/// use datafusion_quality::RuleSet;
+/// use serde_json::json;
+///
+/// // Create a rule to check if payload holds events with an id and a known kind
+/// let rule = dfq_json_schema(json!({
+///     "type": "object",
+///     "required": ["id", "kind"],
+///     "properties": {"id": {"type": "integer"}, "kind": {"enum": ["click", "view"]}}
+/// }));
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule("payload", rule);
+/// ```
+pub fn dfq_json_schema(schema: serde_json::Value) -> Arc<JsonRule> {
+    Arc::new(JsonRule::new(Some(schema)))
+}
+
 /// Rule that applies a custom SQL expression to a column
 #[derive(Debug, Clone)]
 pub struct CustomRule {
@@ -870,10 +1280,87 @@ pub fn dfq_custom(rule_name: &str, expression: Expr) -> Arc<CustomRule> {
     Arc::new(CustomRule::new(rule_name, expression))
 }
 
+/// Rule that applies a custom SQL expression, given as text, to a column
+///
+/// The expression is parsed against the schema of the input DataFrame when the
+/// rule is applied, so it can come from configuration.
+#[derive(Debug, Clone)]
+pub struct CustomSqlRule {
+    rule_name: String,
+    sql: String,
+}
+
+impl CustomSqlRule {
+    pub fn new(rule_name: &str, sql: &str) -> Self {
+        Self {
+            rule_name: rule_name.to_string(),
+            sql: sql.to_string(),
+        }
+    }
+}
+
+impl ColumnRule for CustomSqlRule {
+    fn apply(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        let expr = parse_rule_sql(&df, &self.sql, &self.rule_name, column_name)?;
+        df.with_column(&self.new_column_name(column_name), expr)
+            .context(DataFusionSnafu)
+    }
+
+    fn apply_with_ruleset(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        rule_set: &RuleSet,
+    ) -> Result<DataFrame, ValidationError> {
+        let sql = rule_set.context().resolve(&self.sql)?;
+        let expr = parse_rule_sql(&df, &sql, &self.rule_name, column_name)?;
+        df.with_column(&self.new_column_name(column_name), expr)
+            .context(DataFusionSnafu)
+    }
+
+    fn name(&self) -> &str {
+        "custom_sql"
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", column_name, self.rule_name)
+    }
+
+    fn description(&self) -> &str {
+        "Applies a custom SQL expression, parsed from text, to a column"
+    }
+}
+
+/// Creates a rule that applies a custom SQL expression, given as text, to a column.
+///
+/// # Arguments
+///
+/// * `rule_name` - A name for the custom rule
+/// * `sql` - The SQL expression to apply, parsed when the rule is applied. `${name}`
+///   placeholders are resolved from the [`RuleContext`](crate::RuleContext) of the RuleSet.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::column::dfq_custom_sql;
+/// use datafusion_quality::RuleSet;
+///
+/// // Create a custom rule to check if age is greater than 25
+/// let rule = dfq_custom_sql("age_gt_25", "age > 25");
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule("age", rule);
+/// ```
+pub fn dfq_custom_sql(rule_name: &str, sql: &str) -> Arc<CustomSqlRule> {
+    Arc::new(CustomSqlRule::new(rule_name, sql))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use arrow::array::{Float64Array, Int32Array, StringArray};
+    use crate::RuleSet;
+    use arrow::array::{
+        AsArray, Decimal128Array, Float64Array, Int32Array, Int64Array, StringArray,
+    };
     use arrow::datatypes::{DataType, Field, Schema};
     use arrow::record_batch::RecordBatch;
     use datafusion::assert_batches_eq;
@@ -973,6 +1460,125 @@ mod tests {
         assert_batches_eq!(&expected, &result.collect().await.unwrap());
     }
 
+    #[tokio::test]
+    async fn test_typed_range_rule() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("amount", DataType::Decimal128(10, 2), true),
+        ]);
+        // Ids differing by one above 2^53, where f64 cannot tell them apart
+        let big = 1_i64 << 60;
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int64Array::from(vec![big - 1, big, big + 1])),
+                Arc::new(
+                    Decimal128Array::from(vec![Some(0), Some(1), Some(100_001)])
+                        .with_precision_and_scale(10, 2)
+                        .unwrap(),
+                ),
+            ],
+        )
+        .unwrap();
+        let df = SessionContext::new().read_batch(batch).unwrap();
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("id", dfq_range(Bound::Excluded(big), Bound::Unbounded))
+            .with_column_rule(
+                "amount",
+                // Cast to Decimal128(10, 2) since 1000.0 round-trips exactly
+                dfq_range(Bound::Excluded(0.0), Bound::Included(1000.0)),
+            );
+
+        let result = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .select_columns(&["id", "amount", "id_in_range", "amount_in_range"])
+            .unwrap();
+
+        let expected = vec![
+            "+---------------------+---------+-------------+-----------------+",
+            "| id                  | amount  | id_in_range | amount_in_range |",
+            "+---------------------+---------+-------------+-----------------+",
+            "| 1152921504606846975 | 0.00    | false       | false           |",
+            "| 1152921504606846976 | 0.01    | false       | true            |",
+            "| 1152921504606846977 | 1000.01 | true        | false           |",
+            "+---------------------+---------+-------------+-----------------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        // A bound that does not fit the column type is compared as is
+        let result = dfq_range(Bound::Included(0.5), Bound::Unbounded)
+            .apply(df, "id")
+            .unwrap()
+            .select_columns(&["id_in_range"])
+            .unwrap();
+        let batches = result.collect().await.unwrap();
+        assert_eq!(batches[0].column(0).as_boolean().true_count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_range_rule_qualified() {
+        // After the join, `score` is ambiguous and must be qualified
+        let ctx = SessionContext::new();
+        let left = ctx
+            .sql("SELECT * FROM (VALUES (1, 10), (2, 20), (3, 30)) AS t(id, score)")
+            .await
+            .unwrap();
+        let right = ctx
+            .sql("SELECT * FROM (VALUES (1, 5), (2, 25), (3, 35)) AS u(id, score)")
+            .await
+            .unwrap();
+        let df = left
+            .join(right, JoinType::Inner, &["id"], &["id"], None)
+            .unwrap();
+
+        let result = dfq_in_range(0.0, 15.0)
+            .apply(df.clone(), "t.score")
+            .unwrap()
+            .sort(vec![col("t.id").sort(true, false)])
+            .unwrap();
+        let expected = vec![
+            "+----+-------+----+-------+------------------+",
+            "| id | score | id | score | t.score_in_range |",
+            "+----+-------+----+-------+------------------+",
+            "| 1  | 10    | 1  | 5     | true             |",
+            "| 2  | 20    | 2  | 25    | false            |",
+            "| 3  | 30    | 3  | 35    | false            |",
+            "+----+-------+----+-------+------------------+",
+        ];
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        // Expression bounds, here the score of the other side
+        let result = dfq_range_expr(Bound::Included(lit(0)), Bound::Excluded(col("u.score")))
+            .apply(df.clone(), "t.score")
+            .unwrap()
+            .sort(vec![col("t.id").sort(true, false)])
+            .unwrap();
+        let expected = vec![
+            "+----+-------+----+-------+------------------+",
+            "| id | score | id | score | t.score_in_range |",
+            "+----+-------+----+-------+------------------+",
+            "| 1  | 10    | 1  | 5     | false            |",
+            "| 2  | 20    | 2  | 25    | true             |",
+            "| 3  | 30    | 3  | 35    | true             |",
+            "+----+-------+----+-------+------------------+",
+        ];
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        assert!(matches!(
+            dfq_in_range(0.0, 15.0).apply(df.clone(), "score"),
+            Err(ValidationError::DataFusion { .. })
+        ));
+        assert!(matches!(
+            dfq_in_range(0.0, 15.0).apply(df, "t.missing"),
+            Err(ValidationError::ColumnNotFound { .. })
+        ));
+    }
+
     #[tokio::test]
     async fn test_pattern_rule() {
         // Test case sensitive pattern match
@@ -1044,6 +1650,103 @@ mod tests {
         assert_batches_eq!(&expected, &result.collect().await.unwrap());
     }
 
+    #[tokio::test]
+    async fn test_format_rules() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("value", DataType::Utf8, true),
+        ]);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3, 4])),
+                Arc::new(StringArray::from(vec![
+                    Some("alice@example.com"),
+                    Some("123e4567-e89b-12d3-a456-426614174000"),
+                    Some("192.168.0.1"),
+                    None,
+                ])),
+            ],
+        )
+        .unwrap();
+
+        let ctx = SessionContext::new();
+        let df = ctx.read_batch(batch).unwrap();
+
+        let result = dfq_is_email().apply(df.clone(), "value").unwrap();
+        let result = dfq_is_uuid().apply(result, "value").unwrap();
+        let result = dfq_is_ipv4().apply(result, "value").unwrap();
+
+        let expected = vec![
+            "+----+--------------------------------------+----------------+---------------+---------------+",
+            "| id | value                                | value_is_email | value_is_uuid | value_is_ipv4 |",
+            "+----+--------------------------------------+----------------+---------------+---------------+",
+            "| 1  | alice@example.com                    | true           | false         | false         |",
+            "| 2  | 123e4567-e89b-12d3-a456-426614174000 | false          | true          | false         |",
+            "| 3  | 192.168.0.1                          | false          | false         | true          |",
+            "| 4  |                                      |                |               |               |",
+            "+----+--------------------------------------+----------------+---------------+---------------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_json_rules() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("payload", DataType::Utf8, true),
+        ]);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3, 4])),
+                Arc::new(StringArray::from(vec![
+                    Some(r#"{"id": 1, "kind": "click"}"#),
+                    Some(r#"{"kind": "scroll"}"#),
+                    Some("{not json"),
+                    None,
+                ])),
+            ],
+        )
+        .unwrap();
+
+        let ctx = SessionContext::new();
+        let df = ctx.read_batch(batch).unwrap();
+
+        let event_schema = serde_json::json!({
+            "type": "object",
+            "required": ["id"],
+            "properties": {"kind": {"enum": ["click", "view"]}}
+        });
+        let result = dfq_is_json().apply(df.clone(), "payload").unwrap();
+        let result = dfq_json_schema(event_schema)
+            .apply(result, "payload")
+            .unwrap()
+            .drop_columns(&["payload"])
+            .unwrap();
+
+        let expected = vec![
+            "+----+-----------------+---------------------+",
+            "| id | payload_is_json | payload_json_schema |",
+            "+----+-----------------+---------------------+",
+            "| 1  | true            | true                |",
+            "| 2  | true            | false               |",
+            "| 3  | false           | false               |",
+            "| 4  |                 |                     |",
+            "+----+-----------------+---------------------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        assert!(matches!(
+            dfq_json_schema(serde_json::json!({"type": "float"})).apply(df, "payload"),
+            Err(ValidationError::Configuration { .. })
+        ));
+    }
+
     #[tokio::test]
     async fn test_custom_rule() {
         let df = create_test_df().await;
@@ -1063,6 +1766,31 @@ mod tests {
         assert_batches_eq!(&expected, &result.collect().await.unwrap());
     }
 
+    #[tokio::test]
+    async fn test_custom_sql_rule() {
+        let df = create_test_df().await;
+        let rule = dfq_custom_sql("age_gt_25", "age > 25");
+        let result = rule.apply(df.clone(), "age").unwrap();
+
+        let expected = vec![
+            "+----+---------+-----+-------+---------------+",
+            "| id | name    | age | score | age_age_gt_25 |",
+            "+----+---------+-----+-------+---------------+",
+            "| 1  | Alice   | 25  | 85.5  | false         |",
+            "| 2  | Bob     |     | 92.0  |               |",
+            "| 3  | Charlie | 30  |       | true          |",
+            "+----+---------+-----+-------+---------------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        let rule = dfq_custom_sql("bad", "agee > 25");
+        let err = rule.apply(df, "age").unwrap_err();
+        assert!(matches!(err, ValidationError::Configuration { .. }));
+        assert!(err.to_string().contains("'bad'"));
+        assert!(err.to_string().contains("'age'"));
+    }
+
     #[tokio::test]
     async fn test_less_than_rule() {
         let df = create_test_df().await;
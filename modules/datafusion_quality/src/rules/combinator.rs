@@ -0,0 +1,423 @@
+use crate::{
+    ColumnRule, RuleSet, SupportedTypes, ValidationError, error::DataFusionSnafu, rules::added_expr,
+};
+
+use arrow::datatypes::DataType;
+use datafusion::prelude::*;
+use futures::future::BoxFuture;
+use snafu::ResultExt;
+use std::sync::Arc;
+
+/// How a [`CombinedRule`] combines the results of its rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Combinator {
+    /// The single rule fails
+    Not,
+    /// Every rule passes
+    All,
+    /// At least one rule passes
+    Any,
+    /// At least `k` rules pass
+    AtLeast(usize),
+}
+
+/// Rule that combines the results of other column rules into a single column
+#[derive(Debug, Clone)]
+pub struct CombinedRule {
+    combinator: Combinator,
+    rules: Vec<Arc<dyn ColumnRule>>,
+    name: String,
+    description: String,
+}
+
+impl CombinedRule {
+    /// Creates a new CombinedRule, deriving its name and description from `rules`
+    ///
+    /// # Arguments
+    ///
+    /// * `combinator` - How the results are combined
+    /// * `rules` - The rules to combine, applied to the same column
+    pub fn new(combinator: Combinator, rules: Vec<Arc<dyn ColumnRule>>) -> Self {
+        let names: Vec<&str> = rules.iter().map(|rule| rule.name()).collect();
+        let descriptions: Vec<&str> = rules.iter().map(|rule| rule.description()).collect();
+        let (name, description) = match combinator {
+            Combinator::Not => (
+                format!("not_{}", names.join("_")),
+                format!("Not: {}", descriptions.join("; ")),
+            ),
+            Combinator::All => (
+                format!("all_{}", names.join("_and_")),
+                format!("All of: {}", descriptions.join("; ")),
+            ),
+            Combinator::Any => (
+                format!("any_{}", names.join("_or_")),
+                format!("Any of: {}", descriptions.join("; ")),
+            ),
+            Combinator::AtLeast(k) => (
+                format!("at_least_{}_of_{}", k, names.join("_")),
+                format!("At least {} of: {}", k, descriptions.join("; ")),
+            ),
+        };
+
+        Self {
+            combinator,
+            rules,
+            name,
+            description,
+        }
+    }
+
+    /// Start combining the results of the rules applied to `df`
+    fn combination(&self, df: DataFrame) -> Result<Combination, ValidationError> {
+        if self.combinator == Combinator::Not && self.rules.len() != 1 {
+            return Err(ValidationError::Configuration {
+                message: format!("dfq_not expects one rule, got {}", self.rules.len()),
+            });
+        }
+        match self.combinator {
+            Combinator::AtLeast(k) if k > self.rules.len() => {
+                return Err(ValidationError::Configuration {
+                    message: format!(
+                        "dfq_at_least expects at most {} rules to pass, got {}",
+                        self.rules.len(),
+                        k
+                    ),
+                });
+            }
+            _ => {}
+        }
+        Ok(Combination {
+            df,
+            results: Vec::with_capacity(self.rules.len()),
+            temporary: Vec::new(),
+        })
+    }
+
+    fn apply_rules(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        rule_set: Option<&RuleSet>,
+    ) -> Result<DataFrame, ValidationError> {
+        let mut combination = self.combination(df)?;
+        for rule in &self.rules {
+            let result = match rule_set {
+                Some(rule_set) => {
+                    rule.apply_with_ruleset(combination.df.clone(), column_name, rule_set)?
+                }
+                None => rule.apply(combination.df.clone(), column_name)?,
+            };
+            combination.add(result, &rule.new_column_name(column_name))?;
+        }
+        self.combine(combination, column_name)
+    }
+
+    /// Add the combined result column and drop the temporary rule columns
+    fn combine(
+        &self,
+        combination: Combination,
+        column_name: &str,
+    ) -> Result<DataFrame, ValidationError> {
+        let Combination {
+            df,
+            mut results,
+            temporary,
+        } = combination;
+        let combined = match self.combinator {
+            Combinator::Not => results.remove(0).not(),
+            Combinator::All => results.into_iter().reduce(Expr::and).unwrap_or(lit(true)),
+            Combinator::Any => results.into_iter().reduce(Expr::or).unwrap_or(lit(false)),
+            Combinator::AtLeast(k) => results
+                .into_iter()
+                .map(|check| cast(check.is_true(), DataType::Int64))
+                .reduce(|acc, passed| acc + passed)
+                .unwrap_or(lit(0i64))
+                .gt_eq(lit(i64::try_from(k).unwrap_or(i64::MAX))),
+        };
+
+        let temporary: Vec<&str> = temporary.iter().map(String::as_str).collect();
+        df.with_column(&self.new_column_name(column_name), combined)?
+            .drop_columns(&temporary)
+            .context(DataFusionSnafu)
+    }
+}
+
+/// The results of the rules of a [`CombinedRule`] applied so far
+struct Combination {
+    df: DataFrame,
+    results: Vec<Expr>,
+    /// Rule columns kept until the results are combined
+    temporary: Vec<String>,
+}
+
+impl Combination {
+    /// Add the result of a rule applied to `self.df`, found in `rule_column`
+    fn add(&mut self, result: DataFrame, rule_column: &str) -> Result<(), ValidationError> {
+        // Inline the check where possible; otherwise keep its column until combined
+        match added_expr(&result, &self.df, rule_column) {
+            Some(check) => self.results.push(check),
+            None => {
+                let name = format!("__dfq_combined_{}", self.results.len());
+                self.df = result.with_column_renamed(rule_column, &name)?;
+                self.results.push(col(&name));
+                self.temporary.push(name);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ColumnRule for CombinedRule {
+    fn apply(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        self.apply_rules(df, column_name, None)
+    }
+
+    fn apply_with_ruleset(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        rule_set: &RuleSet,
+    ) -> Result<DataFrame, ValidationError> {
+        self.apply_rules(df, column_name, Some(rule_set))
+    }
+
+    fn apply_async<'a>(
+        &'a self,
+        df: DataFrame,
+        column_name: &'a str,
+        rule_set: &'a RuleSet,
+    ) -> BoxFuture<'a, Result<DataFrame, ValidationError>> {
+        Box::pin(async move {
+            let mut combination = self.combination(df)?;
+            for rule in &self.rules {
+                let result = rule
+                    .apply_async(combination.df.clone(), column_name, rule_set)
+                    .await?;
+                combination.add(result, &rule.new_column_name(column_name))?;
+            }
+            self.combine(combination, column_name)
+        })
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", column_name, self.name())
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn supported_types(&self) -> SupportedTypes {
+        // Every rule is applied to the column, whatever the combinator
+        SupportedTypes::All(
+            self.rules
+                .iter()
+                .map(|rule| rule.supported_types())
+                .collect(),
+        )
+    }
+}
+
+/// Creates a rule that passes where `rule` fails.
+///
+/// Null results stay null. The rule is named `not_{rule}`.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::{column::dfq_is_email, combinator::dfq_not};
+/// use datafusion_quality::RuleSet;
+///
+/// // Usernames must not be email addresses
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule("username", dfq_not(dfq_is_email()));
+/// ```
+pub fn dfq_not(rule: Arc<dyn ColumnRule>) -> Arc<CombinedRule> {
+    Arc::new(CombinedRule::new(Combinator::Not, vec![rule]))
+}
+
+/// Creates a rule that passes where every rule passes.
+///
+/// The rule is named `all_{a}_and_{b}...`.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::{column::*, combinator::dfq_all};
+/// use datafusion_quality::RuleSet;
+///
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule("code", dfq_all(vec![dfq_str_length(Some(3), Some(8)), dfq_like("A%")]));
+/// ```
+pub fn dfq_all(rules: Vec<Arc<dyn ColumnRule>>) -> Arc<CombinedRule> {
+    Arc::new(CombinedRule::new(Combinator::All, rules))
+}
+
+/// Creates a rule that passes where at least one rule passes.
+///
+/// The rule is named `any_{a}_or_{b}...`.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::{column::*, combinator::dfq_any};
+/// use datafusion_quality::RuleSet;
+///
+/// // A contact is either an email address or a UUID
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule("contact", dfq_any(vec![dfq_is_email(), dfq_is_uuid()]));
+/// ```
+pub fn dfq_any(rules: Vec<Arc<dyn ColumnRule>>) -> Arc<CombinedRule> {
+    Arc::new(CombinedRule::new(Combinator::Any, rules))
+}
+
+/// Creates a rule that passes where at least `k` of the rules pass.
+///
+/// Null results count as failed. The rule is named `at_least_{k}_of_{a}_{b}...`.
+/// Applying it returns a configuration error when `k` exceeds the number of rules.
+///
+/// # Arguments
+///
+/// * `k` - The number of rules that must pass
+/// * `rules` - The rules to check
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::{column::*, combinator::dfq_at_least};
+/// use datafusion_quality::RuleSet;
+///
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule(
+///     "password",
+///     dfq_at_least(2, vec![dfq_like("%[0-9]%"), dfq_str_min_length(12), dfq_like("%!%")]),
+/// );
+/// ```
+pub fn dfq_at_least(k: usize, rules: Vec<Arc<dyn ColumnRule>>) -> Arc<CombinedRule> {
+    Arc::new(CombinedRule::new(Combinator::AtLeast(k), rules))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::column::*;
+    use crate::rules::nested::dfq_list_length;
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::{Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion::assert_batches_eq;
+
+    fn create_test_df() -> DataFrame {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("contact", DataType::Utf8, true),
+        ]);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3, 4])),
+                Arc::new(StringArray::from(vec![
+                    Some("alice@example.com"),
+                    Some("123e4567-e89b-12d3-a456-426614174000"),
+                    Some("bob"),
+                    None,
+                ])),
+            ],
+        )
+        .unwrap();
+
+        SessionContext::new().read_batch(batch).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_combinators() {
+        let df = create_test_df();
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("contact", dfq_not(dfq_is_email()))
+            .with_column_rule(
+                "contact",
+                dfq_all(vec![dfq_not_null(), dfq_str_min_length(4)]),
+            )
+            .with_column_rule("contact", dfq_any(vec![dfq_is_email(), dfq_is_uuid()]))
+            .with_column_rule(
+                "contact",
+                dfq_at_least(
+                    2,
+                    vec![dfq_not_null(), dfq_like("%@%"), dfq_str_max_length(5)],
+                ),
+            );
+
+        let result = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .drop_columns(&["contact", "dfq_pass"])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+
+        let expected = vec![
+            "+----+----------------------+-------------------------------------+---------------------------------+------------------------------------------------+",
+            "| id | contact_not_is_email | contact_all_not_null_and_min_length | contact_any_is_email_or_is_uuid | contact_at_least_2_of_not_null_like_max_length |",
+            "+----+----------------------+-------------------------------------+---------------------------------+------------------------------------------------+",
+            "| 1  | false                | true                                | true                            | true                                           |",
+            "| 2  | true                 | true                                | true                            | false                                          |",
+            "| 3  | true                 | false                               | false                           | true                                           |",
+            "| 4  |                      | false                               |                                 | false                                          |",
+            "+----+----------------------+-------------------------------------+---------------------------------+------------------------------------------------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        // More rules must pass than there are
+        let rule = dfq_at_least(3, vec![dfq_not_null(), dfq_like("%@%")]);
+        assert!(matches!(
+            rule.apply(create_test_df(), "contact"),
+            Err(ValidationError::Configuration { .. })
+        ));
+
+        let rule = dfq_any(vec![dfq_is_email(), dfq_is_uuid()]);
+        assert_eq!(
+            rule.description(),
+            "Any of: Checks if string values in a column are in a well known format; \
+             Checks if string values in a column are in a well known format"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_combinator_fallback() {
+        // List rules over nested paths are applied through joins and combined by column
+        let df = create_test_df()
+            .with_column("contacts", make_array(vec![col("contact"), col("contact")]))
+            .unwrap();
+
+        let rule = dfq_not(dfq_list_length(Some(3), None));
+        let result = rule
+            .apply(df, "contacts")
+            .unwrap()
+            .select_columns(&["id", "contacts_not_list_length"])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+
+        let expected = vec![
+            "+----+--------------------------+",
+            "| id | contacts_not_list_length |",
+            "+----+--------------------------+",
+            "| 1  | true                     |",
+            "| 2  | true                     |",
+            "| 3  | true                     |",
+            "| 4  | true                     |",
+            "+----+--------------------------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+    }
+}
@@ -0,0 +1,394 @@
+use crate::{
+    ColumnRule, RuleSet, SupportedTypes, TableRule, ValidationError,
+    error::DataFusionSnafu,
+    rules::{added_expr, parse_rule_sql},
+};
+
+use datafusion::prelude::*;
+use futures::future::BoxFuture;
+use snafu::ResultExt;
+use std::sync::Arc;
+
+/// Rule that only applies another rule to the rows matching a predicate
+///
+/// Wraps both column and table rules, see [`dfq_when`].
+#[derive(Debug, Clone)]
+pub struct ConditionalRule<R: ?Sized> {
+    predicate: Predicate,
+    inner: Arc<R>,
+}
+
+#[derive(Debug, Clone)]
+enum Predicate {
+    Expr(Box<Expr>),
+    /// Parsed against the schema of the DataFrame the rule is applied to
+    Sql(String),
+}
+
+impl<R: ?Sized> ConditionalRule<R> {
+    /// Creates a new ConditionalRule
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - The rows the rule applies to
+    /// * `inner` - The rule to apply
+    pub fn new(predicate: Expr, inner: Arc<R>) -> Self {
+        Self {
+            predicate: Predicate::Expr(Box::new(predicate)),
+            inner,
+        }
+    }
+
+    /// Creates a ConditionalRule whose predicate is a SQL expression, e.g.
+    /// `type = 'sale'`, parsed when the rule is applied
+    ///
+    /// # Arguments
+    ///
+    /// * `predicate` - The rows the rule applies to
+    /// * `inner` - The rule to apply
+    pub fn with_sql_predicate(predicate: &str, inner: Arc<R>) -> Self {
+        Self {
+            predicate: Predicate::Sql(predicate.to_string()),
+            inner,
+        }
+    }
+
+    fn predicate(&self, df: &DataFrame, column_name: &str) -> Result<Expr, ValidationError> {
+        match &self.predicate {
+            Predicate::Expr(predicate) => Ok(predicate.as_ref().clone()),
+            Predicate::Sql(sql) => parse_rule_sql(df, sql, "when", column_name),
+        }
+    }
+
+    /// Evaluate a column rule's check only where the predicate holds, passing other rows
+    ///
+    /// `result` is `df` with the check of the wrapped rule.
+    fn apply_column(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        new_column_name: &str,
+        result: DataFrame,
+    ) -> Result<DataFrame, ValidationError> {
+        let matches = self.predicate(&df, column_name)?.is_true();
+
+        // Prefer guarding the check itself, so it is never evaluated for other rows
+        match added_expr(&result, &df, new_column_name) {
+            Some(check) => {
+                df.with_column(new_column_name, when(matches, check).otherwise(lit(true))?)
+            }
+            None => result.with_column(
+                new_column_name,
+                when(matches, col(new_column_name)).otherwise(lit(true))?,
+            ),
+        }
+        .context(DataFusionSnafu)
+    }
+
+    /// The rows where the predicate holds, that a table rule aggregates
+    fn filtered(&self, df: &DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        df.clone()
+            .filter(self.predicate(df, column_name)?)
+            .context(DataFusionSnafu)
+    }
+
+    /// Compute a table rule's aggregate over the rows where the predicate holds
+    ///
+    /// `result` is `filtered` with the aggregate of the wrapped rule.
+    fn apply_table(
+        &self,
+        df: DataFrame,
+        filtered: DataFrame,
+        new_column_name: &str,
+        rule_name: &str,
+        result: DataFrame,
+    ) -> Result<DataFrame, ValidationError> {
+        // The aggregate is a scalar subquery over the filtered rows, so it can be
+        // added to the unfiltered rows as is
+        let value = added_expr(&result, &filtered, new_column_name)
+            .filter(|value| value.column_refs().is_empty())
+            .ok_or_else(|| ValidationError::Configuration {
+                message: format!(
+                    "Table rule '{}' does not add a table-wide value and cannot be made conditional",
+                    rule_name
+                ),
+            })?;
+
+        df.with_column(new_column_name, value)
+            .context(DataFusionSnafu)
+    }
+}
+
+impl<R: ColumnRule + ?Sized> ColumnRule for ConditionalRule<R> {
+    fn apply(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        let result = self.inner.apply(df.clone(), column_name)?;
+        self.apply_column(df, column_name, &self.new_column_name(column_name), result)
+    }
+
+    fn apply_with_ruleset(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        rule_set: &RuleSet,
+    ) -> Result<DataFrame, ValidationError> {
+        let result = self
+            .inner
+            .apply_with_ruleset(df.clone(), column_name, rule_set)?;
+        self.apply_column(df, column_name, &self.new_column_name(column_name), result)
+    }
+
+    fn apply_async<'a>(
+        &'a self,
+        df: DataFrame,
+        column_name: &'a str,
+        rule_set: &'a RuleSet,
+    ) -> BoxFuture<'a, Result<DataFrame, ValidationError>> {
+        Box::pin(async move {
+            let result = self
+                .inner
+                .apply_async(df.clone(), column_name, rule_set)
+                .await?;
+            self.apply_column(df, column_name, &self.new_column_name(column_name), result)
+        })
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        self.inner.new_column_name(column_name)
+    }
+
+    fn description(&self) -> &str {
+        "Applies a column rule only to the rows matching a predicate; other rows pass"
+    }
+
+    fn supported_types(&self) -> SupportedTypes {
+        self.inner.supported_types()
+    }
+}
+
+impl<R: TableRule + ?Sized> TableRule for ConditionalRule<R> {
+    fn apply(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        let filtered = self.filtered(&df, column_name)?;
+        let result = self.inner.apply(filtered.clone(), column_name)?;
+        self.apply_table(
+            df,
+            filtered,
+            &self.new_column_name(column_name),
+            self.inner.name(),
+            result,
+        )
+    }
+
+    fn apply_with_ruleset(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        rule_set: &RuleSet,
+    ) -> Result<DataFrame, ValidationError> {
+        let filtered = self.filtered(&df, column_name)?;
+        let result = self
+            .inner
+            .apply_with_ruleset(filtered.clone(), column_name, rule_set)?;
+        self.apply_table(
+            df,
+            filtered,
+            &self.new_column_name(column_name),
+            self.inner.name(),
+            result,
+        )
+    }
+
+    fn apply_async<'a>(
+        &'a self,
+        df: DataFrame,
+        column_name: &'a str,
+        rule_set: &'a RuleSet,
+    ) -> BoxFuture<'a, Result<DataFrame, ValidationError>> {
+        Box::pin(async move {
+            let filtered = self.filtered(&df, column_name)?;
+            let result = self
+                .inner
+                .apply_async(filtered.clone(), column_name, rule_set)
+                .await?;
+            self.apply_table(
+                df,
+                filtered,
+                &self.new_column_name(column_name),
+                self.inner.name(),
+                result,
+            )
+        })
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        self.inner.new_column_name(column_name)
+    }
+
+    fn description(&self) -> &str {
+        "Computes a table rule over the rows matching a predicate"
+    }
+
+    fn supported_types(&self) -> SupportedTypes {
+        self.inner.supported_types()
+    }
+}
+
+/// Creates a rule that only applies `rule` where `predicate` holds.
+///
+/// For a column rule, the check is only evaluated for matching rows and all other
+/// rows pass. For a table rule, the aggregate is computed over the matching rows
+/// only, like [`CustomAggregationRuleBuilder::with_filter`](crate::rules::table::CustomAggregationRuleBuilder::with_filter).
+/// The result column keeps the name of the wrapped rule.
+///
+/// # Arguments
+///
+/// * `predicate` - The rows the rule applies to; rows where it is null do not match
+/// * `rule` - The column or table rule to apply
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::{column::*, conditional::dfq_when, table::*};
+/// use datafusion_quality::RuleSet;
+/// use datafusion::prelude::*;
+///
+/// let mut ruleset = RuleSet::new();
+/// // amount > 0, but only for sales
+/// ruleset.with_column_rule("amount", dfq_when(col("type").eq(lit("sale")), dfq_gt(lit(0))));
+/// // The average refund is below 100
+/// ruleset.with_table_rule(
+///     "amount",
+///     dfq_when(col("type").eq(lit("refund")), dfq_avg()),
+///     Some(dfq_lt(lit(100.0))),
+/// );
+/// ```
+pub fn dfq_when<R: ?Sized>(predicate: Expr, rule: Arc<R>) -> Arc<ConditionalRule<R>> {
+    Arc::new(ConditionalRule::new(predicate, rule))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::column::*;
+    use crate::rules::nested::dfq_list_all_not_null;
+    use crate::rules::table::*;
+    use arrow::array::{Float64Array, Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion::assert_batches_eq;
+    use datafusion::common::ScalarValue;
+
+    fn create_test_df() -> DataFrame {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("type", DataType::Utf8, true),
+            Field::new("amount", DataType::Float64, true),
+        ]);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3, 4, 5])),
+                Arc::new(StringArray::from(vec![
+                    Some("sale"),
+                    Some("sale"),
+                    Some("refund"),
+                    Some("refund"),
+                    None,
+                ])),
+                Arc::new(Float64Array::from(vec![10.0, -5.0, -20.0, -40.0, -1.0])),
+            ],
+        )
+        .unwrap();
+
+        SessionContext::new().read_batch(batch).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_conditional_rules() {
+        let df = create_test_df();
+        let is_sale = col("type").eq(lit("sale"));
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("amount", dfq_when(is_sale.clone(), dfq_gt(lit(0.0))))
+            .with_table_rule(
+                "amount",
+                dfq_when(col("type").eq(lit("refund")), dfq_avg()),
+                Some(dfq_gt(lit(-50.0))),
+            )
+            .with_table_rule("id", dfq_when(is_sale, dfq_count()), None);
+
+        let result = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .select_columns(&[
+                "id",
+                "amount_greater_than",
+                "amount_avg",
+                "id_count",
+                "dfq_pass",
+            ])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+
+        let expected = vec![
+            "+----+---------------------+------------+----------+----------+",
+            "| id | amount_greater_than | amount_avg | id_count | dfq_pass |",
+            "+----+---------------------+------------+----------+----------+",
+            "| 1  | true                | -30.0      | 2        | true     |",
+            "| 2  | false               | -30.0      | 2        | false    |",
+            "| 3  | true                | -30.0      | 2        | true     |",
+            "| 4  | true                | -30.0      | 2        | true     |",
+            "| 5  | true                | -30.0      | 2        | true     |",
+            "+----+---------------------+------------+----------+----------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_conditional_fallback() {
+        // The list rule adds its check through a join, so its result is wrapped instead
+        let amount_or_null = when(
+            col("id").in_list(vec![lit(1), lit(4)], false),
+            lit(ScalarValue::Float64(None)),
+        )
+        .otherwise(col("amount"))
+        .unwrap();
+        let df = create_test_df()
+            .with_column("amounts", make_array(vec![amount_or_null]))
+            .unwrap();
+
+        let rule = dfq_when(col("id").gt(lit(2)), dfq_list_all_not_null());
+        let result = ColumnRule::apply(rule.as_ref(), df, "amounts")
+            .unwrap()
+            .select_columns(&["id", "amounts_list_all_not_null"])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+
+        let expected = vec![
+            "+----+---------------------------+",
+            "| id | amounts_list_all_not_null |",
+            "+----+---------------------------+",
+            "| 1  | true                      |",
+            "| 2  | true                      |",
+            "| 3  | true                      |",
+            "| 4  | false                     |",
+            "| 5  | true                      |",
+            "+----+---------------------------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+    }
+}
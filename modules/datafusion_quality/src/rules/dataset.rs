@@ -0,0 +1,474 @@
+use crate::{
+    ColumnRule, DatasetRule, RuleSet, SupportedTypes, ValidationError,
+    clock::{Clock, SystemClock, duration_nanos, utc_timestamp},
+    error::DataFusionSnafu,
+};
+
+use arrow::datatypes::DataType;
+use datafusion::common::ScalarValue;
+use datafusion::functions_aggregate::{
+    count::count_all,
+    expr_fn::{avg, max},
+};
+use datafusion::prelude::*;
+use futures::future::BoxFuture;
+use snafu::ResultExt;
+use std::{sync::Arc, time::Duration};
+
+/// Rule that checks the number of rows in the table is within a range
+#[derive(Debug, Clone)]
+pub struct RowCountRule {
+    min: u64,
+    max: u64,
+}
+
+impl RowCountRule {
+    /// Creates a new RowCountRule
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The minimum number of rows (inclusive)
+    /// * `max` - The maximum number of rows (inclusive)
+    pub fn new(min: u64, max: u64) -> Self {
+        Self { min, max }
+    }
+}
+
+impl DatasetRule for RowCountRule {
+    fn evaluate(&self, df: DataFrame, _column_name: &str) -> Result<DataFrame, ValidationError> {
+        df.aggregate(vec![], vec![count_all().alias("observed")])?
+            .select(vec![
+                cast(col("observed"), DataType::Utf8).alias("observed"),
+                col("observed")
+                    .between(
+                        lit(i64::try_from(self.min).unwrap_or(i64::MAX)),
+                        lit(i64::try_from(self.max).unwrap_or(i64::MAX)),
+                    )
+                    .alias("passed"),
+            ])
+            .context(DataFusionSnafu)
+    }
+
+    fn name(&self) -> &str {
+        "row_count_between"
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", column_name, self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Checks the number of rows in the table is within a range"
+    }
+}
+
+/// Creates a rule that checks the table has between `min` and `max` rows (inclusive).
+///
+/// The column name the rule is registered with only labels the outcome.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::dataset::dfq_row_count_between;
+/// use datafusion_quality::RuleSet;
+///
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_dataset_rule("orders", dfq_row_count_between(1, 1_000_000));
+/// ```
+pub fn dfq_row_count_between(min: u64, max: u64) -> Arc<RowCountRule> {
+    Arc::new(RowCountRule::new(min, max))
+}
+
+/// Rule that checks the latest value of a temporal column is recent
+#[derive(Debug, Clone)]
+pub struct FreshnessRule {
+    max_age: Duration,
+    clock: Arc<dyn Clock>,
+}
+
+impl FreshnessRule {
+    /// Creates a new FreshnessRule
+    ///
+    /// # Arguments
+    ///
+    /// * `max_age` - How old the latest value may be
+    /// * `clock` - The clock giving the current time
+    pub fn new(max_age: Duration, clock: Arc<dyn Clock>) -> Self {
+        Self { max_age, clock }
+    }
+
+    /// Use a different clock, e.g. a [`FixedClock`](crate::clock::FixedClock) in tests
+    pub fn with_clock(self: Arc<Self>, clock: Arc<dyn Clock>) -> Arc<Self> {
+        Arc::new(Self {
+            max_age: self.max_age,
+            clock,
+        })
+    }
+}
+
+impl DatasetRule for FreshnessRule {
+    fn evaluate(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        // Dates, timestamps of any unit and time zone are compared as UTC nanoseconds
+        let oldest_allowed = self
+            .clock
+            .now_nanos()
+            .saturating_sub(duration_nanos(self.max_age));
+        let oldest_allowed = lit(ScalarValue::TimestampNanosecond(
+            Some(oldest_allowed),
+            Some("UTC".into()),
+        ));
+
+        df.aggregate(
+            vec![],
+            vec![max(cast(col(column_name), utc_timestamp())).alias("observed")],
+        )?
+        .select(vec![
+            cast(col("observed"), DataType::Utf8).alias("observed"),
+            col("observed")
+                .gt_eq(oldest_allowed)
+                .is_true()
+                .alias("passed"),
+        ])
+        .context(DataFusionSnafu)
+    }
+
+    fn name(&self) -> &str {
+        "freshness"
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", column_name, self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Checks the latest value of a temporal column is within a maximum age"
+    }
+
+    fn supported_types(&self) -> SupportedTypes {
+        SupportedTypes::Temporal
+    }
+}
+
+/// Creates a rule that checks the latest value of a date or timestamp column is at
+/// most `max_age` old. Fails when the column has no values.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::clock::FixedClock;
+/// use datafusion_quality::rules::dataset::dfq_freshness;
+/// use datafusion_quality::RuleSet;
+/// use std::{sync::Arc, time::Duration};
+///
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_dataset_rule("event_time", dfq_freshness(Duration::from_secs(6 * 60 * 60)));
+///
+/// // With a fixed "now" for deterministic tests
+/// let rule = dfq_freshness(Duration::from_secs(3600))
+///     .with_clock(Arc::new(FixedClock::from_unix_secs(1_700_000_000)));
+/// ```
+pub fn dfq_freshness(max_age: Duration) -> Arc<FreshnessRule> {
+    Arc::new(FreshnessRule::new(max_age, Arc::new(SystemClock)))
+}
+
+/// Rule that checks the share of rows passing a column rule is at least a threshold
+#[derive(Debug, Clone)]
+pub struct PassRateRule {
+    rule: Arc<dyn ColumnRule>,
+    mostly: f64,
+}
+
+impl PassRateRule {
+    /// Creates a new PassRateRule
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - The column rule evaluated for every row
+    /// * `mostly` - The minimum share of passing rows, between 0 and 1
+    pub fn new(rule: Arc<dyn ColumnRule>, mostly: f64) -> Self {
+        Self { rule, mostly }
+    }
+}
+
+impl PassRateRule {
+    fn check_threshold(&self) -> Result<(), ValidationError> {
+        if !(0.0..=1.0).contains(&self.mostly) {
+            return Err(ValidationError::Configuration {
+                message: format!(
+                    "Pass rate threshold for rule '{}' must be between 0 and 1, got {}",
+                    self.rule.name(),
+                    self.mostly
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// The outcome for `df` with the result column of the rule
+    fn pass_rate(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        let check = col(self.rule.new_column_name(column_name));
+        df.aggregate(
+            vec![],
+            vec![avg(cast(check.is_true(), DataType::Float64)).alias("observed")],
+        )?
+        .select(vec![
+            cast(col("observed"), DataType::Utf8).alias("observed"),
+            // An empty table has no failing rows
+            col("observed")
+                .gt_eq(lit(self.mostly))
+                .is_not_false()
+                .alias("passed"),
+        ])
+        .context(DataFusionSnafu)
+    }
+}
+
+impl DatasetRule for PassRateRule {
+    fn evaluate(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        self.check_threshold()?;
+        self.pass_rate(self.rule.apply(df, column_name)?, column_name)
+    }
+
+    fn evaluate_with_ruleset(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        rule_set: &RuleSet,
+    ) -> Result<DataFrame, ValidationError> {
+        self.check_threshold()?;
+        let df = self.rule.apply_with_ruleset(df, column_name, rule_set)?;
+        self.pass_rate(df, column_name)
+    }
+
+    fn evaluate_async<'a>(
+        &'a self,
+        df: DataFrame,
+        column_name: &'a str,
+        rule_set: &'a RuleSet,
+    ) -> BoxFuture<'a, Result<DataFrame, ValidationError>> {
+        Box::pin(async move {
+            self.check_threshold()?;
+            let df = self.rule.apply_async(df, column_name, rule_set).await?;
+            self.pass_rate(df, column_name)
+        })
+    }
+
+    fn name(&self) -> &str {
+        "pass_rate"
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", self.rule.new_column_name(column_name), self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Checks the share of rows passing a column rule is at least a threshold"
+    }
+
+    fn row_rule(&self) -> Option<Arc<dyn ColumnRule>> {
+        Some(self.rule.clone())
+    }
+}
+
+/// Creates a rule that passes when at least a `mostly` share of rows pass `rule`,
+/// like the `mostly` parameter of Great Expectations.
+///
+/// The observed value is the share of passing rows; null results count as failures.
+/// [`RuleSet::apply`](crate::RuleSet::apply) still adds the row-level result of `rule`,
+/// but it is not part of `dfq_pass`, so a handful of bad rows does not fail the
+/// whole partition.
+///
+/// # Arguments
+///
+/// * `rule` - The column rule evaluated for every row
+/// * `mostly` - The minimum share of passing rows, between 0 and 1
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::{column::dfq_is_email, dataset::dfq_mostly};
+/// use datafusion_quality::RuleSet;
+///
+/// // At least 99% of emails must be valid
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_dataset_rule("email", dfq_mostly(dfq_is_email(), 0.99));
+/// ```
+pub fn dfq_mostly(rule: Arc<dyn ColumnRule>, mostly: f64) -> Arc<PassRateRule> {
+    Arc::new(PassRateRule::new(rule, mostly))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RuleSet;
+    use crate::clock::FixedClock;
+    use crate::rules::column::dfq_not_null;
+    use arrow::array::{Date32Array, Int32Array, TimestampMillisecondArray};
+    use arrow::datatypes::TimeUnit;
+    use arrow::datatypes::{Field, Schema};
+    use arrow::record_batch::RecordBatch;
+
+    const DAY_SECS: u64 = 24 * 60 * 60;
+
+    fn create_test_df() -> DataFrame {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("event_day", DataType::Date32, true),
+            Field::new(
+                "event_time",
+                DataType::Timestamp(TimeUnit::Millisecond, Some("+02:00".into())),
+                true,
+            ),
+        ]);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                // Days 10 and 12 after the epoch, i.e. 1970-01-11 and 1970-01-13
+                Arc::new(Date32Array::from(vec![Some(10), Some(12), None])),
+                Arc::new(
+                    TimestampMillisecondArray::from(vec![
+                        Some(10 * 86_400_000),
+                        Some(12 * 86_400_000 + 3_600_000),
+                        None,
+                    ])
+                    .with_timezone("+02:00"),
+                ),
+            ],
+        )
+        .unwrap();
+
+        SessionContext::new().read_batch(batch).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_dataset_rules() {
+        let df = create_test_df();
+        // 1970-01-14T00:00:00Z
+        let clock = Arc::new(FixedClock::from_unix_secs(13 * DAY_SECS));
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_dataset_rule("table", dfq_row_count_between(1, 2))
+            .with_dataset_rule("table", dfq_row_count_between(1, 10))
+            .with_dataset_rule(
+                "event_day",
+                dfq_freshness(Duration::from_secs(DAY_SECS)).with_clock(clock.clone()),
+            )
+            .with_dataset_rule(
+                "event_time",
+                dfq_freshness(Duration::from_secs(DAY_SECS / 2)).with_clock(clock.clone()),
+            );
+
+        let outcomes = rule_set.dataset_outcomes(&df).await.unwrap();
+        let summary: Vec<(&str, Option<&str>, bool)> = outcomes
+            .iter()
+            .map(|o| (o.outcome_name.as_str(), o.observed.as_deref(), o.passed))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("table_row_count_between", Some("3"), false),
+                ("table_row_count_between", Some("3"), true),
+                ("event_day_freshness", Some("1970-01-13T00:00:00Z"), true),
+                ("event_time_freshness", Some("1970-01-13T01:00:00Z"), false),
+            ]
+        );
+
+        // Dataset rules are not part of dfq_pass
+        let passed = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .filter(col("dfq_pass"))
+            .unwrap()
+            .count()
+            .await
+            .unwrap();
+        assert_eq!(passed, 3);
+    }
+
+    #[tokio::test]
+    async fn test_freshness_stale_and_empty() {
+        let df = create_test_df();
+        let clock = Arc::new(FixedClock::from_unix_secs(20 * DAY_SECS));
+
+        let mut rule_set = RuleSet::new();
+        rule_set.with_dataset_rule(
+            "event_time",
+            dfq_freshness(Duration::from_secs(DAY_SECS)).with_clock(clock),
+        );
+
+        let outcomes = rule_set.dataset_outcomes(&df).await.unwrap();
+        assert!(!outcomes[0].passed);
+
+        let empty = df.filter(lit(false)).unwrap();
+        let outcomes = rule_set.dataset_outcomes(&empty).await.unwrap();
+        assert_eq!(outcomes[0].observed, None);
+        assert!(!outcomes[0].passed);
+    }
+
+    #[tokio::test]
+    async fn test_pass_rate() {
+        let df = create_test_df();
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_dataset_rule("event_day", dfq_mostly(dfq_not_null(), 0.5))
+            .with_dataset_rule("event_time", dfq_mostly(dfq_not_null(), 0.9));
+
+        let outcomes = rule_set.dataset_outcomes(&df).await.unwrap();
+        let summary: Vec<(&str, Option<&str>, bool)> = outcomes
+            .iter()
+            .map(|o| (o.outcome_name.as_str(), o.observed.as_deref(), o.passed))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "event_day_not_null_pass_rate",
+                    Some("0.6666666666666666"),
+                    true
+                ),
+                (
+                    "event_time_not_null_pass_rate",
+                    Some("0.6666666666666666"),
+                    false
+                ),
+            ]
+        );
+
+        // The row-level results are added but do not fail any row
+        let result = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .select_columns(&["id", "event_day_not_null", "dfq_pass"])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+        let expected = vec![
+            "+----+--------------------+----------+",
+            "| id | event_day_not_null | dfq_pass |",
+            "+----+--------------------+----------+",
+            "| 1  | true               | true     |",
+            "| 2  | true               | true     |",
+            "| 3  | false              | true     |",
+            "+----+--------------------+----------+",
+        ];
+        datafusion::assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        let empty = df.clone().filter(lit(false)).unwrap();
+        let outcomes = rule_set.dataset_outcomes(&empty).await.unwrap();
+        assert_eq!(outcomes[0].observed, None);
+        assert!(outcomes[0].passed);
+
+        let mut invalid = RuleSet::new();
+        invalid.with_dataset_rule("event_day", dfq_mostly(dfq_not_null(), 1.5));
+        assert!(matches!(
+            invalid.dataset_outcomes(&df).await,
+            Err(ValidationError::Configuration { .. })
+        ));
+    }
+}
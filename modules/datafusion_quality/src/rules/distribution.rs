@@ -0,0 +1,617 @@
+use crate::{RuleSet, SupportedTypes, TableRule, ValidationError, error::DataFusionSnafu};
+
+use arrow::datatypes::DataType;
+use datafusion::common::JoinType;
+use datafusion::functions::core::expr_fn::nullif;
+use datafusion::functions::math::expr_fn::{abs, floor, ln, log2};
+use datafusion::functions_aggregate::{
+    expr_fn::{max, min, sum},
+    sum::sum_udaf,
+};
+use datafusion::logical_expr::{ExprFunctionExt, Subquery, WindowFrame, expr::WindowFunction};
+use datafusion::prelude::*;
+use futures::future::BoxFuture;
+use snafu::ResultExt;
+use std::sync::Arc;
+
+/// Proportions below this are clamped so empty bins don't produce infinite PSI
+/// or chi-squared contributions
+const EPSILON: f64 = 1e-4;
+
+const VALUE: &str = "__dfq_value";
+const SIDE: &str = "__dfq_side";
+const BIN: &str = "__dfq_bin";
+const CURRENT_N: &str = "__dfq_current_n";
+const REFERENCE_N: &str = "__dfq_reference_n";
+const CURRENT_TOTAL: &str = "__dfq_current_total";
+const REFERENCE_TOTAL: &str = "__dfq_reference_total";
+
+/// How values are grouped into histogram bins
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Binning {
+    /// One bin per distinct value, compared as strings
+    Categorical,
+    /// `n` equal-width bins over the range of the reference values; values
+    /// outside the range fall into the first or last bin
+    EqualWidth(usize),
+}
+
+/// The statistic computed by a [`DistributionRule`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DistributionStatistic {
+    /// Largest distance between the two empirical CDFs, between 0 and 1
+    KolmogorovSmirnov,
+    /// Population stability index, `sum((p - q) * ln(p / q))`
+    PopulationStability,
+    /// Chi-squared statistic of the current counts against the counts expected
+    /// from the reference proportions
+    ChiSquared,
+    /// Jensen-Shannon divergence in bits, between 0 and 1
+    JensenShannon,
+}
+
+impl DistributionStatistic {
+    fn name(&self) -> &'static str {
+        match self {
+            DistributionStatistic::KolmogorovSmirnov => "ks_statistic",
+            DistributionStatistic::PopulationStability => "psi",
+            DistributionStatistic::ChiSquared => "chi_squared",
+            DistributionStatistic::JensenShannon => "js_divergence",
+        }
+    }
+}
+
+/// The reference values of a [`DistributionRule`]
+#[derive(Debug, Clone)]
+enum Reference {
+    DataFrame(Box<DataFrame>),
+    /// A table looked up in the [`RuleContext`](crate::RuleContext) of the RuleSet
+    Table(String),
+}
+
+/// Rule that compares the distribution of a column to the same column of a
+/// reference DataFrame
+#[derive(Debug, Clone)]
+pub struct DistributionRule {
+    reference: Reference,
+    statistic: DistributionStatistic,
+    binning: Binning,
+}
+
+impl DistributionRule {
+    /// Creates a new DistributionRule
+    ///
+    /// # Arguments
+    ///
+    /// * `reference` - The DataFrame holding the reference values of the column
+    /// * `statistic` - The statistic to compute
+    /// * `binning` - How values are grouped into histogram bins; the
+    ///   Kolmogorov-Smirnov statistic always uses the raw numeric values
+    pub fn new(reference: DataFrame, statistic: DistributionStatistic, binning: Binning) -> Self {
+        Self {
+            reference: Reference::DataFrame(Box::new(reference)),
+            statistic,
+            binning,
+        }
+    }
+
+    /// Creates a DistributionRule comparing to a reference table of the RuleSet's
+    /// [`RuleContext`](crate::RuleContext), looked up like [`dfq_in_reference`](crate::rules::reference::dfq_in_reference)
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The name of the reference table
+    /// * `statistic` - The statistic to compute
+    /// * `binning` - How values are grouped into histogram bins
+    pub fn with_reference_table(
+        table: &str,
+        statistic: DistributionStatistic,
+        binning: Binning,
+    ) -> Self {
+        Self {
+            reference: Reference::Table(table.to_string()),
+            statistic,
+            binning,
+        }
+    }
+
+    /// The non-null values of `column_name`, tagged with the side they came from
+    fn values(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        side: i64,
+    ) -> Result<DataFrame, ValidationError> {
+        let value_type = match (self.statistic, self.binning) {
+            (DistributionStatistic::KolmogorovSmirnov, _) | (_, Binning::EqualWidth(_)) => {
+                DataType::Float64
+            }
+            (_, Binning::Categorical) => DataType::Utf8,
+        };
+        df.filter(col(column_name).is_not_null())?
+            .select(vec![
+                cast(col(column_name), value_type).alias(VALUE),
+                lit(side).alias(SIDE),
+            ])
+            .context(DataFusionSnafu)
+    }
+
+    /// The bin of each value
+    fn bin_expr(&self, reference: &DataFrame, column_name: &str) -> Result<Expr, ValidationError> {
+        let bins = match (self.statistic, self.binning) {
+            (DistributionStatistic::KolmogorovSmirnov, _) | (_, Binning::Categorical) => {
+                return Ok(col(VALUE));
+            }
+            (_, Binning::EqualWidth(bins)) => bins.max(1),
+        };
+
+        let reference_bound = |bound: Expr, alias: &str| -> Result<Expr, ValidationError> {
+            let plan = reference
+                .clone()
+                .aggregate(vec![], vec![bound.alias(alias)])?
+                .logical_plan()
+                .clone();
+            Ok(Expr::ScalarSubquery(Subquery {
+                subquery: Arc::new(plan),
+                outer_ref_columns: vec![],
+            }))
+        };
+        let value = cast(col(column_name), DataType::Float64);
+        let low = reference_bound(min(value.clone()), "__dfq_low")?;
+        let high = reference_bound(max(value), "__dfq_high")?;
+
+        let last = i64::try_from(bins - 1).unwrap_or(i64::MAX);
+        let bin = cast(
+            floor((col(VALUE) - low.clone()) / nullif(high - low, lit(0.0)) * lit(bins as f64)),
+            DataType::Int64,
+        );
+        Ok(when(bin.clone().is_null(), lit(0i64))
+            .when(bin.clone().lt(lit(0i64)), lit(0i64))
+            .when(bin.clone().gt(lit(last)), lit(last))
+            .otherwise(bin)?)
+    }
+
+    /// The statistic as a single row, single column DataFrame
+    fn statistic_df(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        reference: &DataFrame,
+    ) -> Result<DataFrame, ValidationError> {
+        let values = self.values(df, column_name, 0)?.union(self.values(
+            reference.clone(),
+            column_name,
+            1,
+        )?)?;
+
+        let count_side = |side: i64| {
+            sum(when(col(SIDE).eq(lit(side)), lit(1i64))
+                .otherwise(lit(0i64))
+                .unwrap_or(lit(0i64)))
+        };
+        let counts = values
+            .select(vec![
+                self.bin_expr(reference, column_name)?.alias(BIN),
+                col(SIDE),
+            ])?
+            .aggregate(
+                vec![col(BIN)],
+                vec![
+                    count_side(0).alias(CURRENT_N),
+                    count_side(1).alias(REFERENCE_N),
+                ],
+            )?;
+        let totals = counts.clone().aggregate(
+            vec![],
+            vec![
+                sum(col(CURRENT_N)).alias(CURRENT_TOTAL),
+                sum(col(REFERENCE_N)).alias(REFERENCE_TOTAL),
+            ],
+        )?;
+        let bins = counts.join(totals, JoinType::Inner, &[], &[], None)?;
+
+        let as_float = |name: &str| cast(col(name), DataType::Float64);
+        let p = as_float(CURRENT_N) / nullif(as_float(CURRENT_TOTAL), lit(0.0));
+        let q = as_float(REFERENCE_N) / nullif(as_float(REFERENCE_TOTAL), lit(0.0));
+        let clamp = |e: Expr| {
+            when(e.clone().lt(lit(EPSILON)), lit(EPSILON))
+                .otherwise(e)
+                .unwrap_or(lit(EPSILON))
+        };
+
+        let statistic = self.statistic.name();
+        let result = match self.statistic {
+            DistributionStatistic::KolmogorovSmirnov => {
+                let cumulative = |e: Expr| {
+                    Expr::WindowFunction(WindowFunction::new(sum_udaf(), vec![e]))
+                        .order_by(vec![col(BIN).sort(true, false)])
+                        .window_frame(WindowFrame::new(Some(true)))
+                        .build()
+                };
+                bins.select(vec![(cumulative(p)? - cumulative(q)?).alias(VALUE)])?
+                    .aggregate(vec![], vec![max(abs(col(VALUE))).alias(statistic)])?
+            }
+            DistributionStatistic::PopulationStability => {
+                let (p, q) = (clamp(p), clamp(q));
+                bins.aggregate(
+                    vec![],
+                    vec![sum((p.clone() - q.clone()) * ln(p / q)).alias(statistic)],
+                )?
+            }
+            DistributionStatistic::ChiSquared => {
+                let expected = clamp(q) * as_float(CURRENT_TOTAL);
+                let diff = as_float(CURRENT_N) - expected.clone();
+                bins.aggregate(
+                    vec![],
+                    vec![sum(diff.clone() * diff / expected).alias(statistic)],
+                )?
+            }
+            DistributionStatistic::JensenShannon => {
+                let m = (p.clone() + q.clone()) / lit(2.0);
+                let kl_term = |x: Expr| {
+                    when(x.clone().gt(lit(0.0)), x.clone() * log2(x / m.clone()))
+                        .otherwise(lit(0.0))
+                        .unwrap_or(lit(0.0))
+                };
+                bins.aggregate(
+                    vec![],
+                    vec![sum((kl_term(p) + kl_term(q)) / lit(2.0)).alias(statistic)],
+                )?
+            }
+        };
+
+        Ok(result)
+    }
+
+    fn apply_reference(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        reference: &DataFrame,
+    ) -> Result<DataFrame, ValidationError> {
+        let new_column_name = self.new_column_name(column_name);
+        let subquery = self
+            .statistic_df(df.clone(), column_name, reference)?
+            .select(vec![
+                col(self.statistic.name()).alias(new_column_name.as_str()),
+            ])?;
+
+        let subquery_expr = Expr::ScalarSubquery(Subquery {
+            subquery: Arc::new(subquery.logical_plan().clone()),
+            outer_ref_columns: vec![],
+        });
+
+        df.with_column(&new_column_name, subquery_expr)
+            .context(DataFusionSnafu)
+    }
+}
+
+impl TableRule for DistributionRule {
+    fn apply(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        match &self.reference {
+            Reference::DataFrame(reference) => self.apply_reference(df, column_name, reference),
+            Reference::Table(table) => Err(ValidationError::Configuration {
+                message: format!(
+                    "Rule '{}' looks up reference table '{}' and must be applied through a RuleSet",
+                    self.name(),
+                    table
+                ),
+            }),
+        }
+    }
+
+    fn apply_with_ruleset(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        rule_set: &RuleSet,
+    ) -> Result<DataFrame, ValidationError> {
+        let Reference::Table(table) = &self.reference else {
+            return self.apply(df, column_name);
+        };
+        let context = rule_set.context();
+        let reference = match context.registered_table(table) {
+            Some(reference) => reference,
+            // Session tables are resolved asynchronously, see apply_async
+            None if context.session_state().is_some() => {
+                return Err(ValidationError::AsyncOnly {
+                    rule_name: self.name().to_string(),
+                });
+            }
+            None => {
+                return Err(ValidationError::Configuration {
+                    message: format!("Unknown reference table '{}'", table),
+                });
+            }
+        };
+        self.apply_reference(df, column_name, &reference)
+    }
+
+    fn apply_async<'a>(
+        &'a self,
+        df: DataFrame,
+        column_name: &'a str,
+        rule_set: &'a RuleSet,
+    ) -> BoxFuture<'a, Result<DataFrame, ValidationError>> {
+        Box::pin(async move {
+            let Reference::Table(table) = &self.reference else {
+                return self.apply(df, column_name);
+            };
+            let reference = rule_set.context().reference_table(table).await?;
+            self.apply_reference(df, column_name, &reference)
+        })
+    }
+
+    fn name(&self) -> &str {
+        self.statistic.name()
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", column_name, self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Compares the distribution of a column to a reference DataFrame"
+    }
+
+    fn supported_types(&self) -> SupportedTypes {
+        // Values are cast to Float64 unless binned by category
+        match (self.statistic, self.binning) {
+            (DistributionStatistic::KolmogorovSmirnov, _) | (_, Binning::EqualWidth(_)) => {
+                SupportedTypes::Numeric
+            }
+            (_, Binning::Categorical) => SupportedTypes::Any,
+        }
+    }
+}
+
+/// Creates a rule computing the two-sample Kolmogorov-Smirnov statistic of a
+/// numeric column against a reference DataFrame.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::distribution::dfq_ks_statistic;
+/// use datafusion_quality::rules::column::dfq_lte;
+/// use datafusion_quality::RuleSet;
+/// use datafusion::prelude::*;
+///
+/// # fn example(reference: DataFrame) {
+/// // Fail when the CDFs of score differ by more than 0.2
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_table_rule("score", dfq_ks_statistic(reference), Some(dfq_lte(lit(0.2))));
+/// # }
+/// ```
+pub fn dfq_ks_statistic(reference: DataFrame) -> Arc<DistributionRule> {
+    Arc::new(DistributionRule::new(
+        reference,
+        DistributionStatistic::KolmogorovSmirnov,
+        Binning::Categorical,
+    ))
+}
+
+/// Creates a rule computing the population stability index of a column against
+/// a reference DataFrame.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::distribution::{dfq_psi, Binning};
+/// use datafusion_quality::rules::column::dfq_lt;
+/// use datafusion_quality::RuleSet;
+/// use datafusion::prelude::*;
+///
+/// # fn example(reference: DataFrame) {
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_table_rule("country", dfq_psi(reference, Binning::Categorical), Some(dfq_lt(lit(0.1))));
+/// # }
+/// ```
+pub fn dfq_psi(reference: DataFrame, binning: Binning) -> Arc<DistributionRule> {
+    Arc::new(DistributionRule::new(
+        reference,
+        DistributionStatistic::PopulationStability,
+        binning,
+    ))
+}
+
+/// Creates a rule computing the chi-squared statistic of a column's counts
+/// against the proportions of a reference DataFrame.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::distribution::{dfq_chi_squared, Binning};
+/// use datafusion_quality::rules::column::dfq_lt;
+/// use datafusion_quality::RuleSet;
+/// use datafusion::prelude::*;
+///
+/// # fn example(reference: DataFrame) {
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_table_rule("status", dfq_chi_squared(reference, Binning::Categorical), Some(dfq_lt(lit(16.9))));
+/// # }
+/// ```
+pub fn dfq_chi_squared(reference: DataFrame, binning: Binning) -> Arc<DistributionRule> {
+    Arc::new(DistributionRule::new(
+        reference,
+        DistributionStatistic::ChiSquared,
+        binning,
+    ))
+}
+
+/// Creates a rule computing the Jensen-Shannon divergence between the
+/// histograms of a column and a reference DataFrame.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::distribution::{dfq_js_divergence, Binning};
+/// use datafusion_quality::rules::column::dfq_lte;
+/// use datafusion_quality::RuleSet;
+/// use datafusion::prelude::*;
+///
+/// # fn example(reference: DataFrame) {
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_table_rule("score", dfq_js_divergence(reference, Binning::EqualWidth(10)), Some(dfq_lte(lit(0.1))));
+/// # }
+/// ```
+pub fn dfq_js_divergence(reference: DataFrame, binning: Binning) -> Arc<DistributionRule> {
+    Arc::new(DistributionRule::new(
+        reference,
+        DistributionStatistic::JensenShannon,
+        binning,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RuleSet;
+    use crate::rules::column::dfq_lte;
+    use arrow::array::{AsArray, Float64Array, StringArray};
+    use arrow::datatypes::{Field, Float64Type, Schema};
+    use arrow::record_batch::RecordBatch;
+
+    fn create_df(ctx: &SessionContext, scores: Vec<f64>, colors: Vec<&str>) -> DataFrame {
+        let schema = Schema::new(vec![
+            Field::new("score", DataType::Float64, true),
+            Field::new("color", DataType::Utf8, true),
+        ]);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Float64Array::from(scores)),
+                Arc::new(StringArray::from(colors)),
+            ],
+        )
+        .unwrap();
+
+        ctx.read_batch(batch).unwrap()
+    }
+
+    async fn statistic(df: &DataFrame, column_name: &str, rule: Arc<DistributionRule>) -> f64 {
+        let name = rule.new_column_name(column_name);
+        let batches = rule
+            .apply(df.clone(), column_name)
+            .unwrap()
+            .select_columns(&[name.as_str()])
+            .unwrap()
+            .limit(0, Some(1))
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        batches[0].column(0).as_primitive::<Float64Type>().value(0)
+    }
+
+    #[tokio::test]
+    async fn test_distribution_statistics() {
+        let ctx = SessionContext::new();
+        let reference = create_df(
+            &ctx,
+            vec![1.0, 2.0, 3.0, 4.0],
+            vec!["red", "red", "blue", "blue"],
+        );
+        let same = create_df(
+            &ctx,
+            vec![4.0, 3.0, 2.0, 1.0],
+            vec!["blue", "red", "blue", "red"],
+        );
+        let shifted = create_df(
+            &ctx,
+            vec![3.0, 4.0, 5.0, 6.0],
+            vec!["red", "red", "red", "green"],
+        );
+
+        // Identical distributions
+        assert_eq!(
+            statistic(&same, "score", dfq_ks_statistic(reference.clone())).await,
+            0.0
+        );
+        assert_eq!(
+            statistic(
+                &same,
+                "color",
+                dfq_psi(reference.clone(), Binning::Categorical)
+            )
+            .await,
+            0.0
+        );
+        assert_eq!(
+            statistic(
+                &same,
+                "color",
+                dfq_chi_squared(reference.clone(), Binning::Categorical)
+            )
+            .await,
+            0.0
+        );
+        assert_eq!(
+            statistic(
+                &same,
+                "score",
+                dfq_js_divergence(reference.clone(), Binning::EqualWidth(2))
+            )
+            .await,
+            0.0
+        );
+
+        // Half of the shifted values are beyond the reference range
+        assert_eq!(
+            statistic(&shifted, "score", dfq_ks_statistic(reference.clone())).await,
+            0.5
+        );
+
+        // Reference bins [1, 2.5) and [2.5, 4] hold 2/4 each; shifted values all
+        // land in the upper bin: JS = 0.5 * (1 * log2(1 / 0.75)) + 0.5 * (0.5 * log2(0.5 / 0.75) + 0.5 * log2(0.5 / 0.25))
+        let js = statistic(
+            &shifted,
+            "score",
+            dfq_js_divergence(reference.clone(), Binning::EqualWidth(2)),
+        )
+        .await;
+        let expected =
+            0.5 * (4.0f64 / 3.0).log2() + 0.5 * (0.5 * (2.0f64 / 3.0).log2() + 0.5 * 2.0f64.log2());
+        assert!((js - expected).abs() < 1e-9, "{js} != {expected}");
+
+        // Categorical PSI with an unseen category
+        let psi = statistic(
+            &shifted,
+            "color",
+            dfq_psi(reference.clone(), Binning::Categorical),
+        )
+        .await;
+        assert!(psi > 1.0, "{psi}");
+    }
+
+    #[tokio::test]
+    async fn test_distribution_rule_check() {
+        let ctx = SessionContext::new();
+        let reference = create_df(&ctx, vec![1.0, 2.0, 3.0, 4.0], vec!["a", "a", "b", "b"]);
+        let df = create_df(&ctx, vec![3.0, 4.0, 5.0, 6.0], vec!["a", "b", "b", "b"]);
+
+        let mut rule_set = RuleSet::new();
+        rule_set.with_table_rule(
+            "score",
+            dfq_ks_statistic(reference),
+            Some(dfq_lte(lit(0.2))),
+        );
+
+        let batches = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .select_columns(&["score_ks_statistic", "dfq_pass"])
+            .unwrap()
+            .limit(0, Some(1))
+            .unwrap()
+            .collect()
+            .await
+            .unwrap();
+        assert_eq!(
+            batches[0].column(0).as_primitive::<Float64Type>().value(0),
+            0.5
+        );
+        assert!(!batches[0].column(1).as_boolean().value(0));
+    }
+}
@@ -1,15 +1,69 @@
+use crate::ValidationError;
+use datafusion::logical_expr::LogicalPlan;
+use datafusion::prelude::{DataFrame, Expr};
+
 pub mod column;
+pub mod combinator;
+pub mod conditional;
+pub mod dataset;
+pub mod distribution;
+pub mod nested;
+pub mod reference;
 pub mod schema;
 pub mod table;
+pub mod temporal;
 
 pub use column::*;
+pub use combinator::*;
+pub use conditional::*;
+pub use dataset::*;
+pub use distribution::*;
+pub use nested::*;
+pub use reference::*;
 pub use schema::*;
 pub use table::*;
+pub use temporal::*;
 
-pub use table::{dfq_avg, dfq_count, dfq_max, dfq_min, dfq_null_count, dfq_stddev, dfq_sum};
+pub use table::{
+    dfq_avg, dfq_count, dfq_custom_agg_sql, dfq_max, dfq_min, dfq_null_count, dfq_stddev, dfq_sum,
+};
 
 pub use column::{
-    dfq_custom, dfq_eq, dfq_gt, dfq_gte, dfq_ilike, dfq_in_range, dfq_like, dfq_lt, dfq_lte,
-    dfq_not_eq, dfq_not_ilike, dfq_not_like, dfq_not_null, dfq_null, dfq_str_empty, dfq_str_length,
-    dfq_str_max_length, dfq_str_min_length, dfq_str_not_empty,
+    dfq_custom, dfq_custom_sql, dfq_eq, dfq_gt, dfq_gte, dfq_ilike, dfq_in_range, dfq_is_email,
+    dfq_is_ipv4, dfq_is_uuid, dfq_like, dfq_lt, dfq_lte, dfq_not_eq, dfq_not_ilike, dfq_not_like,
+    dfq_not_null, dfq_null, dfq_str_empty, dfq_str_length, dfq_str_max_length, dfq_str_min_length,
+    dfq_str_not_empty,
 };
+
+/// Parse a SQL expression against the schema of `df`, naming the rule and
+/// column in the error if it cannot be planned
+pub(crate) fn parse_rule_sql(
+    df: &DataFrame,
+    sql: &str,
+    rule_name: &str,
+    column_name: &str,
+) -> Result<Expr, ValidationError> {
+    df.parse_sql_expr(sql)
+        .map_err(|e| ValidationError::Configuration {
+            message: format!(
+                "Rule '{}' on column '{}' has an invalid SQL expression '{}': {}",
+                rule_name, column_name, sql, e
+            ),
+        })
+}
+
+/// The expression a rule added as `column_name` with `DataFrame::with_column`,
+/// if it was added directly on top of `input`
+pub(crate) fn added_expr(result: &DataFrame, input: &DataFrame, column_name: &str) -> Option<Expr> {
+    match result.logical_plan() {
+        LogicalPlan::Projection(projection)
+            if projection.input.as_ref() == input.logical_plan() =>
+        {
+            projection.expr.iter().find_map(|expr| match expr {
+                Expr::Alias(alias) if alias.name == column_name => Some(*alias.expr.clone()),
+                _ => None,
+            })
+        }
+        _ => None,
+    }
+}
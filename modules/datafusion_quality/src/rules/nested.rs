@@ -0,0 +1,680 @@
+use crate::{
+    ColumnRule, RuleSet, SupportedTypes, ValidationError,
+    error::DataFusionSnafu,
+    rules::column::{dfq_in_range, dfq_not_null},
+};
+
+use arrow::datatypes::DataType;
+use datafusion::common::{Column, ScalarValue, UnnestOptions};
+use datafusion::functions::core::expr_fn::get_field;
+use datafusion::functions_aggregate::expr_fn::{bool_and, first_value};
+use datafusion::functions_nested::expr_fn::{array_distinct, cardinality};
+use datafusion::functions_window::expr_fn::row_number;
+use datafusion::prelude::*;
+use futures::future::BoxFuture;
+use snafu::ResultExt;
+use std::sync::Arc;
+
+/// A nested column path such as `payload.user.id` or `items[*].price`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct NestedPath {
+    /// The top-level column
+    column: String,
+    /// The struct fields and map keys applied to the column and then to the
+    /// elements of each list step, one more than there are `[*]` steps
+    levels: Vec<Vec<String>>,
+}
+
+/// Parse a path such as `payload.user.id` or `items[*].price`
+fn parse_path(path: &str) -> Result<NestedPath, ValidationError> {
+    let invalid = || ValidationError::Configuration {
+        message: format!("Invalid nested column path '{}'", path),
+    };
+
+    let mut column = None;
+    let mut levels = Vec::new();
+    let mut fields = Vec::new();
+    for part in path.split('.') {
+        let mut name = part;
+        let mut elements = 0;
+        while let Some(stripped) = name.strip_suffix("[*]") {
+            name = stripped;
+            elements += 1;
+        }
+        if name.is_empty() || name.contains(['[', ']']) {
+            return Err(invalid());
+        }
+        match column {
+            None => column = Some(name.to_string()),
+            Some(_) => fields.push(name.to_string()),
+        }
+        for _ in 0..elements {
+            levels.push(std::mem::take(&mut fields));
+        }
+    }
+    levels.push(fields);
+    Ok(NestedPath {
+        column: column.ok_or_else(invalid)?,
+        levels,
+    })
+}
+
+/// The name of the result column for a path, e.g. `items_price` for `items[*].price`
+fn path_column_name(path: &str) -> String {
+    path.replace("[*]", "").replace('.', "_")
+}
+
+/// Apply struct field and map key steps to `base`
+fn field_expr(base: Expr, fields: &[String]) -> Expr {
+    fields
+        .iter()
+        .fold(base, |expr, name| get_field(expr, name.as_str()))
+}
+
+/// The expression for a path without list steps, for rules over a single value
+fn path_expr(path: &str, rule_name: &str) -> Result<Expr, ValidationError> {
+    let NestedPath { column, levels } = parse_path(path)?;
+    match levels.as_slice() {
+        [fields] => Ok(field_expr(ident(column), fields)),
+        _ => Err(ValidationError::Configuration {
+            message: format!(
+                "Rule '{}' does not support '[*]' in '{}', wrap it in dfq_nested instead",
+                rule_name, path
+            ),
+        }),
+    }
+}
+
+/// The number of elements in a list, 0 for an empty list and null for a null list
+fn list_length(list: Expr) -> Result<Expr, ValidationError> {
+    // cardinality is null for empty lists
+    when(list.clone().is_null(), lit(ScalarValue::UInt64(None)))
+        .otherwise(coalesce(vec![cardinality(list), lit(0u64)]))
+        .context(DataFusionSnafu)
+}
+
+/// Rule that applies another column rule to a nested path
+#[derive(Debug, Clone)]
+pub struct NestedRule {
+    inner: Arc<dyn ColumnRule>,
+    name: String,
+    /// Extra list steps appended to the path the rule is registered with
+    elements: bool,
+}
+
+impl NestedRule {
+    /// Creates a new NestedRule
+    ///
+    /// # Arguments
+    ///
+    /// * `inner` - The rule to apply to the value at the path
+    pub fn new(inner: Arc<dyn ColumnRule>) -> Self {
+        Self {
+            name: inner.name().to_string(),
+            inner,
+            elements: false,
+        }
+    }
+
+    /// Creates a rule applying `inner` to every element of a list column
+    fn elements(inner: Arc<dyn ColumnRule>, name: String) -> Self {
+        Self {
+            inner,
+            name,
+            elements: true,
+        }
+    }
+
+    /// Add a `VALUE` column with the value at `column_name`, unnesting every list
+    /// step so each element is on its own row
+    ///
+    /// Returns the list steps, for [`NestedRule::aggregate_elements`].
+    fn unnest_path(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+    ) -> Result<(DataFrame, Vec<ListStep>), ValidationError> {
+        let NestedPath { column, mut levels } = parse_path(column_name)?;
+        if self.elements {
+            levels.push(Vec::new());
+        }
+
+        let mut df = df;
+        let mut steps = Vec::new();
+        let mut value = ident(column);
+        for (depth, fields) in levels.iter().enumerate() {
+            if depth > 0 {
+                let step = ListStep::new(&df, depth);
+                // Empty and null lists keep a single row with a null element
+                // Cast, so the row number is not a key of the unnested rows, whose
+                // other columns grouping would add back
+                df = df
+                    .with_column(&step.row, cast(row_number(), DataType::Int64))?
+                    .with_column(
+                        &step.has_elements,
+                        cardinality(value.clone()).gt(lit(0u64)).is_true(),
+                    )?
+                    .with_column(&step.element, when(col(&step.has_elements), value).end()?)?
+                    .unnest_columns_with_options(
+                        &[&step.element],
+                        UnnestOptions::new().with_preserve_nulls(true),
+                    )?;
+                value = col(&step.element);
+                steps.push(step);
+            }
+            value = field_expr(value, fields);
+        }
+        df.with_column(VALUE, value)
+            .context(DataFusionSnafu)
+            .map(|df| (df, steps))
+    }
+
+    /// Rename the inner rule's result for the `VALUE` column to the rule's result
+    /// column, checking all elements of each list passed
+    ///
+    /// Each list step is aggregated back to one row per list by the row number it
+    /// was given before unnesting. Both happen in the same plan, so the numbering
+    /// does not depend on the order rows are read in.
+    fn aggregate_elements(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        steps: Vec<ListStep>,
+    ) -> Result<DataFrame, ValidationError> {
+        let mut df = df
+            .with_column_renamed(self.inner.new_column_name(VALUE), RESULT)?
+            .drop_columns(&[VALUE])?;
+        for step in steps.into_iter().rev() {
+            let passed =
+                bool_and(col(&step.has_elements).not().or(col(RESULT).is_true())).alias(RESULT);
+            // Every row of a list has the same values in the other columns
+            let values = step.columns.iter().map(|column| {
+                first_value(Expr::Column(column.clone()), None)
+                    .alias_qualified(column.relation.clone(), &column.name)
+            });
+            let columns = step
+                .columns
+                .iter()
+                .cloned()
+                .map(Expr::Column)
+                .chain(std::iter::once(col(RESULT)));
+            df = df
+                .aggregate(
+                    vec![col(&step.row)],
+                    std::iter::once(passed).chain(values).collect(),
+                )?
+                .select(columns.collect())?;
+        }
+        df.with_column_renamed(RESULT, &self.new_column_name(column_name))
+            .context(DataFusionSnafu)
+    }
+}
+
+const VALUE: &str = "__dfq_value";
+const RESULT: &str = "__dfq_result";
+
+/// A `[*]` step of a nested path, unnested by [`NestedRule::unnest_path`]
+#[derive(Debug, Clone)]
+struct ListStep {
+    /// The columns before unnesting
+    columns: Vec<Column>,
+    /// The row number of the list
+    row: String,
+    /// Whether the list has elements; empty and null lists pass
+    has_elements: String,
+    /// The element, one per row
+    element: String,
+}
+
+impl ListStep {
+    fn new(df: &DataFrame, depth: usize) -> Self {
+        Self {
+            columns: df.schema().columns(),
+            row: format!("__dfq_row_{}", depth),
+            has_elements: format!("__dfq_has_elements_{}", depth),
+            element: format!("__dfq_element_{}", depth),
+        }
+    }
+}
+
+impl ColumnRule for NestedRule {
+    fn apply(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        let (df, steps) = self.unnest_path(df, column_name)?;
+        let df = self.inner.apply(df, VALUE)?;
+        self.aggregate_elements(df, column_name, steps)
+    }
+
+    fn apply_with_ruleset(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        rule_set: &RuleSet,
+    ) -> Result<DataFrame, ValidationError> {
+        let (df, steps) = self.unnest_path(df, column_name)?;
+        let df = self.inner.apply_with_ruleset(df, VALUE, rule_set)?;
+        self.aggregate_elements(df, column_name, steps)
+    }
+
+    fn apply_async<'a>(
+        &'a self,
+        df: DataFrame,
+        column_name: &'a str,
+        rule_set: &'a RuleSet,
+    ) -> BoxFuture<'a, Result<DataFrame, ValidationError>> {
+        Box::pin(async move {
+            let (df, steps) = self.unnest_path(df, column_name)?;
+            let df = self.inner.apply_async(df, VALUE, rule_set).await?;
+            self.aggregate_elements(df, column_name, steps)
+        })
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", path_column_name(column_name), self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Applies a column rule to a nested struct field, map value or list elements"
+    }
+
+    fn supported_types(&self) -> SupportedTypes {
+        if self.elements {
+            SupportedTypes::List
+        } else {
+            self.inner.supported_types()
+        }
+    }
+}
+
+/// Creates a rule that applies `rule` to a nested path instead of a top-level column.
+///
+/// Register it with the path as the column name. Struct fields and map keys are
+/// separated by `.` and `[*]` steps into every element of a list; a row passes
+/// when the rule holds for all its elements, so empty and null lists pass. The
+/// result column is named after the path with `.` replaced by `_` and `[*]`
+/// removed, e.g. `items_price_in_range`.
+///
+/// # Arguments
+///
+/// * `rule` - The rule to apply to the value at the path
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::{column::*, nested::dfq_nested};
+/// use datafusion_quality::RuleSet;
+///
+/// let mut ruleset = RuleSet::new();
+/// ruleset
+///     .with_column_rule("payload.user.id", dfq_nested(dfq_not_null()))
+///     .with_column_rule("items[*].price", dfq_nested(dfq_in_range(0.0, 10_000.0)));
+/// ```
+pub fn dfq_nested(rule: Arc<dyn ColumnRule>) -> Arc<NestedRule> {
+    Arc::new(NestedRule::new(rule))
+}
+
+/// Creates a rule that checks `rule` holds for every element of a list column.
+///
+/// The column name may be a nested path, see [`dfq_nested`]. Empty and null lists
+/// pass.
+///
+/// # Arguments
+///
+/// * `rule` - The rule to apply to each element
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::{column::*, nested::dfq_list_all};
+/// use datafusion_quality::RuleSet;
+///
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule("tags", dfq_list_all(dfq_str_not_empty()));
+/// ```
+pub fn dfq_list_all(rule: Arc<dyn ColumnRule>) -> Arc<NestedRule> {
+    let name = format!("list_all_{}", rule.name());
+    Arc::new(NestedRule::elements(rule, name))
+}
+
+/// Creates a rule that checks no element of a list column is null.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::nested::dfq_list_all_not_null;
+/// use datafusion_quality::RuleSet;
+///
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule("payload.scores", dfq_list_all_not_null());
+/// ```
+pub fn dfq_list_all_not_null() -> Arc<NestedRule> {
+    dfq_list_all(dfq_not_null())
+}
+
+/// Creates a rule that checks every element of a list column is within a range.
+///
+/// # Arguments
+///
+/// * `min` - The minimum value of the range (inclusive)
+/// * `max` - The maximum value of the range (inclusive)
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::nested::dfq_list_all_in_range;
+/// use datafusion_quality::RuleSet;
+///
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule("scores", dfq_list_all_in_range(0.0, 100.0));
+/// ```
+pub fn dfq_list_all_in_range(min: f64, max: f64) -> Arc<NestedRule> {
+    dfq_list_all(dfq_in_range(min, max))
+}
+
+/// Rule that checks the number of elements in a list column
+#[derive(Debug, Clone)]
+pub struct ListLengthRule {
+    min: Option<u32>,
+    max: Option<u32>,
+}
+
+impl ListLengthRule {
+    pub fn new(min: Option<u32>, max: Option<u32>) -> Self {
+        Self { min, max }
+    }
+}
+
+impl ColumnRule for ListLengthRule {
+    fn apply(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        let length = list_length(path_expr(column_name, self.name())?)?;
+        let within = match (self.min, self.max) {
+            (Some(min), Some(max)) => length.between(lit(u64::from(min)), lit(u64::from(max))),
+            (Some(min), None) => length.gt_eq(lit(u64::from(min))),
+            (None, Some(max)) => length.lt_eq(lit(u64::from(max))),
+            (None, None) => length.is_not_null(),
+        };
+
+        df.with_column(&self.new_column_name(column_name), within)
+            .context(DataFusionSnafu)
+    }
+
+    fn name(&self) -> &str {
+        "list_length"
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", path_column_name(column_name), self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Checks the number of elements in a list column is within bounds"
+    }
+
+    fn supported_types(&self) -> SupportedTypes {
+        SupportedTypes::List
+    }
+}
+
+/// Creates a rule that checks the number of elements in a list column is within
+/// bounds. Null lists have no length and do not pass.
+///
+/// # Arguments
+///
+/// * `min` - The minimum number of elements (inclusive), if any
+/// * `max` - The maximum number of elements (inclusive), if any
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::nested::dfq_list_length;
+/// use datafusion_quality::RuleSet;
+///
+/// // Every order has between 1 and 50 items
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule("items", dfq_list_length(Some(1), Some(50)));
+/// ```
+pub fn dfq_list_length(min: Option<u32>, max: Option<u32>) -> Arc<ListLengthRule> {
+    Arc::new(ListLengthRule::new(min, max))
+}
+
+/// Rule that checks a list column has no duplicate elements
+#[derive(Debug, Clone, Default)]
+pub struct ListDistinctRule;
+
+impl ColumnRule for ListDistinctRule {
+    fn apply(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        let list = path_expr(column_name, self.name())?;
+        let distinct = list_length(array_distinct(list.clone()))?.eq(list_length(list)?);
+
+        df.with_column(&self.new_column_name(column_name), distinct)
+            .context(DataFusionSnafu)
+    }
+
+    fn name(&self) -> &str {
+        "list_distinct"
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", path_column_name(column_name), self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Checks a list column has no duplicate elements"
+    }
+
+    fn supported_types(&self) -> SupportedTypes {
+        SupportedTypes::List
+    }
+}
+
+/// Creates a rule that checks a list column has no duplicate elements.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::nested::dfq_list_distinct;
+/// use datafusion_quality::RuleSet;
+///
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule("payload.tags", dfq_list_distinct());
+/// ```
+pub fn dfq_list_distinct() -> Arc<ListDistinctRule> {
+    Arc::new(ListDistinctRule)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RuleSet;
+    use crate::rules::column::*;
+    use arrow::array::{
+        Array, ArrayRef, Float64Array, Float64Builder, Int32Array, ListArray, ListBuilder,
+        StringArray, StructArray,
+    };
+    use arrow::buffer::OffsetBuffer;
+    use arrow::datatypes::Field;
+    use arrow::record_batch::RecordBatch;
+    use datafusion::{assert_batches_eq, datasource::MemTable};
+
+    fn create_test_df() -> DataFrame {
+        // payload: { user: { id: Utf8 } }
+        let user = StructArray::from(vec![(
+            Arc::new(Field::new("id", DataType::Utf8, true)),
+            Arc::new(StringArray::from(vec![
+                Some("u1"),
+                None,
+                Some("u3"),
+                Some("u4"),
+            ])) as ArrayRef,
+        )]);
+        let payload = StructArray::from(vec![(
+            Arc::new(Field::new("user", user.data_type().clone(), true)),
+            Arc::new(user) as ArrayRef,
+        )]);
+
+        // scores: [[10, 20], [20, 20], [], null]
+        let mut scores = ListBuilder::new(Float64Builder::new());
+        scores.append_value([Some(10.0), Some(20.0)]);
+        scores.append_value([Some(20.0), Some(20.0)]);
+        scores.append_value([]);
+        scores.append_null();
+
+        // items: [{ price }] with one negative and one null price
+        let price_field = Arc::new(Field::new("price", DataType::Float64, true));
+        let prices = |values: Vec<Option<f64>>| {
+            StructArray::from(vec![(
+                price_field.clone(),
+                Arc::new(Float64Array::from(values)) as ArrayRef,
+            )])
+        };
+        let item_field = Arc::new(Field::new(
+            "item",
+            DataType::Struct(vec![price_field.clone()].into()),
+            true,
+        ));
+        let items = ListArray::new(
+            item_field,
+            OffsetBuffer::from_lengths([2, 1, 0, 1]),
+            Arc::new(prices(vec![Some(1.0), Some(2.0), Some(-1.0), None])),
+            None,
+        );
+
+        let batch = RecordBatch::try_from_iter(vec![
+            (
+                "id",
+                Arc::new(Int32Array::from(vec![1, 2, 3, 4])) as ArrayRef,
+            ),
+            ("payload", Arc::new(payload) as ArrayRef),
+            ("scores", Arc::new(scores.finish()) as ArrayRef),
+            ("items", Arc::new(items) as ArrayRef),
+        ])
+        .unwrap();
+
+        SessionContext::new().read_batch(batch).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_nested_rules() {
+        let df = create_test_df();
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("payload.user.id", dfq_nested(dfq_not_null()))
+            .with_column_rule("items[*].price", dfq_nested(dfq_in_range(0.0, 10.0)))
+            .with_column_rule("id", dfq_nested(dfq_gt(lit(1))));
+
+        let result = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .select_columns(&[
+                "id",
+                "payload_user_id_not_null",
+                "items_price_in_range",
+                "id_greater_than",
+                "dfq_pass",
+            ])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+
+        let expected = vec![
+            "+----+--------------------------+----------------------+-----------------+----------+",
+            "| id | payload_user_id_not_null | items_price_in_range | id_greater_than | dfq_pass |",
+            "+----+--------------------------+----------------------+-----------------+----------+",
+            "| 1  | true                     | true                 | false           | false    |",
+            "| 2  | false                    | false                | true            | false    |",
+            "| 3  | true                     | true                 | true            | true     |",
+            "| 4  | true                     | false                | true            | false    |",
+            "+----+--------------------------+----------------------+-----------------+----------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_list_rules() {
+        let df = create_test_df();
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("scores", dfq_list_length(Some(1), None))
+            .with_column_rule("scores", dfq_list_all_not_null())
+            .with_column_rule("scores", dfq_list_all_in_range(15.0, 100.0))
+            .with_column_rule("scores", dfq_list_distinct());
+
+        let result = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .select_columns(&[
+                "id",
+                "scores_list_length",
+                "scores_list_all_not_null",
+                "scores_list_all_in_range",
+                "scores_list_distinct",
+            ])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+
+        let expected = vec![
+            "+----+--------------------+--------------------------+--------------------------+----------------------+",
+            "| id | scores_list_length | scores_list_all_not_null | scores_list_all_in_range | scores_list_distinct |",
+            "+----+--------------------+--------------------------+--------------------------+----------------------+",
+            "| 1  | true               | true                     | false                    | true                 |",
+            "| 2  | true               | true                     | true                     | false                |",
+            "| 3  | false              | true                     | true                     | true                 |",
+            "| 4  |                    | true                     | true                     |                      |",
+            "+----+--------------------+--------------------------+--------------------------+----------------------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        assert!(matches!(
+            dfq_list_length(None, Some(1)).apply(df, "items[*].price"),
+            Err(ValidationError::Configuration { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_nested_rules_partitioned() {
+        // One row per partition, so unnested rows are read back in any order
+        let batches = create_test_df().collect().await.unwrap();
+        let partitions: Vec<Vec<RecordBatch>> = (0..batches[0].num_rows())
+            .map(|i| vec![batches[0].slice(i, 1)])
+            .collect();
+        let table = MemTable::try_new(batches[0].schema(), partitions).unwrap();
+        let df = SessionContext::new().read_table(Arc::new(table)).unwrap();
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("items[*].price", dfq_nested(dfq_in_range(0.0, 10.0)))
+            .with_column_rule("scores", dfq_list_all_in_range(15.0, 100.0));
+
+        let result = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .select_columns(&["id", "items_price_in_range", "scores_list_all_in_range"])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+
+        let expected = vec![
+            "+----+----------------------+--------------------------+",
+            "| id | items_price_in_range | scores_list_all_in_range |",
+            "+----+----------------------+--------------------------+",
+            "| 1  | true                 | false                    |",
+            "| 2  | false                | true                     |",
+            "| 3  | true                 | true                     |",
+            "| 4  | false                | true                     |",
+            "+----+----------------------+--------------------------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+    }
+}
@@ -0,0 +1,349 @@
+use crate::{ColumnRule, RuleSet, ValidationError, error::DataFusionSnafu};
+
+use datafusion::{common::ScalarValue, logical_expr::JoinType, prelude::*};
+use futures::future::BoxFuture;
+use snafu::ResultExt;
+use std::sync::Arc;
+
+const REFERENCE_KEY: &str = "__dfq_reference_key";
+const REFERENCE_FOUND: &str = "__dfq_reference_found";
+
+/// Rule that checks that values exist in a column of a reference table
+///
+/// The reference table is looked up by name in the [`RuleContext`](crate::RuleContext)
+/// of the RuleSet, first among its reference tables and then in its session.
+#[derive(Debug, Clone)]
+pub struct ReferenceRule {
+    table: String,
+    column: String,
+}
+
+impl ReferenceRule {
+    /// Creates a new ReferenceRule
+    ///
+    /// # Arguments
+    ///
+    /// * `table` - The name of the reference table
+    /// * `column` - The column of the reference table holding the allowed values
+    pub fn new(table: &str, column: &str) -> Self {
+        Self {
+            table: table.to_string(),
+            column: column.to_string(),
+        }
+    }
+
+    fn apply_reference(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        reference: DataFrame,
+    ) -> Result<DataFrame, ValidationError> {
+        let reference = reference
+            .select(vec![col(&self.column).alias(REFERENCE_KEY)])?
+            .distinct()?
+            .with_column(REFERENCE_FOUND, lit(true))?;
+
+        df.join_on(
+            reference,
+            JoinType::Left,
+            [col(column_name).eq(col(REFERENCE_KEY))],
+        )?
+        .with_column(
+            &self.new_column_name(column_name),
+            when(col(column_name).is_null(), lit(ScalarValue::Boolean(None)))
+                .otherwise(col(REFERENCE_FOUND).is_not_null())?,
+        )?
+        .drop_columns(&[REFERENCE_KEY, REFERENCE_FOUND])
+        .context(DataFusionSnafu)
+    }
+}
+
+impl ColumnRule for ReferenceRule {
+    fn apply(&self, _df: DataFrame, _column_name: &str) -> Result<DataFrame, ValidationError> {
+        Err(ValidationError::Configuration {
+            message: format!(
+                "Rule '{}' looks up reference table '{}' and must be applied through a RuleSet",
+                self.name(),
+                self.table
+            ),
+        })
+    }
+
+    fn apply_with_ruleset(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        rule_set: &RuleSet,
+    ) -> Result<DataFrame, ValidationError> {
+        let context = rule_set.context();
+        let reference = match context.registered_table(&self.table) {
+            Some(reference) => reference,
+            // Session tables are resolved asynchronously, see apply_async
+            None if context.session_state().is_some() => {
+                return Err(ValidationError::AsyncOnly {
+                    rule_name: self.name().to_string(),
+                });
+            }
+            None => {
+                return Err(ValidationError::Configuration {
+                    message: format!("Unknown reference table '{}'", self.table),
+                });
+            }
+        };
+        self.apply_reference(df, column_name, reference)
+    }
+
+    fn apply_async<'a>(
+        &'a self,
+        df: DataFrame,
+        column_name: &'a str,
+        rule_set: &'a RuleSet,
+    ) -> BoxFuture<'a, Result<DataFrame, ValidationError>> {
+        Box::pin(async move {
+            let reference = rule_set.context().reference_table(&self.table).await?;
+            self.apply_reference(df, column_name, reference)
+        })
+    }
+
+    fn name(&self) -> &str {
+        "in_reference"
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", column_name, self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Checks if values in a column exist in a column of a reference table"
+    }
+}
+
+/// Creates a rule that checks if values in a column exist in a column of a reference table.
+///
+/// Null values stay null. The table is looked up in the [`RuleContext`](crate::RuleContext)
+/// of the RuleSet.
+///
+/// # Arguments
+///
+/// * `table` - The name of the reference table
+/// * `column` - The column of the reference table holding the allowed values
+///
+/// # Examples
+///
+/// ```
+/// use datafusion::prelude::*;
+/// use datafusion_quality::rules::reference::dfq_in_reference;
+/// use datafusion_quality::{RuleContext, RuleSet};
+///
+/// let ctx = SessionContext::new();
+/// let mut ruleset = RuleSet::new();
+/// ruleset
+///     .with_column_rule("country", dfq_in_reference("countries", "code"))
+///     .with_context(RuleContext::new().with_session_state(ctx.state()));
+/// ```
+pub fn dfq_in_reference(table: &str, column: &str) -> Arc<ReferenceRule> {
+    Arc::new(ReferenceRule::new(table, column))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RuleContext;
+    use crate::rules::column::dfq_custom_sql;
+    use crate::rules::table::dfq_custom_agg_sql;
+    use datafusion::assert_batches_eq;
+
+    #[tokio::test]
+    async fn test_in_reference() {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE countries (code VARCHAR) AS VALUES ('FR'), ('DE'), ('FR')")
+            .await
+            .unwrap();
+        let df = ctx
+            .sql(
+                "SELECT * FROM (VALUES (1, 'FR', DATE '2024-01-30'), (2, 'XX', DATE '2024-02-01'), \
+                 (3, NULL, DATE '2024-01-31')) AS t(id, country, event_date)",
+            )
+            .await
+            .unwrap();
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("country", dfq_in_reference("countries", "code"))
+            .with_column_rule(
+                "event_date",
+                dfq_custom_sql("not_after_run", "event_date <= DATE '${run_date}'"),
+            )
+            .with_table_rule(
+                "id",
+                dfq_custom_agg_sql("count(id) >= ${min_rows}", "min_rows"),
+                None,
+            )
+            .with_context(
+                RuleContext::new()
+                    .with_session_state(ctx.state())
+                    .with_run_date("2024-01-31")
+                    .with_variable("min_rows", "3"),
+            );
+
+        let result = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .select_columns(&[
+                "id",
+                "country_in_reference",
+                "event_date_not_after_run",
+                "id_min_rows",
+                "dfq_pass",
+            ])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+
+        let expected = vec![
+            "+----+----------------------+--------------------------+-------------+----------+",
+            "| id | country_in_reference | event_date_not_after_run | id_min_rows | dfq_pass |",
+            "+----+----------------------+--------------------------+-------------+----------+",
+            "| 1  | true                 | true                     | true        | true     |",
+            "| 2  | false                | false                    | true        | false    |",
+            "| 3  |                      | true                     | true        |          |",
+            "+----+----------------------+--------------------------+-------------+----------+",
+        ];
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        // Registered reference tables take precedence over the session's tables
+        let allowed = ctx
+            .sql("SELECT * FROM (VALUES ('XX')) AS t(code)")
+            .await
+            .unwrap();
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("country", dfq_in_reference("countries", "code"))
+            .with_context(RuleContext::new().with_reference_table("countries", allowed));
+        let result = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .filter(col("country_in_reference"))
+            .unwrap();
+        assert_eq!(result.count().await.unwrap(), 1);
+
+        // Unknown reference tables and variables are configuration errors
+        let mut rule_set = RuleSet::new();
+        rule_set.with_column_rule("country", dfq_in_reference("countries", "code"));
+        assert!(matches!(
+            rule_set.apply(&df).await,
+            Err(ValidationError::Configuration { .. })
+        ));
+        let mut rule_set = RuleSet::new();
+        rule_set.with_column_rule("event_date", dfq_custom_sql("after", "${unknown}"));
+        assert!(matches!(
+            rule_set.apply(&df).await,
+            Err(ValidationError::Configuration { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_wrapped_reference_rules() {
+        use crate::rules::{combinator::dfq_not, dataset::dfq_mostly, nested::dfq_nested};
+        use crate::spec::RuleSetSpec;
+
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE countries (code VARCHAR) AS VALUES ('FR'), ('DE')")
+            .await
+            .unwrap();
+        let df = ctx
+            .sql(
+                "SELECT *, named_struct('country', country) AS address FROM (VALUES \
+                 (1, 'FR', DATE '2024-01-30'), (2, 'XX', DATE '2024-02-01'), \
+                 (3, NULL, DATE '2024-01-31')) AS t(id, country, event_date)",
+            )
+            .await
+            .unwrap();
+        let context = RuleContext::new()
+            .with_session_state(ctx.state())
+            .with_run_date("2024-01-31");
+
+        // Wrapped rules see the context of the RuleSet
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("country", dfq_not(dfq_in_reference("countries", "code")))
+            .with_column_rule(
+                "address.country",
+                dfq_nested(dfq_in_reference("countries", "code")),
+            )
+            .with_dataset_rule(
+                "country",
+                dfq_mostly(dfq_in_reference("countries", "code"), 0.3),
+            )
+            .with_dataset_rule(
+                "event_date",
+                dfq_mostly(
+                    dfq_custom_sql("before", "event_date < DATE '${run_date}'"),
+                    0.5,
+                ),
+            )
+            .with_context(context.clone());
+
+        let outcomes = rule_set.dataset_outcomes(&df).await.unwrap();
+        assert_eq!(outcomes[0].outcome_name, "country_in_reference_pass_rate");
+        assert!(outcomes[0].passed);
+        assert_eq!(outcomes[1].outcome_name, "event_date_before_pass_rate");
+        assert!(!outcomes[1].passed);
+
+        let result = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .select_columns(&[
+                "id",
+                "country_not_in_reference",
+                "address_country_in_reference",
+                "country_in_reference",
+                "event_date_before",
+            ])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+
+        let expected = vec![
+            "+----+--------------------------+------------------------------+----------------------+-------------------+",
+            "| id | country_not_in_reference | address_country_in_reference | country_in_reference | event_date_before |",
+            "+----+--------------------------+------------------------------+----------------------+-------------------+",
+            "| 1  | false                    | true                         | true                 | true              |",
+            "| 2  | true                     | false                        | false                | false             |",
+            "| 3  |                          |                              |                      | false             |",
+            "+----+--------------------------+------------------------------+----------------------+-------------------+",
+        ];
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        // The same rules from a spec
+        let mut rule_set = RuleSetSpec::from_yaml(
+            r#"
+columns:
+  - column: address.country
+    rules:
+      - in_reference: { table: countries, column: code }
+dataset:
+  - column: country
+    rule: { mostly: { rule: { in_reference: { table: countries, column: code } }, ratio: 0.5 } }
+"#,
+        )
+        .unwrap()
+        .to_rule_set()
+        .unwrap();
+        rule_set.with_context(context);
+
+        let outcomes = rule_set.dataset_outcomes(&df).await.unwrap();
+        assert_eq!(outcomes[0].outcome_name, "country_in_reference_pass_rate");
+        assert!(!outcomes[0].passed);
+        let result = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .filter(col("address_country_in_reference"))
+            .unwrap();
+        assert_eq!(result.count().await.unwrap(), 1);
+    }
+}
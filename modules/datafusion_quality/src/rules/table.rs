@@ -1,7 +1,14 @@
-use crate::{TableRule, ValidationError, error::DataFusionSnafu};
-
+use crate::{
+    RuleSet, SupportedTypes, TableRule, ValidationError, error::DataFusionSnafu,
+    rules::parse_rule_sql,
+};
+
+use datafusion::common::{
+    Column,
+    tree_node::{Transformed, TreeNode},
+};
 use datafusion::functions_aggregate::{count::count_all, expr_fn::*};
-use datafusion::logical_expr::{SortExpr, Subquery};
+use datafusion::logical_expr::{SortExpr, Subquery, utils::find_aggregate_exprs};
 use datafusion::prelude::*;
 use snafu::ResultExt;
 use std::sync::Arc;
@@ -301,6 +308,20 @@ impl TableRule for CalculationRule {
     fn description(&self) -> &str {
         "Calculates a value for a column across the entire table"
     }
+
+    fn supported_types(&self) -> SupportedTypes {
+        match self.calculation_type {
+            CalculationType::Count
+            | CalculationType::CountDistinct
+            | CalculationType::Max
+            | CalculationType::Min
+            | CalculationType::FirstValue(_)
+            | CalculationType::LastValue
+            | CalculationType::NthValue(..)
+            | CalculationType::RegrCount { .. } => SupportedTypes::Any,
+            _ => SupportedTypes::Numeric,
+        }
+    }
 }
 
 /// Macro to create a zero argument calculation rule
@@ -812,6 +833,119 @@ pub fn dfq_custom_agg(aggregation: Expr, rule_name: String) -> Arc<CustomAggrega
     CustomAggregationRule::builder(aggregation, rule_name).build()
 }
 
+/// Rule that applies a custom aggregation, given as SQL text, across the entire table
+///
+/// The expression may combine aggregates, e.g. `count(*) > 1000`, and is parsed
+/// against the schema of the input DataFrame when the rule is applied.
+#[derive(Debug, Clone)]
+pub struct CustomSqlAggregationRule {
+    sql: String,
+    rule_name: String,
+}
+
+impl CustomSqlAggregationRule {
+    pub fn new(sql: &str, rule_name: &str) -> Self {
+        Self {
+            sql: sql.to_string(),
+            rule_name: rule_name.to_string(),
+        }
+    }
+}
+
+impl CustomSqlAggregationRule {
+    fn apply_sql(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        sql: &str,
+    ) -> Result<DataFrame, ValidationError> {
+        let expr = parse_rule_sql(&df, sql, &self.rule_name, column_name)?;
+
+        // Aggregate the aggregate functions, then evaluate the full expression over them
+        let aggregate_exprs = find_aggregate_exprs(std::slice::from_ref(&expr));
+        if aggregate_exprs.is_empty() {
+            return Err(ValidationError::Configuration {
+                message: format!(
+                    "Rule '{}' on column '{}' has no aggregate function in '{}'",
+                    self.rule_name, column_name, sql
+                ),
+            });
+        }
+        let aliases: Vec<String> = (0..aggregate_exprs.len())
+            .map(|i| format!("__dfq_agg_{}", i))
+            .collect();
+        let aggregation = expr
+            .transform_up(|e| {
+                Ok(match aggregate_exprs.iter().position(|agg| agg == &e) {
+                    Some(i) => Transformed::yes(Expr::Column(Column::from_name(&aliases[i]))),
+                    None => Transformed::no(e),
+                })
+            })?
+            .data;
+        let aggregate_exprs = aggregate_exprs
+            .into_iter()
+            .zip(&aliases)
+            .map(|(agg, alias)| agg.alias(alias))
+            .collect();
+
+        CustomAggregationRule::builder(aggregation, self.rule_name.clone())
+            .with_aggregate_exprs(aggregate_exprs)
+            .build()
+            .apply(df, column_name)
+    }
+}
+
+impl TableRule for CustomSqlAggregationRule {
+    fn apply(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        self.apply_sql(df, column_name, &self.sql)
+    }
+
+    fn apply_with_ruleset(
+        &self,
+        df: DataFrame,
+        column_name: &str,
+        rule_set: &RuleSet,
+    ) -> Result<DataFrame, ValidationError> {
+        let sql = rule_set.context().resolve(&self.sql)?;
+        self.apply_sql(df, column_name, &sql)
+    }
+
+    fn name(&self) -> &str {
+        &self.rule_name
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", column_name, self.rule_name)
+    }
+
+    fn description(&self) -> &str {
+        "Applies a custom aggregation, parsed from SQL text, across the entire table"
+    }
+}
+
+/// Creates a rule that applies a custom aggregation, given as SQL text, across the entire table.
+///
+/// # Arguments
+///
+/// * `sql` - The aggregate SQL expression, parsed when the rule is applied. `${name}`
+///   placeholders are resolved from the [`RuleContext`](crate::RuleContext) of the RuleSet.
+/// * `rule_name` - A name for the custom rule
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::table::dfq_custom_agg_sql;
+/// use datafusion_quality::RuleSet;
+///
+/// // Create a rule that checks the table has more than 1000 rows
+/// let rule = dfq_custom_agg_sql("count(*) > 1000", "enough_rows");
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_table_rule("id", rule, None);
+/// ```
+pub fn dfq_custom_agg_sql(sql: &str, rule_name: &str) -> Arc<CustomSqlAggregationRule> {
+    Arc::new(CustomSqlAggregationRule::new(sql, rule_name))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1632,6 +1766,54 @@ mod tests {
         assert_batches_eq!(&expected, &result.collect().await.unwrap());
     }
 
+    #[tokio::test]
+    async fn test_custom_sql_aggregation_rule() {
+        let df = create_test_df().await;
+        let rule = dfq_custom_agg_sql("max(score) - min(score)", "score_spread");
+        let result = rule.apply(df.clone(), "score").unwrap();
+
+        let expected = vec![
+            "+----+---------+-----+-------+--------------------+",
+            "| id | name    | age | score | score_score_spread |",
+            "+----+---------+-----+-------+--------------------+",
+            "| 1  | Alice   | 25  | 85.5  | 16.5               |",
+            "| 2  | Bob     | 30  | 92.0  | 16.5               |",
+            "| 3  |         | 15  | 78.5  | 16.5               |",
+            "| 4  | Charlie | 40  | 95.0  | 16.5               |",
+            "| 5  | Dave    | 25  | 88.5  | 16.5               |",
+            "+----+---------+-----+-------+--------------------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        let rule = dfq_custom_agg_sql("count(*) > 3", "enough_rows");
+        let result = rule
+            .apply(df.clone(), "id")
+            .unwrap()
+            .select_columns(&["id", "id_enough_rows"])
+            .unwrap()
+            .limit(0, Some(1))
+            .unwrap();
+
+        let expected = vec![
+            "+----+----------------+",
+            "| id | id_enough_rows |",
+            "+----+----------------+",
+            "| 1  | true           |",
+            "+----+----------------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        let rule = dfq_custom_agg_sql("count(*) >", "broken");
+        let err = rule.apply(df.clone(), "id").unwrap_err();
+        assert!(matches!(err, ValidationError::Configuration { .. }));
+
+        let rule = dfq_custom_agg_sql("score > 1", "not_aggregate");
+        let err = rule.apply(df, "score").unwrap_err();
+        assert!(matches!(err, ValidationError::Configuration { .. }));
+    }
+
     #[tokio::test]
     async fn test_custom_aggregation_rule_builder() {
         let df = create_test_df().await;
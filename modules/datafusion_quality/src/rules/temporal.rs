@@ -0,0 +1,658 @@
+use crate::{
+    ColumnRule, SupportedTypes, TableRule, ValidationError,
+    clock::{Clock, SystemClock, duration_nanos, utc_timestamp},
+    error::DataFusionSnafu,
+};
+
+use arrow::datatypes::DataType;
+use datafusion::common::ScalarValue;
+use datafusion::functions::expr_fn::{coalesce, date_bin};
+use datafusion::functions_aggregate::expr_fn::sum;
+use datafusion::functions_aggregate::first_last::last_value_udaf;
+use datafusion::functions_window::expr_fn::lead;
+use datafusion::logical_expr::{
+    ExprFunctionExt, Subquery, WindowFrame, WindowFrameBound, WindowFrameUnits,
+    expr::WindowFunction,
+};
+use datafusion::prelude::*;
+use datafusion::sql::sqlparser::ast::NullTreatment;
+use snafu::ResultExt;
+use std::{sync::Arc, time::Duration};
+
+/// Normalize a date or timestamp column to UTC nanoseconds
+fn utc(column_name: &str) -> Expr {
+    cast(col(column_name), utc_timestamp())
+}
+
+/// Parse an ISO 8601 date or timestamp as a UTC nanosecond timestamp literal
+fn parse_bound(value: &str, rule_name: &str) -> Result<Expr, ValidationError> {
+    ScalarValue::try_from_string(value.to_string(), &utc_timestamp())
+        .map(lit)
+        .map_err(|e| ValidationError::Configuration {
+            message: format!(
+                "Rule '{}' has an invalid date or timestamp '{}': {}",
+                rule_name, value, e
+            ),
+        })
+}
+
+/// Whether a bound is a date without a time of day, e.g. `2024-12-31`
+fn is_date_only(value: &str) -> bool {
+    !value.contains(['T', 't', ' '])
+}
+
+/// Rule that checks if dates or timestamps in a column fall within a range
+#[derive(Debug, Clone)]
+pub struct DateRangeRule {
+    start: String,
+    end: String,
+}
+
+impl DateRangeRule {
+    /// Creates a new DateRangeRule
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The earliest allowed date or timestamp (inclusive)
+    /// * `end` - The latest allowed date or timestamp (inclusive); a date covers the whole day
+    pub fn new(start: &str, end: &str) -> Self {
+        Self {
+            start: start.to_string(),
+            end: end.to_string(),
+        }
+    }
+}
+
+impl ColumnRule for DateRangeRule {
+    fn apply(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        let start = utc(column_name).gt_eq(parse_bound(&self.start, self.name())?);
+        let end = parse_bound(&self.end, self.name())?;
+        // A date-only end bound includes the whole day, up to the next midnight
+        let end = if is_date_only(&self.end) {
+            utc(column_name).lt(end + lit(ScalarValue::new_interval_dt(1, 0)))
+        } else {
+            utc(column_name).lt_eq(end)
+        };
+        let in_range = start.and(end);
+
+        df.with_column(&self.new_column_name(column_name), in_range)
+            .context(DataFusionSnafu)
+    }
+
+    fn name(&self) -> &str {
+        "date_between"
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", column_name, self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Checks if dates or timestamps in a column fall within a range"
+    }
+
+    fn supported_types(&self) -> SupportedTypes {
+        SupportedTypes::Temporal
+    }
+}
+
+/// Creates a rule that checks if dates or timestamps in a column fall within a range.
+///
+/// Works on Date32, Date64 and Timestamp columns of any unit and time zone; values
+/// are compared as UTC instants. Bounds are ISO 8601 strings, a bound without a
+/// time zone is read as UTC. A date-only start bound means midnight and a
+/// date-only end bound includes the whole day.
+///
+/// # Arguments
+///
+/// * `start` - The earliest allowed date or timestamp (inclusive)
+/// * `end` - The latest allowed date or timestamp (inclusive)
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::temporal::dfq_date_between;
+/// use datafusion_quality::RuleSet;
+///
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule("order_date", dfq_date_between("2024-01-01", "2024-12-31"));
+/// ruleset.with_column_rule(
+///     "created_at",
+///     dfq_date_between("2024-01-01T00:00:00Z", "2024-06-30T23:59:59Z"),
+/// );
+/// ```
+pub fn dfq_date_between(start: &str, end: &str) -> Arc<DateRangeRule> {
+    Arc::new(DateRangeRule::new(start, end))
+}
+
+/// Rule that checks dates or timestamps in a column are not after the current time
+#[derive(Debug, Clone)]
+pub struct NotInFutureRule {
+    clock: Arc<dyn Clock>,
+}
+
+impl NotInFutureRule {
+    /// Creates a new NotInFutureRule
+    ///
+    /// # Arguments
+    ///
+    /// * `clock` - The clock giving the current time
+    pub fn new(clock: Arc<dyn Clock>) -> Self {
+        Self { clock }
+    }
+
+    /// Use a different clock, e.g. a [`FixedClock`](crate::clock::FixedClock) in tests
+    pub fn with_clock(self: Arc<Self>, clock: Arc<dyn Clock>) -> Arc<Self> {
+        Arc::new(Self { clock })
+    }
+}
+
+impl ColumnRule for NotInFutureRule {
+    fn apply(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        let now = lit(ScalarValue::TimestampNanosecond(
+            Some(self.clock.now_nanos()),
+            Some("UTC".into()),
+        ));
+
+        df.with_column(
+            &self.new_column_name(column_name),
+            utc(column_name).lt_eq(now),
+        )
+        .context(DataFusionSnafu)
+    }
+
+    fn name(&self) -> &str {
+        "not_in_future"
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", column_name, self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Checks dates or timestamps in a column are not after the current time"
+    }
+
+    fn supported_types(&self) -> SupportedTypes {
+        SupportedTypes::Temporal
+    }
+}
+
+/// Creates a rule that checks dates or timestamps in a column are not after the
+/// current time, as given by the system clock.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::clock::FixedClock;
+/// use datafusion_quality::rules::temporal::dfq_not_in_future;
+/// use datafusion_quality::RuleSet;
+/// use std::sync::Arc;
+///
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule("created_at", dfq_not_in_future());
+///
+/// // With a fixed "now" for deterministic tests
+/// let rule = dfq_not_in_future().with_clock(Arc::new(FixedClock::from_unix_secs(1_700_000_000)));
+/// ```
+pub fn dfq_not_in_future() -> Arc<NotInFutureRule> {
+    Arc::new(NotInFutureRule::new(Arc::new(SystemClock)))
+}
+
+/// Rule that checks values in a column never decrease when ordered by another column
+#[derive(Debug, Clone)]
+pub struct MonotonicRule {
+    order_by: String,
+}
+
+impl MonotonicRule {
+    /// Creates a new MonotonicRule
+    ///
+    /// # Arguments
+    ///
+    /// * `order_by` - The column giving the order of the rows
+    pub fn new(order_by: &str) -> Self {
+        Self {
+            order_by: order_by.to_string(),
+        }
+    }
+}
+
+impl ColumnRule for MonotonicRule {
+    fn apply(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        // The last non-null value before the row, so a null in between does not
+        // hide a decrease
+        let previous = Expr::WindowFunction(WindowFunction::new(
+            last_value_udaf(),
+            vec![col(column_name)],
+        ))
+        .order_by(vec![col(self.order_by.as_str()).sort(true, false)])
+        .window_frame(WindowFrame::new_bounds(
+            WindowFrameUnits::Rows,
+            WindowFrameBound::Preceding(ScalarValue::UInt64(None)),
+            WindowFrameBound::Preceding(ScalarValue::UInt64(Some(1))),
+        ))
+        .null_treatment(NullTreatment::IgnoreNulls)
+        .build()
+        .context(DataFusionSnafu)?;
+        // Rows without a previous value pass
+        let increasing = previous
+            .clone()
+            .is_null()
+            .or(col(column_name).gt_eq(previous));
+
+        df.with_column(&self.new_column_name(column_name), increasing)
+            .context(DataFusionSnafu)
+    }
+
+    fn name(&self) -> &str {
+        "monotonic_increasing"
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", column_name, self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Checks values in a column never decrease when ordered by another column"
+    }
+}
+
+/// Creates a rule that checks each value in a column is greater than or equal to
+/// the last non-null value before it, ordered by `order_by`.
+///
+/// Null values stay null. Rows with equal `order_by` values are compared in an
+/// unspecified order.
+///
+/// # Arguments
+///
+/// * `order_by` - The column giving the order of the rows
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::temporal::dfq_monotonic_increasing;
+/// use datafusion_quality::RuleSet;
+///
+/// // Event times must never go backwards within the sequence
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_column_rule("event_time", dfq_monotonic_increasing("sequence_id"));
+/// ```
+pub fn dfq_monotonic_increasing(order_by: &str) -> Arc<MonotonicRule> {
+    Arc::new(MonotonicRule::new(order_by))
+}
+
+/// Find the gaps in a time series, where no value of `column_name` falls in one or
+/// more consecutive buckets of width `interval`.
+///
+/// Buckets are aligned to the Unix epoch in UTC. Returns one row per gap with the
+/// `gap_start` and `gap_end` of the first and last missing bucket (UTC timestamps)
+/// and the number of `missing_buckets`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use datafusion_quality::rules::temporal::find_time_gaps;
+/// use datafusion::prelude::*;
+/// use std::time::Duration;
+///
+/// # async fn example(df: DataFrame) -> Result<(), Box<dyn std::error::Error>> {
+/// // Hours without any events
+/// find_time_gaps(&df, "event_time", Duration::from_secs(3600))?
+///     .show()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn find_time_gaps(
+    df: &DataFrame,
+    column_name: &str,
+    interval: Duration,
+) -> Result<DataFrame, ValidationError> {
+    let stride = duration_nanos(interval);
+    if stride == 0 {
+        return Err(ValidationError::Configuration {
+            message: format!(
+                "Time gap interval for column '{}' must be positive",
+                column_name
+            ),
+        });
+    }
+
+    let bucket = date_bin(
+        lit(ScalarValue::new_interval_mdn(0, 0, stride)),
+        utc(column_name),
+        lit(ScalarValue::TimestampNanosecond(
+            Some(0),
+            Some("UTC".into()),
+        )),
+    );
+    let next = lead(col("__dfq_bucket"), Some(1), None)
+        .order_by(vec![col("__dfq_bucket").sort(true, false)])
+        .build()
+        .context(DataFusionSnafu)?;
+    let to_timestamp = |nanos: Expr| cast(nanos, utc_timestamp());
+
+    df.clone()
+        .select(vec![cast(bucket, DataType::Int64).alias("__dfq_bucket")])?
+        .filter(col("__dfq_bucket").is_not_null())?
+        .distinct()?
+        .with_column("__dfq_next", next)?
+        .filter((col("__dfq_next") - col("__dfq_bucket")).gt(lit(stride)))?
+        .select(vec![
+            to_timestamp(col("__dfq_bucket") + lit(stride)).alias("gap_start"),
+            to_timestamp(col("__dfq_next") - lit(stride)).alias("gap_end"),
+            ((col("__dfq_next") - col("__dfq_bucket")) / lit(stride) - lit(1i64))
+                .alias("missing_buckets"),
+        ])
+        .context(DataFusionSnafu)
+}
+
+/// Rule that counts the missing buckets in a time series, see [`find_time_gaps`]
+#[derive(Debug, Clone)]
+pub struct TimeGapRule {
+    interval: Duration,
+}
+
+impl TimeGapRule {
+    /// Creates a new TimeGapRule
+    ///
+    /// # Arguments
+    ///
+    /// * `interval` - The width of the buckets, e.g. one hour or one day
+    pub fn new(interval: Duration) -> Self {
+        Self { interval }
+    }
+}
+
+impl TableRule for TimeGapRule {
+    fn apply(&self, df: DataFrame, column_name: &str) -> Result<DataFrame, ValidationError> {
+        let new_column_name = self.new_column_name(column_name);
+        let subquery = find_time_gaps(&df, column_name, self.interval)?
+            .aggregate(
+                vec![],
+                vec![sum(col("missing_buckets")).alias(new_column_name.as_str())],
+            )?
+            .select(vec![
+                coalesce(vec![col(new_column_name.as_str()), lit(0i64)])
+                    .alias(new_column_name.as_str()),
+            ])?;
+
+        let subquery_expr = Expr::ScalarSubquery(Subquery {
+            subquery: Arc::new(subquery.logical_plan().clone()),
+            outer_ref_columns: vec![],
+        });
+
+        df.with_column(&new_column_name, subquery_expr)
+            .context(DataFusionSnafu)
+    }
+
+    fn name(&self) -> &str {
+        "time_gaps"
+    }
+
+    fn new_column_name(&self, column_name: &str) -> String {
+        format!("{}_{}", column_name, self.name())
+    }
+
+    fn description(&self) -> &str {
+        "Counts the missing buckets between the earliest and latest value of a time series"
+    }
+
+    fn supported_types(&self) -> SupportedTypes {
+        SupportedTypes::Temporal
+    }
+}
+
+/// Creates a rule that counts the buckets of width `interval` between the earliest
+/// and latest value of a date or timestamp column that contain no values.
+///
+/// Use [`find_time_gaps`] to list where the gaps are.
+///
+/// # Arguments
+///
+/// * `interval` - The width of the buckets, e.g. one hour or one day
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::rules::{table::*, column::*, temporal::dfq_time_gaps};
+/// use datafusion_quality::RuleSet;
+/// use datafusion::prelude::*;
+/// use std::time::Duration;
+///
+/// // Every day between the first and last order must have an order
+/// let mut ruleset = RuleSet::new();
+/// ruleset.with_table_rule(
+///     "order_date",
+///     dfq_time_gaps(Duration::from_secs(24 * 60 * 60)),
+///     Some(dfq_eq(lit(0))),
+/// );
+/// ```
+pub fn dfq_time_gaps(interval: Duration) -> Arc<TimeGapRule> {
+    Arc::new(TimeGapRule::new(interval))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RuleSet;
+    use crate::clock::FixedClock;
+    use arrow::array::{Date32Array, Int32Array, TimestampMillisecondArray};
+    use arrow::datatypes::{Field, Schema, TimeUnit};
+    use arrow::record_batch::RecordBatch;
+    use datafusion::assert_batches_eq;
+
+    const HOUR_MS: i64 = 60 * 60 * 1000;
+
+    fn create_test_df() -> DataFrame {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("day", DataType::Date32, true),
+            Field::new(
+                "event_time",
+                DataType::Timestamp(TimeUnit::Millisecond, Some("+02:00".into())),
+                true,
+            ),
+        ]);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3, 4, 5])),
+                // 2024-01-01, 2024-01-02, 2024-01-05, 2024-01-05 and null
+                Arc::new(Date32Array::from(vec![
+                    Some(19723),
+                    Some(19724),
+                    Some(19727),
+                    Some(19727),
+                    None,
+                ])),
+                // Hours 0, 1, 3 (out of order), 2 and 6 after the epoch
+                Arc::new(
+                    TimestampMillisecondArray::from(vec![
+                        Some(0),
+                        Some(HOUR_MS + 30 * 60 * 1000),
+                        Some(3 * HOUR_MS),
+                        Some(2 * HOUR_MS),
+                        Some(6 * HOUR_MS),
+                    ])
+                    .with_timezone("+02:00"),
+                ),
+            ],
+        )
+        .unwrap();
+
+        SessionContext::new().read_batch(batch).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_temporal_column_rules() {
+        let df = create_test_df();
+        // 1970-01-01T02:30:00Z
+        let clock = Arc::new(FixedClock::from_unix_secs(2 * 60 * 60 + 30 * 60));
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("day", dfq_date_between("2024-01-01", "2024-01-04"))
+            .with_column_rule(
+                "event_time",
+                dfq_date_between("1970-01-01T01:00:00+00:00", "1970-01-01T05:00:00Z"),
+            )
+            .with_column_rule("event_time", dfq_not_in_future().with_clock(clock))
+            .with_column_rule("event_time", dfq_monotonic_increasing("id"));
+
+        let result = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .select_columns(&[
+                "id",
+                "day_date_between",
+                "event_time_date_between",
+                "event_time_not_in_future",
+                "event_time_monotonic_increasing",
+            ])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+
+        let expected = vec![
+            "+----+------------------+-------------------------+--------------------------+---------------------------------+",
+            "| id | day_date_between | event_time_date_between | event_time_not_in_future | event_time_monotonic_increasing |",
+            "+----+------------------+-------------------------+--------------------------+---------------------------------+",
+            "| 1  | true             | false                   | true                     | true                            |",
+            "| 2  | true             | true                    | true                     | true                            |",
+            "| 3  | false            | true                    | false                    | true                            |",
+            "| 4  | false            | true                    | true                     | false                           |",
+            "| 5  |                  | false                   | false                    | true                            |",
+            "+----+------------------+-------------------------+--------------------------+---------------------------------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        let mut rule_set = RuleSet::new();
+        rule_set.with_column_rule("day", dfq_date_between("2024-01-01", "not a date"));
+        assert!(matches!(
+            rule_set.apply(&df).await,
+            Err(ValidationError::Configuration { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_date_only_end_bound() {
+        let df = create_test_df();
+
+        // Every event happens on 1970-01-01 in UTC, after midnight
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("day", dfq_date_between("2024-01-01", "2024-01-02"))
+            .with_column_rule("event_time", dfq_date_between("1970-01-01", "1970-01-01"));
+
+        let result = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .select_columns(&["id", "day_date_between", "event_time_date_between"])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+
+        let expected = vec![
+            "+----+------------------+-------------------------+",
+            "| id | day_date_between | event_time_date_between |",
+            "+----+------------------+-------------------------+",
+            "| 1  | true             | true                    |",
+            "| 2  | true             | true                    |",
+            "| 3  | false            | true                    |",
+            "| 4  | false            | true                    |",
+            "| 5  |                  | true                    |",
+            "+----+------------------+-------------------------+",
+        ];
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_monotonic_with_nulls() {
+        let df = SessionContext::new()
+            .sql(
+                "SELECT * FROM (VALUES (1, 5), (2, NULL), (3, 3), (4, 4), (5, NULL), (6, 9)) \
+                 AS t(id, v)",
+            )
+            .await
+            .unwrap();
+
+        let mut rule_set = RuleSet::new();
+        rule_set.with_column_rule("v", dfq_monotonic_increasing("id"));
+
+        let result = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .select_columns(&["id", "v", "v_monotonic_increasing"])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+
+        // 3 is compared to 5, the last value before the null
+        let expected = vec![
+            "+----+---+------------------------+",
+            "| id | v | v_monotonic_increasing |",
+            "+----+---+------------------------+",
+            "| 1  | 5 | true                   |",
+            "| 2  |   |                        |",
+            "| 3  | 3 | false                  |",
+            "| 4  | 4 | true                   |",
+            "| 5  |   |                        |",
+            "| 6  | 9 | true                   |",
+            "+----+---+------------------------+",
+        ];
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_time_gaps() {
+        let df = create_test_df();
+
+        let gaps = find_time_gaps(&df, "event_time", Duration::from_secs(60 * 60)).unwrap();
+        let expected = vec![
+            "+----------------------+----------------------+-----------------+",
+            "| gap_start            | gap_end              | missing_buckets |",
+            "+----------------------+----------------------+-----------------+",
+            "| 1970-01-01T04:00:00Z | 1970-01-01T05:00:00Z | 2               |",
+            "+----------------------+----------------------+-----------------+",
+        ];
+        assert_batches_eq!(&expected, &gaps.collect().await.unwrap());
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_table_rule(
+                "day",
+                dfq_time_gaps(Duration::from_secs(24 * 60 * 60)),
+                None,
+            )
+            .with_table_rule(
+                "event_time",
+                dfq_time_gaps(Duration::from_secs(60 * 60)),
+                None,
+            );
+
+        let result = rule_set
+            .apply(&df)
+            .await
+            .unwrap()
+            .select_columns(&["day_time_gaps", "event_time_time_gaps"])
+            .unwrap()
+            .limit(0, Some(1))
+            .unwrap();
+
+        let expected = vec![
+            "+---------------+----------------------+",
+            "| day_time_gaps | event_time_time_gaps |",
+            "+---------------+----------------------+",
+            "| 2             | 2                    |",
+            "+---------------+----------------------+",
+        ];
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+    }
+}
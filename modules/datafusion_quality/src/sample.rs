@@ -0,0 +1,220 @@
+use crate::{RuleRef, RuleSet, ValidationError, error::DataFusionSnafu};
+use arrow::record_batch::RecordBatch;
+use datafusion::{functions::expr_fn::random, prelude::*};
+use snafu::ResultExt;
+
+/// How failing rows are picked by [`RuleSet::failure_samples`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SampleMode {
+    /// The first N failing rows encountered
+    #[default]
+    First,
+    /// A random sample of N failing rows
+    Random,
+}
+
+/// Example failing rows for a single column rule
+#[derive(Debug, Clone)]
+pub struct FailureSample {
+    /// The column the rule was registered against
+    pub column_name: String,
+    /// The name of the rule
+    pub rule_name: String,
+    /// The name of the column holding the rule result
+    pub check_column: String,
+    /// At most N failing rows, restricted to the selected columns
+    pub rows: Vec<RecordBatch>,
+}
+
+impl FailureSample {
+    /// Number of sampled rows
+    pub fn num_rows(&self) -> usize {
+        self.rows.iter().map(RecordBatch::num_rows).sum()
+    }
+}
+
+impl RuleSet {
+    /// Collect up to `n` failing rows for every column rule.
+    ///
+    /// A row fails a rule when the rule column is false or null. Each rule is
+    /// evaluated with its own limited query, so only the sampled rows are
+    /// materialized.
+    ///
+    /// # Arguments
+    ///
+    /// * `df` - The DataFrame to validate
+    /// * `n` - The maximum number of rows returned per rule
+    /// * `columns` - Identifying columns to include; the rule's own column and
+    ///   result column are always included
+    /// * `mode` - Whether to take the first N or a random sample of N rows
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use datafusion_quality::RuleSet;
+    /// use datafusion_quality::rules::column::dfq_not_null;
+    /// use datafusion_quality::sample::SampleMode;
+    /// use datafusion::prelude::*;
+    ///
+    /// # async fn example(df: DataFrame) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut ruleset = RuleSet::new();
+    /// ruleset.with_column_rule("name", dfq_not_null());
+    ///
+    /// for sample in ruleset.failure_samples(&df, 5, &["id"], SampleMode::First).await? {
+    ///     println!("{}: {} example rows", sample.check_column, sample.num_rows());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn failure_samples(
+        &self,
+        df: &DataFrame,
+        n: usize,
+        columns: &[&str],
+        mode: SampleMode,
+    ) -> Result<Vec<FailureSample>, ValidationError> {
+        let evaluation = self.evaluate_rules(df, false).await?;
+        let dq_df = evaluation.df.clone();
+
+        let mut samples = Vec::with_capacity(self.column_rules.len());
+        for (i, (column_name, rule, _)) in self.column_rules.iter().enumerate() {
+            // Skipped rules were not evaluated, so no row failed them
+            if evaluation.is_skipped(RuleRef::Column(i)) {
+                continue;
+            }
+            let check_column = rule.new_column_name(column_name);
+
+            let mut selection: Vec<&str> = columns.to_vec();
+            for name in [column_name.as_str(), check_column.as_str()] {
+                if !selection.contains(&name) {
+                    selection.push(name);
+                }
+            }
+
+            let mut failures = dq_df
+                .clone()
+                .filter(col(check_column.as_str()).is_not_true())?;
+            if mode == SampleMode::Random {
+                failures = failures.sort(vec![random().sort(true, false)])?;
+            }
+            let rows = failures
+                .limit(0, Some(n))?
+                .select_columns(&selection)?
+                .collect()
+                .await
+                .context(DataFusionSnafu)?;
+
+            samples.push(FailureSample {
+                column_name: column_name.clone(),
+                rule_name: rule.name().to_string(),
+                check_column,
+                rows,
+            });
+        }
+
+        Ok(samples)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::column::*;
+    use arrow::array::{Float64Array, Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion::assert_batches_eq;
+    use std::sync::Arc;
+
+    fn create_test_df() -> DataFrame {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("score", DataType::Float64, true),
+        ]);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3, 4, 5])),
+                Arc::new(StringArray::from(vec![
+                    Some("Alice"),
+                    None,
+                    Some("Charlie"),
+                    None,
+                    None,
+                ])),
+                Arc::new(Float64Array::from(vec![
+                    Some(85.5),
+                    Some(12.0),
+                    Some(40.0),
+                    None,
+                    Some(99.0),
+                ])),
+            ],
+        )
+        .unwrap();
+
+        SessionContext::new().read_batch(batch).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_failure_samples_first() {
+        let df = create_test_df();
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("name", dfq_not_null())
+            .with_column_rule("score", dfq_in_range(50.0, 100.0));
+
+        let samples = rule_set
+            .failure_samples(&df, 3, &["id"], SampleMode::First)
+            .await
+            .unwrap();
+        assert_eq!(samples.len(), 2);
+
+        assert_eq!(samples[0].rule_name, "not_null");
+        let expected = vec![
+            "+----+------+---------------+",
+            "| id | name | name_not_null |",
+            "+----+------+---------------+",
+            "| 2  |      | false         |",
+            "| 4  |      | false         |",
+            "| 5  |      | false         |",
+            "+----+------+---------------+",
+        ];
+        assert_batches_eq!(&expected, &samples[0].rows);
+
+        // Null rule results count as failures
+        let expected = vec![
+            "+----+-------+----------------+",
+            "| id | score | score_in_range |",
+            "+----+-------+----------------+",
+            "| 2  | 12.0  | false          |",
+            "| 3  | 40.0  | false          |",
+            "| 4  |       |                |",
+            "+----+-------+----------------+",
+        ];
+        assert_batches_eq!(&expected, &samples[1].rows);
+    }
+
+    #[tokio::test]
+    async fn test_failure_samples_random() {
+        let df = create_test_df();
+
+        let mut rule_set = RuleSet::new();
+        rule_set.with_column_rule("name", dfq_not_null());
+
+        let samples = rule_set
+            .failure_samples(&df, 2, &["id"], SampleMode::Random)
+            .await
+            .unwrap();
+        assert_eq!(samples[0].num_rows(), 2);
+
+        let samples = rule_set
+            .failure_samples(&df, 10, &[], SampleMode::Random)
+            .await
+            .unwrap();
+        assert_eq!(samples[0].num_rows(), 3);
+        assert_eq!(samples[0].rows[0].num_columns(), 2);
+    }
+}
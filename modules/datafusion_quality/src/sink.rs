@@ -0,0 +1,345 @@
+use crate::{RuleSet, ValidationError, error::DataFusionSnafu};
+use datafusion::{
+    dataframe::DataFrameWriteOptions,
+    datasource::file_format::{arrow::ArrowFormatFactory, format_as_file_type},
+    functions::expr_fn::now,
+    logical_expr::LogicalPlanBuilder,
+    prelude::*,
+};
+use snafu::ResultExt;
+use std::{collections::HashMap, sync::Arc};
+
+/// File format used when writing partitioned outputs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    #[default]
+    Parquet,
+    Csv,
+    /// Newline delimited JSON
+    NdJson,
+    /// Arrow IPC file format
+    ArrowIpc,
+}
+
+/// Destination for the pass and fail outputs of [`RuleSet::partition_to`]
+#[derive(Debug, Clone)]
+pub struct PartitionSink {
+    /// Path the passing rows are written to
+    pub pass: String,
+    /// Path the failing rows are written to
+    pub fail: String,
+    /// Format used for both outputs
+    pub format: OutputFormat,
+    /// Drop the rule result columns (and `dfq_pass`) from the pass output
+    pub drop_pass_helper_columns: bool,
+    /// Optional run identifier written to the fail output as `dfq_run_id`
+    pub run_id: Option<String>,
+}
+
+impl PartitionSink {
+    /// Creates a new PartitionSink that drops helper columns from the pass output
+    ///
+    /// # Arguments
+    ///
+    /// * `pass` - The path the passing rows are written to
+    /// * `fail` - The path the failing rows are written to
+    /// * `format` - The format used for both outputs
+    pub fn new(pass: impl AsRef<str>, fail: impl AsRef<str>, format: OutputFormat) -> Self {
+        Self {
+            pass: pass.as_ref().to_string(),
+            fail: fail.as_ref().to_string(),
+            format,
+            drop_pass_helper_columns: true,
+            run_id: None,
+        }
+    }
+
+    pub fn with_drop_pass_helper_columns(mut self, drop_pass_helper_columns: bool) -> Self {
+        self.drop_pass_helper_columns = drop_pass_helper_columns;
+        self
+    }
+
+    pub fn with_run_id(mut self, run_id: impl AsRef<str>) -> Self {
+        self.run_id = Some(run_id.as_ref().to_string());
+        self
+    }
+}
+
+/// Number of rows written by [`RuleSet::partition_to`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PartitionCounts {
+    pub pass_rows: u64,
+    pub fail_rows: u64,
+}
+
+impl RuleSet {
+    /// Partition a DataFrame and write the pass and fail outputs to the given sink.
+    ///
+    /// The fail output always keeps the rule result columns and `dfq_pass`, and
+    /// additionally gets a `dfq_run_timestamp` column (and `dfq_run_id` if set).
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use datafusion_quality::RuleSet;
+    /// use datafusion_quality::rules::column::dfq_not_null;
+    /// use datafusion_quality::sink::{OutputFormat, PartitionSink};
+    /// use datafusion::prelude::*;
+    ///
+    /// # async fn example(df: DataFrame) -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut ruleset = RuleSet::new();
+    /// ruleset.with_column_rule("name", dfq_not_null());
+    ///
+    /// let sink = PartitionSink::new("out/pass", "out/fail", OutputFormat::Parquet)
+    ///     .with_run_id("2024-01-01");
+    /// let counts = ruleset.partition_to(&df, sink).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn partition_to(
+        &self,
+        df: &DataFrame,
+        sink: PartitionSink,
+    ) -> Result<PartitionCounts, ValidationError> {
+        let dq_df = self.apply(df).await?.cache().await?;
+        sink.write(df, dq_df).await
+    }
+}
+
+impl PartitionSink {
+    /// Write the pass and fail outputs of `dq_df`, the result of applying a RuleSet
+    /// to `df`, e.g. the cached [`Evaluation::df`](crate::evaluation::Evaluation::df) of a run
+    /// that is also reported
+    pub async fn write(
+        &self,
+        df: &DataFrame,
+        dq_df: DataFrame,
+    ) -> Result<PartitionCounts, ValidationError> {
+        let pass_expr = col("dfq_pass").eq(lit(true));
+        let mut pass_df = dq_df.clone().filter(pass_expr.clone())?;
+        if self.drop_pass_helper_columns {
+            pass_df = pass_df.select_columns(
+                &df.schema()
+                    .fields()
+                    .iter()
+                    .map(|s| s.name().as_str())
+                    .collect::<Vec<&str>>(),
+            )?;
+        }
+
+        let mut fail_df = dq_df
+            .filter(pass_expr.not())?
+            .with_column("dfq_run_timestamp", now())?;
+        if let Some(run_id) = &self.run_id {
+            fail_df = fail_df.with_column("dfq_run_id", lit(run_id.as_str()))?;
+        }
+
+        let pass_rows = write_output(pass_df, &self.pass, self.format).await?;
+        let fail_rows = write_output(fail_df, &self.fail, self.format).await?;
+
+        Ok(PartitionCounts {
+            pass_rows,
+            fail_rows,
+        })
+    }
+}
+
+/// Write a DataFrame to `path`, returning the number of rows written
+async fn write_output(
+    df: DataFrame,
+    path: &str,
+    format: OutputFormat,
+) -> Result<u64, ValidationError> {
+    let options = DataFrameWriteOptions::new();
+    let batches = match format {
+        OutputFormat::Parquet => df.write_parquet(path, options, None).await,
+        OutputFormat::Csv => df.write_csv(path, options, None).await,
+        OutputFormat::NdJson => df.write_json(path, options, None).await,
+        OutputFormat::ArrowIpc => {
+            // DataFrame has no write_arrow, so plan the COPY TO ourselves
+            let (state, plan) = df.into_parts();
+            let file_type = format_as_file_type(Arc::new(ArrowFormatFactory::new()));
+            let plan = LogicalPlanBuilder::copy_to(
+                plan,
+                path.to_string(),
+                file_type,
+                HashMap::new(),
+                vec![],
+            )?
+            .build()?;
+            DataFrame::new(state, plan).collect().await
+        }
+    }
+    .context(DataFusionSnafu)?;
+
+    let count = batches
+        .first()
+        .and_then(|batch| {
+            batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<arrow::array::UInt64Array>()
+        })
+        .map(|counts| counts.value(0))
+        .unwrap_or_default();
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::column::*;
+    use arrow::array::{Float64Array, Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion::assert_batches_sorted_eq;
+
+    fn create_test_df(ctx: &SessionContext) -> DataFrame {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("score", DataType::Float64, true),
+        ]);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![
+                    Some("Alice"),
+                    None,
+                    Some("Charlie"),
+                ])),
+                Arc::new(Float64Array::from(vec![Some(85.5), Some(92.0), Some(40.0)])),
+            ],
+        )
+        .unwrap();
+
+        ctx.read_batch(batch).unwrap()
+    }
+
+    fn output_dir(name: &str) -> String {
+        let dir = std::env::temp_dir().join(format!("dfq_sink_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        format!("{}/", dir.display())
+    }
+
+    #[tokio::test]
+    async fn test_partition_to_parquet() {
+        let ctx = SessionContext::new();
+        let df = create_test_df(&ctx);
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("name", dfq_not_null())
+            .with_column_rule("score", dfq_in_range(50.0, 100.0));
+
+        let dir = output_dir("parquet");
+        let sink = PartitionSink::new(
+            format!("{dir}pass/"),
+            format!("{dir}fail/"),
+            OutputFormat::Parquet,
+        )
+        .with_run_id("run-1");
+        let counts = rule_set.partition_to(&df, sink).await.unwrap();
+        assert_eq!(
+            counts,
+            PartitionCounts {
+                pass_rows: 1,
+                fail_rows: 2
+            }
+        );
+
+        let pass = ctx
+            .read_parquet(format!("{dir}pass/"), ParquetReadOptions::default())
+            .await
+            .unwrap();
+        let expected = vec![
+            "+----+-------+-------+",
+            "| id | name  | score |",
+            "+----+-------+-------+",
+            "| 1  | Alice | 85.5  |",
+            "+----+-------+-------+",
+        ];
+        assert_batches_sorted_eq!(&expected, &pass.collect().await.unwrap());
+
+        let fail = ctx
+            .read_parquet(format!("{dir}fail/"), ParquetReadOptions::default())
+            .await
+            .unwrap()
+            .drop_columns(&["dfq_run_timestamp"])
+            .unwrap();
+        let expected = vec![
+            "+----+---------+-------+---------------+----------------+----------+------------+",
+            "| id | name    | score | name_not_null | score_in_range | dfq_pass | dfq_run_id |",
+            "+----+---------+-------+---------------+----------------+----------+------------+",
+            "| 2  |         | 92.0  | false         | true           | false    | run-1      |",
+            "| 3  | Charlie | 40.0  | true          | false          | false    | run-1      |",
+            "+----+---------+-------+---------------+----------------+----------+------------+",
+        ];
+        assert_batches_sorted_eq!(&expected, &fail.collect().await.unwrap());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_partition_to_keeps_pass_helper_columns() {
+        let ctx = SessionContext::new();
+        let df = create_test_df(&ctx);
+
+        let mut rule_set = RuleSet::new();
+        rule_set.with_column_rule("name", dfq_not_null());
+
+        let dir = output_dir("csv");
+        let sink = PartitionSink::new(
+            format!("{dir}pass/"),
+            format!("{dir}fail/"),
+            OutputFormat::Csv,
+        )
+        .with_drop_pass_helper_columns(false);
+        rule_set.partition_to(&df, sink).await.unwrap();
+
+        let pass = ctx
+            .read_csv(format!("{dir}pass/"), CsvReadOptions::default())
+            .await
+            .unwrap();
+        let expected = vec![
+            "+----+---------+-------+---------------+----------+",
+            "| id | name    | score | name_not_null | dfq_pass |",
+            "+----+---------+-------+---------------+----------+",
+            "| 1  | Alice   | 85.5  | true          | true     |",
+            "| 3  | Charlie | 40.0  | true          | true     |",
+            "+----+---------+-------+---------------+----------+",
+        ];
+        assert_batches_sorted_eq!(&expected, &pass.collect().await.unwrap());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_partition_to_json_and_arrow() {
+        let ctx = SessionContext::new();
+        let df = create_test_df(&ctx);
+
+        let mut rule_set = RuleSet::new();
+        rule_set.with_column_rule("name", dfq_not_null());
+
+        for (name, format) in [
+            ("json", OutputFormat::NdJson),
+            ("arrow", OutputFormat::ArrowIpc),
+        ] {
+            let dir = output_dir(name);
+            let sink = PartitionSink::new(format!("{dir}pass/"), format!("{dir}fail/"), format);
+            let counts = rule_set.partition_to(&df, sink).await.unwrap();
+            assert_eq!(
+                counts,
+                PartitionCounts {
+                    pass_rows: 2,
+                    fail_rows: 1
+                }
+            );
+            let _ = std::fs::remove_dir_all(dir);
+        }
+    }
+}
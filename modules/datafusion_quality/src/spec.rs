@@ -0,0 +1,1004 @@
+use crate::{
+    ColumnRule, DatasetRule, RuleSet, SchemaRule, TableRule, ValidationError,
+    metrics::{JsonFileMetricsStore, dfq_metric_change_within},
+    registry::{BuiltinSpec, RegisteredRule, RuleRegistry},
+    rules::{
+        column::*,
+        combinator::*,
+        conditional::ConditionalRule,
+        dataset::*,
+        distribution::{Binning, DistributionRule, DistributionStatistic},
+        nested::*,
+        reference::*,
+        schema::*,
+        table::*,
+        temporal::*,
+    },
+    template::{RuleTemplate, TemplateInstance},
+};
+use arrow::datatypes::DataType;
+use datafusion::{common::ScalarValue, prelude::*};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::{collections::BTreeMap, ops::Bound, path::Path, str::FromStr, sync::Arc, time::Duration};
+use strum::VariantNames;
+
+/// Implements `Serialize` and `Deserialize` for a spec enum whose derived
+/// (`remote = "Self"`) implementations only know the built-in rules, falling back
+/// to a [`RegisteredRule`] for any other rule
+macro_rules! builtin_or_registered {
+    ($spec:ty) => {
+        impl Serialize for $spec {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match self {
+                    Self::Registered(rule) => rule.serialize(serializer),
+                    spec => <$spec>::serialize(spec, serializer),
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $spec {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = Value::deserialize(deserializer)?;
+                match <$spec>::deserialize(&value) {
+                    Ok(spec) => Ok(spec),
+                    Err(_) => RegisteredRule::deserialize(value)
+                        .map(Self::Registered)
+                        .map_err(serde::de::Error::custom),
+                }
+            }
+        }
+
+        impl BuiltinSpec for $spec {
+            fn deserialize_builtin(value: &Value) -> Result<Self, serde_json::Error> {
+                <$spec>::deserialize(value)
+            }
+        }
+    };
+}
+
+builtin_or_registered!(SchemaRuleSpec);
+builtin_or_registered!(ColumnRuleSpec);
+builtin_or_registered!(TableRuleKind);
+builtin_or_registered!(DatasetRuleKind);
+
+/// A declarative description of a RuleSet, loaded from YAML or JSON
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::spec::RuleSetSpec;
+///
+/// let spec = RuleSetSpec::from_yaml(r#"
+/// schema:
+///   - column_exists: id
+///   - column_type: { column: id, data_type: Int32 }
+/// columns:
+///   - column: name
+///     rules:
+///       - not_null
+///       - str_length: { min: 1, max: 64 }
+///   - column: score
+///     rules:
+///       - in_range: { min: 0, max: 100 }
+/// table:
+///   - column: id
+///     rule: count
+///     check: { gte: 1 }
+/// dataset:
+///   - column: table
+///     rule: { row_count_between: { min: 1, max: 1000000 } }
+/// "#).unwrap();
+///
+/// let ruleset = spec.to_rule_set().unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleSetSpec {
+    #[serde(default)]
+    pub schema: Vec<SchemaRuleSpec>,
+    #[serde(default)]
+    pub columns: Vec<ColumnSpec>,
+    #[serde(default)]
+    pub table: Vec<TableRuleSpec>,
+    #[serde(default)]
+    pub dataset: Vec<DatasetRuleSpec>,
+    /// Named templates, see [`RuleTemplate`]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub templates: BTreeMap<String, RuleTemplate>,
+    /// Templates instantiated into this spec, after its own rules
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub instances: Vec<TemplateInstance>,
+}
+
+/// A schema rule in a [`RuleSetSpec`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, VariantNames)]
+#[serde(remote = "Self", rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SchemaRuleSpec {
+    ColumnExists(String),
+    /// `data_type` is parsed with Arrow's `DataType` display syntax, e.g. `Int32` or `Utf8`
+    ColumnType {
+        column: String,
+        data_type: String,
+    },
+    ColumnNullable(String),
+    ColumnNotNullable(String),
+    /// A rule registered in a [`RuleRegistry`]
+    #[serde(skip)]
+    Registered(RegisteredRule),
+}
+
+impl SchemaRuleSpec {
+    /// Build the schema rule described by this spec, resolving its name in `registry`
+    pub fn to_rule(&self, registry: &RuleRegistry) -> Result<Arc<dyn SchemaRule>, ValidationError> {
+        let rule = RegisteredRule::from_spec(self)?;
+        registry.schema_rule(&rule.name, &rule.params)
+    }
+
+    pub(crate) fn builtin_rule(
+        &self,
+        _registry: &RuleRegistry,
+    ) -> Result<Arc<dyn SchemaRule>, ValidationError> {
+        Ok(match self {
+            SchemaRuleSpec::ColumnExists(column) => dfq_column_exists(column),
+            SchemaRuleSpec::ColumnType { column, data_type } => {
+                let data_type =
+                    DataType::from_str(data_type).map_err(|e| ValidationError::Configuration {
+                        message: format!(
+                            "Invalid data type '{}' for column '{}': {}",
+                            data_type, column, e
+                        ),
+                    })?;
+                dfq_column_type(column, data_type)
+            }
+            SchemaRuleSpec::ColumnNullable(column) => dfq_column_nullable(column),
+            SchemaRuleSpec::ColumnNotNullable(column) => dfq_column_not_nullable(column),
+            SchemaRuleSpec::Registered(rule) => return Err(rule.invalid_params::<Self>()),
+        })
+    }
+}
+
+/// The column rules registered against a single column
+///
+/// `column` may be a nested path such as `payload.user.id` or `items[*].price`,
+/// see [`dfq_nested`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ColumnSpec {
+    pub column: String,
+    pub rules: Vec<ColumnRuleSpec>,
+    /// Tags for every rule of the column, see [`RuleSet::with_tags`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Ids of the rules every rule of the column depends on, see [`RuleSet::with_depends_on`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+}
+
+/// A literal value in a [`RuleSetSpec`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SpecValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+impl SpecValue {
+    fn to_scalar(&self) -> ScalarValue {
+        match self {
+            SpecValue::Bool(v) => ScalarValue::from(*v),
+            SpecValue::Int(v) => ScalarValue::from(*v),
+            SpecValue::Float(v) => ScalarValue::from(*v),
+            SpecValue::String(v) => ScalarValue::from(v.as_str()),
+        }
+    }
+
+    fn to_expr(&self) -> Expr {
+        lit(self.to_scalar())
+    }
+}
+
+/// A column rule in a [`RuleSetSpec`], named after its `dfq_*` function
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, VariantNames)]
+#[serde(remote = "Self", rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ColumnRuleSpec {
+    NotNull,
+    Null,
+    InRange {
+        min: f64,
+        max: f64,
+    },
+    NotInRange {
+        min: f64,
+        max: f64,
+    },
+    /// A range with typed, optionally exclusive bounds, see [`dfq_range`]
+    Range {
+        #[serde(default)]
+        min: Option<SpecValue>,
+        #[serde(default)]
+        max: Option<SpecValue>,
+        #[serde(default)]
+        min_exclusive: bool,
+        #[serde(default)]
+        max_exclusive: bool,
+    },
+    Like(String),
+    NotLike(String),
+    Ilike(String),
+    NotIlike(String),
+    Lt(SpecValue),
+    Lte(SpecValue),
+    Gt(SpecValue),
+    Gte(SpecValue),
+    Eq(SpecValue),
+    NotEq(SpecValue),
+    NotLt(SpecValue),
+    NotLte(SpecValue),
+    NotGt(SpecValue),
+    NotGte(SpecValue),
+    StrLength {
+        #[serde(default)]
+        min: Option<u32>,
+        #[serde(default)]
+        max: Option<u32>,
+    },
+    StrMinLength(u32),
+    StrMaxLength(u32),
+    StrEmpty,
+    StrNotEmpty,
+    IsEmail,
+    IsUuid,
+    IsIpv4,
+    IsJson,
+    /// A JSON Schema the JSON values must conform to, see [`dfq_json_schema`]
+    JsonSchema(serde_json::Value),
+    DateBetween {
+        start: String,
+        end: String,
+    },
+    NotInFuture,
+    MonotonicIncreasing {
+        order_by: String,
+    },
+    ListLength {
+        #[serde(default)]
+        min: Option<u32>,
+        #[serde(default)]
+        max: Option<u32>,
+    },
+    ListAllNotNull,
+    ListAllInRange {
+        min: f64,
+        max: f64,
+    },
+    ListDistinct,
+    /// Values that exist in a column of a reference table, see [`dfq_in_reference`]
+    InReference {
+        table: String,
+        column: String,
+    },
+    /// A SQL expression, see [`dfq_custom_sql`]
+    Sql {
+        name: String,
+        expr: String,
+    },
+    /// See [`dfq_not`]
+    Not(Box<ColumnRuleSpec>),
+    /// See [`dfq_all`]
+    All(Vec<ColumnRuleSpec>),
+    /// See [`dfq_any`]
+    Any(Vec<ColumnRuleSpec>),
+    /// See [`dfq_at_least`]
+    AtLeast {
+        k: usize,
+        rules: Vec<ColumnRuleSpec>,
+    },
+    /// A rule applied where a SQL `predicate` holds, see
+    /// [`dfq_when`](crate::rules::conditional::dfq_when)
+    When {
+        predicate: String,
+        rule: Box<ColumnRuleSpec>,
+    },
+    /// See [`dfq_nested`]
+    Nested(Box<ColumnRuleSpec>),
+    /// See [`dfq_list_all`]
+    ListAll(Box<ColumnRuleSpec>),
+    /// The change of a table metric against the runs recorded in the JSON
+    /// lines file `store`, see [`dfq_metric_change_within`]
+    MetricChangeWithin {
+        store: String,
+        dataset: String,
+        max_change_pct: f64,
+        lookback: usize,
+    },
+    /// A rule registered in a [`RuleRegistry`]
+    #[serde(skip)]
+    Registered(RegisteredRule),
+}
+
+impl ColumnRuleSpec {
+    /// Build the column rule described by this spec, resolving its name in `registry`
+    pub fn to_rule(&self, registry: &RuleRegistry) -> Result<Arc<dyn ColumnRule>, ValidationError> {
+        let rule = RegisteredRule::from_spec(self)?;
+        registry.column_rule(&rule.name, &rule.params)
+    }
+
+    pub(crate) fn builtin_rule(
+        &self,
+        registry: &RuleRegistry,
+    ) -> Result<Arc<dyn ColumnRule>, ValidationError> {
+        let rules = |specs: &[ColumnRuleSpec]| {
+            specs
+                .iter()
+                .map(|spec| spec.to_rule(registry))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        Ok(match self {
+            ColumnRuleSpec::NotNull => dfq_not_null(),
+            ColumnRuleSpec::Null => dfq_null(),
+            ColumnRuleSpec::InRange { min, max } => dfq_in_range(*min, *max),
+            ColumnRuleSpec::NotInRange { min, max } => dfq_not_in_range(*min, *max),
+            ColumnRuleSpec::Range {
+                min,
+                max,
+                min_exclusive,
+                max_exclusive,
+            } => {
+                let bound = |value: &Option<SpecValue>, exclusive: bool| match value {
+                    Some(value) if exclusive => Bound::Excluded(value.to_scalar()),
+                    Some(value) => Bound::Included(value.to_scalar()),
+                    None => Bound::Unbounded,
+                };
+                dfq_range(bound(min, *min_exclusive), bound(max, *max_exclusive))
+            }
+            ColumnRuleSpec::Like(pattern) => dfq_like(pattern),
+            ColumnRuleSpec::NotLike(pattern) => dfq_not_like(pattern),
+            ColumnRuleSpec::Ilike(pattern) => dfq_ilike(pattern),
+            ColumnRuleSpec::NotIlike(pattern) => dfq_not_ilike(pattern),
+            ColumnRuleSpec::Lt(value) => dfq_lt(value.to_expr()),
+            ColumnRuleSpec::Lte(value) => dfq_lte(value.to_expr()),
+            ColumnRuleSpec::Gt(value) => dfq_gt(value.to_expr()),
+            ColumnRuleSpec::Gte(value) => dfq_gte(value.to_expr()),
+            ColumnRuleSpec::Eq(value) => dfq_eq(value.to_expr()),
+            ColumnRuleSpec::NotEq(value) => dfq_not_eq(value.to_expr()),
+            ColumnRuleSpec::NotLt(value) => dfq_not_lt(value.to_expr()),
+            ColumnRuleSpec::NotLte(value) => dfq_not_lte(value.to_expr()),
+            ColumnRuleSpec::NotGt(value) => dfq_not_gt(value.to_expr()),
+            ColumnRuleSpec::NotGte(value) => dfq_not_gte(value.to_expr()),
+            ColumnRuleSpec::StrLength { min, max } => dfq_str_length(*min, *max),
+            ColumnRuleSpec::StrMinLength(min) => dfq_str_min_length(*min),
+            ColumnRuleSpec::StrMaxLength(max) => dfq_str_max_length(*max),
+            ColumnRuleSpec::StrEmpty => dfq_str_empty(),
+            ColumnRuleSpec::StrNotEmpty => dfq_str_not_empty(),
+            ColumnRuleSpec::IsEmail => dfq_is_email(),
+            ColumnRuleSpec::IsUuid => dfq_is_uuid(),
+            ColumnRuleSpec::IsIpv4 => dfq_is_ipv4(),
+            ColumnRuleSpec::IsJson => dfq_is_json(),
+            ColumnRuleSpec::JsonSchema(schema) => dfq_json_schema(schema.clone()),
+            ColumnRuleSpec::DateBetween { start, end } => dfq_date_between(start, end),
+            ColumnRuleSpec::NotInFuture => dfq_not_in_future(),
+            ColumnRuleSpec::MonotonicIncreasing { order_by } => dfq_monotonic_increasing(order_by),
+            ColumnRuleSpec::ListLength { min, max } => dfq_list_length(*min, *max),
+            ColumnRuleSpec::ListAllNotNull => dfq_list_all_not_null(),
+            ColumnRuleSpec::ListAllInRange { min, max } => dfq_list_all_in_range(*min, *max),
+            ColumnRuleSpec::ListDistinct => dfq_list_distinct(),
+            ColumnRuleSpec::InReference { table, column } => dfq_in_reference(table, column),
+            ColumnRuleSpec::Sql { name, expr } => dfq_custom_sql(name, expr),
+            ColumnRuleSpec::Not(rule) => dfq_not(rule.to_rule(registry)?),
+            ColumnRuleSpec::All(specs) => dfq_all(rules(specs)?),
+            ColumnRuleSpec::Any(specs) => dfq_any(rules(specs)?),
+            ColumnRuleSpec::AtLeast { k, rules: specs } => dfq_at_least(*k, rules(specs)?),
+            ColumnRuleSpec::When { predicate, rule } => Arc::new(
+                ConditionalRule::with_sql_predicate(predicate, rule.to_rule(registry)?),
+            ),
+            ColumnRuleSpec::Nested(rule) => dfq_nested(rule.to_rule(registry)?),
+            ColumnRuleSpec::ListAll(rule) => dfq_list_all(rule.to_rule(registry)?),
+            ColumnRuleSpec::MetricChangeWithin {
+                store,
+                dataset,
+                max_change_pct,
+                lookback,
+            } => dfq_metric_change_within(
+                Arc::new(JsonFileMetricsStore::new(store)),
+                dataset,
+                *max_change_pct,
+                *lookback,
+            ),
+            ColumnRuleSpec::Registered(rule) => return Err(rule.invalid_params::<Self>()),
+        })
+    }
+}
+
+/// A table rule in a [`RuleSetSpec`], with an optional check on its result
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TableRuleSpec {
+    pub column: String,
+    pub rule: TableRuleKind,
+    #[serde(default)]
+    pub check: Option<ColumnRuleSpec>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+}
+
+/// The aggregation computed by a [`TableRuleSpec`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, VariantNames)]
+#[serde(remote = "Self", rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TableRuleKind {
+    Count,
+    CountDistinct,
+    Avg,
+    StdDev,
+    Max,
+    Min,
+    Sum,
+    Median,
+    NullCount,
+    NotNullCount,
+    /// Missing buckets of `interval_seconds` in a time series, see [`dfq_time_gaps`]
+    TimeGaps {
+        interval_seconds: u64,
+    },
+    /// An aggregate SQL expression, see [`dfq_custom_agg_sql`]
+    Sql {
+        name: String,
+        expr: String,
+    },
+    StddevPop,
+    VarPop,
+    VarSamp,
+    /// Two-column aggregates like [`dfq_covar_pop`]; set one of `x` or `y`,
+    /// the rule's column takes the other place
+    CovarPop(XySpec),
+    CovarSamp(XySpec),
+    RegrAvgx(XySpec),
+    RegrAvgy(XySpec),
+    RegrCount(XySpec),
+    RegrIntercept(XySpec),
+    RegrR2(XySpec),
+    RegrSlope(XySpec),
+    RegrSxx(XySpec),
+    RegrSxy(XySpec),
+    RegrSyy(XySpec),
+    /// The first value, optionally ordered by columns, see [`dfq_first_value`]
+    FirstValue {
+        #[serde(default)]
+        order_by: Vec<String>,
+    },
+    LastValue,
+    /// The `n`th value, optionally ordered by columns, see [`dfq_nth_value`]
+    NthValue {
+        n: i64,
+        #[serde(default)]
+        order_by: Vec<String>,
+    },
+    /// Distribution statistics against a `reference` table of the
+    /// [`RuleContext`](crate::RuleContext), see
+    /// [`dfq_ks_statistic`](crate::rules::distribution::dfq_ks_statistic)
+    KsStatistic {
+        reference: String,
+    },
+    /// `bins` equal-width bins, or one bin per distinct value if not set,
+    /// see [`Binning`]
+    Psi {
+        reference: String,
+        #[serde(default)]
+        bins: Option<usize>,
+    },
+    ChiSquared {
+        reference: String,
+        #[serde(default)]
+        bins: Option<usize>,
+    },
+    JsDivergence {
+        reference: String,
+        #[serde(default)]
+        bins: Option<usize>,
+    },
+    /// A rule aggregated over the rows where a SQL `predicate` holds, see
+    /// [`dfq_when`](crate::rules::conditional::dfq_when)
+    When {
+        predicate: String,
+        rule: Box<TableRuleKind>,
+    },
+    /// A rule registered in a [`RuleRegistry`]
+    #[serde(skip)]
+    Registered(RegisteredRule),
+}
+
+/// The columns of a two-column aggregate in a [`TableRuleKind`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct XySpec {
+    #[serde(default)]
+    pub x: Option<String>,
+    #[serde(default)]
+    pub y: Option<String>,
+}
+
+impl XySpec {
+    fn x(&self) -> Option<Expr> {
+        self.x.as_deref().map(ident)
+    }
+
+    fn y(&self) -> Option<Expr> {
+        self.y.as_deref().map(ident)
+    }
+}
+
+impl TableRuleKind {
+    /// Build the table rule described by this spec, resolving its name in `registry`
+    pub fn to_rule(&self, registry: &RuleRegistry) -> Result<Arc<dyn TableRule>, ValidationError> {
+        let rule = RegisteredRule::from_spec(self)?;
+        registry.table_rule(&rule.name, &rule.params)
+    }
+
+    pub(crate) fn builtin_rule(
+        &self,
+        registry: &RuleRegistry,
+    ) -> Result<Arc<dyn TableRule>, ValidationError> {
+        let order_by = |columns: &[String]| {
+            (!columns.is_empty()).then(|| {
+                columns
+                    .iter()
+                    .map(|column| ident(column).sort(true, false))
+                    .collect()
+            })
+        };
+        let distribution = |reference: &str, statistic, bins: &Option<usize>| {
+            let binning = bins.map_or(Binning::Categorical, Binning::EqualWidth);
+            Arc::new(DistributionRule::with_reference_table(
+                reference, statistic, binning,
+            ))
+        };
+        Ok(match self {
+            TableRuleKind::Count => dfq_count(),
+            TableRuleKind::CountDistinct => dfq_count_distinct(),
+            TableRuleKind::Avg => dfq_avg(),
+            TableRuleKind::StdDev => dfq_stddev(),
+            TableRuleKind::Max => dfq_max(),
+            TableRuleKind::Min => dfq_min(),
+            TableRuleKind::Sum => dfq_sum(),
+            TableRuleKind::Median => dfq_median(),
+            TableRuleKind::NullCount => dfq_null_count(),
+            TableRuleKind::NotNullCount => dfq_not_null_count(),
+            TableRuleKind::TimeGaps { interval_seconds } => {
+                dfq_time_gaps(Duration::from_secs(*interval_seconds))
+            }
+            TableRuleKind::Sql { name, expr } => dfq_custom_agg_sql(expr, name),
+            TableRuleKind::StddevPop => dfq_stddev_pop(),
+            TableRuleKind::VarPop => dfq_var_pop(),
+            TableRuleKind::VarSamp => dfq_var_samp(),
+            TableRuleKind::CovarPop(xy) => dfq_covar_pop(xy.x(), xy.y()),
+            TableRuleKind::CovarSamp(xy) => dfq_covar_samp(xy.x(), xy.y()),
+            TableRuleKind::RegrAvgx(xy) => dfq_regr_avgx(xy.x(), xy.y()),
+            TableRuleKind::RegrAvgy(xy) => dfq_regr_avgy(xy.x(), xy.y()),
+            TableRuleKind::RegrCount(xy) => dfq_regr_count(xy.x(), xy.y()),
+            TableRuleKind::RegrIntercept(xy) => dfq_regr_intercept(xy.x(), xy.y()),
+            TableRuleKind::RegrR2(xy) => dfq_regr_r2(xy.x(), xy.y()),
+            TableRuleKind::RegrSlope(xy) => dfq_regr_slope(xy.x(), xy.y()),
+            TableRuleKind::RegrSxx(xy) => dfq_regr_sxx(xy.x(), xy.y()),
+            TableRuleKind::RegrSxy(xy) => dfq_regr_sxy(xy.x(), xy.y()),
+            TableRuleKind::RegrSyy(xy) => dfq_regr_syy(xy.x(), xy.y()),
+            TableRuleKind::FirstValue { order_by: columns } => dfq_first_value(order_by(columns)),
+            TableRuleKind::LastValue => dfq_last_value(),
+            TableRuleKind::NthValue {
+                n,
+                order_by: columns,
+            } => dfq_nth_value(*n, order_by(columns)),
+            TableRuleKind::KsStatistic { reference } => {
+                distribution(reference, DistributionStatistic::KolmogorovSmirnov, &None)
+            }
+            TableRuleKind::Psi { reference, bins } => {
+                distribution(reference, DistributionStatistic::PopulationStability, bins)
+            }
+            TableRuleKind::ChiSquared { reference, bins } => {
+                distribution(reference, DistributionStatistic::ChiSquared, bins)
+            }
+            TableRuleKind::JsDivergence { reference, bins } => {
+                distribution(reference, DistributionStatistic::JensenShannon, bins)
+            }
+            TableRuleKind::When { predicate, rule } => Arc::new(
+                ConditionalRule::with_sql_predicate(predicate, rule.to_rule(registry)?),
+            ),
+            TableRuleKind::Registered(rule) => return Err(rule.invalid_params::<Self>()),
+        })
+    }
+}
+
+/// A dataset rule in a [`RuleSetSpec`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DatasetRuleSpec {
+    pub column: String,
+    pub rule: DatasetRuleKind,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+}
+
+/// The check performed by a [`DatasetRuleSpec`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, VariantNames)]
+#[serde(remote = "Self", rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DatasetRuleKind {
+    RowCountBetween {
+        min: u64,
+        max: u64,
+    },
+    Freshness {
+        max_age_seconds: u64,
+    },
+    /// At least a `ratio` share of rows pass a column rule, see [`dfq_mostly`]
+    Mostly {
+        rule: ColumnRuleSpec,
+        ratio: f64,
+    },
+    /// A rule registered in a [`RuleRegistry`]
+    #[serde(skip)]
+    Registered(RegisteredRule),
+}
+
+impl DatasetRuleKind {
+    /// Build the dataset rule described by this spec, resolving its name in `registry`
+    pub fn to_rule(
+        &self,
+        registry: &RuleRegistry,
+    ) -> Result<Arc<dyn DatasetRule>, ValidationError> {
+        let rule = RegisteredRule::from_spec(self)?;
+        registry.dataset_rule(&rule.name, &rule.params)
+    }
+
+    pub(crate) fn builtin_rule(
+        &self,
+        registry: &RuleRegistry,
+    ) -> Result<Arc<dyn DatasetRule>, ValidationError> {
+        Ok(match self {
+            DatasetRuleKind::RowCountBetween { min, max } => dfq_row_count_between(*min, *max),
+            DatasetRuleKind::Freshness { max_age_seconds } => {
+                dfq_freshness(Duration::from_secs(*max_age_seconds))
+            }
+            DatasetRuleKind::Mostly { rule, ratio } => dfq_mostly(rule.to_rule(registry)?, *ratio),
+            DatasetRuleKind::Registered(rule) => return Err(rule.invalid_params::<Self>()),
+        })
+    }
+}
+
+impl RuleSetSpec {
+    /// Parse a spec from YAML
+    pub fn from_yaml(yaml: &str) -> Result<Self, ValidationError> {
+        // serde_yaml only accepts `!tag` enums, so go through a JSON value to share
+        // the `{ rule: args }` representation with JSON specs
+        let value: serde_json::Value =
+            serde_yaml::from_str(yaml).map_err(|e| ValidationError::Configuration {
+                message: format!("Invalid YAML rule spec: {}", e),
+            })?;
+        serde_json::from_value(value).map_err(|e| ValidationError::Configuration {
+            message: format!("Invalid YAML rule spec: {}", e),
+        })
+    }
+
+    /// Parse a spec from JSON
+    pub fn from_json(json: &str) -> Result<Self, ValidationError> {
+        serde_json::from_str(json).map_err(|e| ValidationError::Configuration {
+            message: format!("Invalid JSON rule spec: {}", e),
+        })
+    }
+
+    /// Read a spec from a file, parsed as JSON for a `.json` extension and YAML otherwise
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ValidationError> {
+        let path = path.as_ref();
+        let contents =
+            std::fs::read_to_string(path).map_err(|e| ValidationError::Configuration {
+                message: format!("Unable to read rule spec '{}': {}", path.display(), e),
+            })?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Self::from_json(&contents),
+            _ => Self::from_yaml(&contents),
+        }
+    }
+
+    /// Build the RuleSet described by this spec with the built-in rules
+    pub fn to_rule_set(&self) -> Result<RuleSet, ValidationError> {
+        self.to_rule_set_with(&RuleRegistry::new())
+    }
+
+    /// Build the RuleSet described by this spec, resolving rule names in `registry`
+    pub fn to_rule_set_with(&self, registry: &RuleRegistry) -> Result<RuleSet, ValidationError> {
+        let mut rule_set = RuleSet::new();
+
+        for rule in &self.schema {
+            rule_set.with_schema_rule(rule.to_rule(registry)?);
+        }
+
+        for rule in &self.table {
+            rule_set
+                .with_table_rule(
+                    &rule.column,
+                    rule.rule.to_rule(registry)?,
+                    rule.check
+                        .as_ref()
+                        .map(|check| check.to_rule(registry))
+                        .transpose()?,
+                )
+                .with_tags(&rule.tags)
+                .with_depends_on(&rule.depends_on);
+        }
+
+        for column in &self.columns {
+            // Nested paths such as `payload.user.id` or `items[*].price`
+            let nested = column.column.contains(['.', '[']);
+            for rule in &column.rules {
+                let rule = if nested {
+                    dfq_nested(rule.to_rule(registry)?)
+                } else {
+                    rule.to_rule(registry)?
+                };
+                rule_set
+                    .with_column_rule(&column.column, rule)
+                    .with_tags(&column.tags)
+                    .with_depends_on(&column.depends_on);
+            }
+        }
+
+        for rule in &self.dataset {
+            rule_set
+                .with_dataset_rule(&rule.column, rule.rule.to_rule(registry)?)
+                .with_tags(&rule.tags)
+                .with_depends_on(&rule.depends_on);
+        }
+
+        for instance in &self.instances {
+            let template = self.templates.get(&instance.template).ok_or_else(|| {
+                ValidationError::Configuration {
+                    message: format!("Unknown rule template '{}'", instance.template),
+                }
+            })?;
+            let mut instance_rules = template
+                .bind(&instance.arguments)
+                .and_then(|spec| spec.to_rule_set_with(registry))
+                .map_err(|e| match e {
+                    ValidationError::Configuration { message } => ValidationError::Configuration {
+                        message: format!("Template '{}': {}", instance.template, message),
+                    },
+                    e => e,
+                })?;
+            instance_rules.tag_all(&instance.tags);
+            rule_set.extend(&instance_rules);
+        }
+
+        Ok(rule_set)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Float64Array, Int32Array, StringArray};
+    use arrow::datatypes::{Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion::assert_batches_eq;
+
+    fn create_test_df() -> DataFrame {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("score", DataType::Float64, true),
+        ]);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![
+                    Some("Alice"),
+                    None,
+                    Some("Charlie"),
+                ])),
+                Arc::new(Float64Array::from(vec![
+                    Some(85.5),
+                    Some(92.0),
+                    Some(140.0),
+                ])),
+            ],
+        )
+        .unwrap();
+
+        SessionContext::new().read_batch(batch).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_yaml_spec() {
+        let spec = RuleSetSpec::from_yaml(
+            r#"
+schema:
+  - column_type: { column: id, data_type: Int32 }
+columns:
+  - column: name
+    rules:
+      - not_null
+  - column: score
+    rules:
+      - in_range: { min: 0, max: 100 }
+table:
+  - column: id
+    rule: count
+    check: { gte: 3 }
+dataset:
+  - column: table
+    rule: { row_count_between: { min: 1, max: 2 } }
+  - column: name
+    rule: { mostly: { rule: not_null, ratio: 0.5 } }
+"#,
+        )
+        .unwrap();
+
+        let rule_set = spec.to_rule_set().unwrap();
+        let outcomes = rule_set.dataset_outcomes(&create_test_df()).await.unwrap();
+        assert_eq!(outcomes[0].outcome_name, "table_row_count_between");
+        assert!(!outcomes[0].passed);
+        assert_eq!(outcomes[1].outcome_name, "name_not_null_pass_rate");
+        assert!(outcomes[1].passed);
+
+        let result = rule_set
+            .apply(&create_test_df())
+            .await
+            .unwrap()
+            .select_columns(&[
+                "id",
+                "name_not_null",
+                "score_in_range",
+                "id_count_greater_than_equals",
+                "dfq_pass",
+            ])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+
+        let expected = vec![
+            "+----+---------------+----------------+------------------------------+----------+",
+            "| id | name_not_null | score_in_range | id_count_greater_than_equals | dfq_pass |",
+            "+----+---------------+----------------+------------------------------+----------+",
+            "| 1  | true          | true           | true                         | true     |",
+            "| 2  | false         | true           | true                         | false    |",
+            "| 3  | true          | false          | true                         | false    |",
+            "+----+---------------+----------------+------------------------------+----------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+    }
+
+    #[test]
+    fn test_json_spec() {
+        let spec = RuleSetSpec::from_json(
+            r#"{
+                "columns": [
+                    {"column": "name", "rules": [{"sql": {"name": "not_bob", "expr": "name <> 'Bob'"}}]}
+                ]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            spec.columns[0].rules[0],
+            ColumnRuleSpec::Sql {
+                name: "not_bob".to_string(),
+                expr: "name <> 'Bob'".to_string()
+            }
+        );
+
+        // Unknown rule names are resolved in the registry when the RuleSet is built
+        let spec =
+            RuleSetSpec::from_json(r#"{"columns": [{"column": "name", "rules": ["unknown"]}]}"#)
+                .unwrap();
+        assert!(matches!(
+            spec.to_rule_set(),
+            Err(ValidationError::Configuration { .. })
+        ));
+        assert!(matches!(
+            RuleSetSpec::from_json(r#"{"columns": [{"column": "name", "rules": [1]}]}"#),
+            Err(ValidationError::Configuration { .. })
+        ));
+
+        let spec = RuleSetSpec::from_yaml(
+            "schema:\n  - column_type: { column: id, data_type: NotAType }\n",
+        )
+        .unwrap();
+        assert!(matches!(
+            spec.to_rule_set(),
+            Err(ValidationError::Configuration { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_template_spec() {
+        let spec = RuleSetSpec::from_yaml(
+            r#"
+templates:
+  scored:
+    parameters:
+      column: { type: column }
+      max: { type: float, default: 100 }
+      min_rows: { type: integer }
+    columns:
+      - column: ${column}
+        rules:
+          - in_range: { min: 0, max: "${max}" }
+          - sql: { name: below_max, expr: "${column} < ${max}" }
+    table:
+      - column: ${column}
+        rule: count
+        check: { gte: "${min_rows}" }
+instances:
+  - template: scored
+    arguments: { column: score, max: 90, min_rows: 3 }
+    tags: [scores]
+"#,
+        )
+        .unwrap();
+
+        let rule_set = spec.to_rule_set().unwrap();
+        let result = rule_set
+            .apply(&create_test_df())
+            .await
+            .unwrap()
+            .drop_columns(&["name", "score", "score_count"])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+
+        let expected = vec![
+            "+----+---------------------------------+----------------+-----------------+----------+",
+            "| id | score_count_greater_than_equals | score_in_range | score_below_max | dfq_pass |",
+            "+----+---------------------------------+----------------+-----------------+----------+",
+            "| 1  | true                            | true           | true            | true     |",
+            "| 2  | true                            | false          | false           | false    |",
+            "| 3  | true                            | false          | false           | false    |",
+            "+----+---------------------------------+----------------+-----------------+----------+",
+        ];
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+        assert_eq!(rule_set.filter_by_tags(["scores"]).column_rules.len(), 3);
+
+        // Every parameter must be bound with a value of its type
+        for arguments in [
+            "{ column: score }",
+            "{ column: score, min_rows: 2.5 }",
+            "{ column: \"\", min_rows: 3 }",
+            "{ column: score, min_rows: 3, typo: 1 }",
+        ] {
+            let mut spec = spec.clone();
+            spec.instances[0].arguments = serde_yaml::from_str(arguments).unwrap();
+            assert!(matches!(
+                spec.to_rule_set(),
+                Err(ValidationError::Configuration { .. })
+            ));
+        }
+
+        // Placeholders must be parameters or context variables
+        for (expr, valid) in [
+            ("${column} < DATE '${run_date}'", true),
+            ("${colum} < ${max}", false),
+        ] {
+            let mut spec = spec.clone();
+            let template = spec.templates.get_mut("scored").unwrap();
+            template.rules["columns"][0]["rules"][1]["sql"]["expr"] = expr.into();
+            assert_eq!(spec.to_rule_set().is_ok(), valid);
+        }
+
+        let mut spec = spec.clone();
+        spec.instances[0].template = "missing".to_string();
+        assert!(matches!(
+            spec.to_rule_set(),
+            Err(ValidationError::Configuration { .. })
+        ));
+    }
+}
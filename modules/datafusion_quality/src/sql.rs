@@ -0,0 +1,507 @@
+use crate::{
+    RuleSet, ValidationError,
+    error::DataFusionSnafu,
+    rules::{column::dfq_custom_sql, dfq_eq, table::dfq_custom_agg_sql},
+    udf::RuleSetCatalog,
+};
+use datafusion::{
+    prelude::*,
+    sql::sqlparser::{
+        ast::{Expr as SqlExpr, Ident, visit_expressions},
+        dialect::GenericDialect,
+        keywords::Keyword,
+        parser::{Parser, ParserError},
+        tokenizer::Token,
+    },
+};
+use snafu::ResultExt;
+use std::{collections::HashSet, ops::ControlFlow};
+
+/// A statement understood by [`QualitySession::sql`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum QualityStatement {
+    /// `CREATE RULESET <name> (<check>, ...)`
+    CreateRuleSet { name: String, checks: Vec<SqlExpr> },
+    /// `VALIDATE <table> USING <rule set> [REPORT]`
+    Validate {
+        table: String,
+        rule_set: String,
+        report: bool,
+    },
+    /// Any other statement, passed through to DataFusion
+    Sql(String),
+}
+
+/// Parse a statement, recognising the `CREATE RULESET` and `VALIDATE` extensions.
+///
+/// Each check in `CREATE RULESET` is a SQL boolean expression. `<expr> NOT NULL` is
+/// accepted as shorthand for `<expr> IS NOT NULL`.
+pub fn parse_statement(sql: &str) -> Result<QualityStatement, ValidationError> {
+    let dialect = GenericDialect {};
+    let mut parser = Parser::new(&dialect)
+        .try_with_sql(sql)
+        .map_err(parser_error)?;
+
+    let is_word = |token: Token, word: &str| match token {
+        Token::Word(w) => w.value.eq_ignore_ascii_case(word),
+        _ => false,
+    };
+
+    if parser.peek_keyword(Keyword::CREATE) && is_word(parser.peek_nth_token(1).token, "RULESET") {
+        parser.next_token();
+        parser.next_token();
+        let name = parser.parse_identifier().map_err(parser_error)?.value;
+        parser.expect_token(&Token::LParen).map_err(parser_error)?;
+        let checks = parser
+            .parse_comma_separated(|p| {
+                let expr = p.parse_expr()?;
+                if p.parse_keywords(&[Keyword::NOT, Keyword::NULL]) {
+                    Ok(SqlExpr::IsNotNull(Box::new(expr)))
+                } else {
+                    Ok(expr)
+                }
+            })
+            .map_err(parser_error)?;
+        parser.expect_token(&Token::RParen).map_err(parser_error)?;
+        expect_end(&mut parser)?;
+        return Ok(QualityStatement::CreateRuleSet { name, checks });
+    }
+
+    if is_word(parser.peek_token().token, "VALIDATE") {
+        parser.next_token();
+        let table = parser.parse_object_name(false).map_err(parser_error)?;
+        if !parser.parse_keyword(Keyword::USING) {
+            return Err(expected("USING", &parser));
+        }
+        let rule_set = parser.parse_identifier().map_err(parser_error)?.value;
+        let report = is_word(parser.peek_token().token, "REPORT");
+        if report {
+            parser.next_token();
+        }
+        expect_end(&mut parser)?;
+        return Ok(QualityStatement::Validate {
+            table: table.to_string(),
+            rule_set,
+            report,
+        });
+    }
+
+    Ok(QualityStatement::Sql(sql.to_string()))
+}
+
+fn expect_end(parser: &mut Parser) -> Result<(), ValidationError> {
+    let _ = parser.consume_token(&Token::SemiColon);
+    match parser.peek_token().token {
+        Token::EOF => Ok(()),
+        _ => Err(expected("end of statement", parser)),
+    }
+}
+
+/// The error for an unexpected next token
+fn expected(what: &str, parser: &Parser) -> ValidationError {
+    parser_error(ParserError::ParserError(format!(
+        "Expected: {}, found: {}",
+        what,
+        parser.peek_token()
+    )))
+}
+
+fn parser_error(e: ParserError) -> ValidationError {
+    ValidationError::Configuration {
+        message: format!("Invalid data quality statement: {}", e),
+    }
+}
+
+/// A SessionContext that also understands `CREATE RULESET` and `VALIDATE` statements
+///
+/// # Examples
+///
+/// ```no_run
+/// use datafusion_quality::sql::QualitySession;
+/// use datafusion::prelude::*;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let session = QualitySession::new(SessionContext::new());
+/// session
+///     .sql("CREATE RULESET orders_checks (name NOT NULL, score BETWEEN 0 AND 100, COUNT(*) > 1000)")
+///     .await?;
+/// let annotated = session.sql("VALIDATE orders USING orders_checks").await?;
+/// let report = session.sql("VALIDATE orders USING orders_checks REPORT").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct QualitySession {
+    ctx: SessionContext,
+    rule_sets: RuleSetCatalog,
+}
+
+impl std::fmt::Debug for QualitySession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QualitySession")
+            .field("session_id", &self.ctx.session_id())
+            .field("rule_sets", &self.rule_sets)
+            .finish()
+    }
+}
+
+impl QualitySession {
+    /// Create a QualitySession with an empty RuleSetCatalog
+    pub fn new(ctx: SessionContext) -> Self {
+        Self::with_catalog(ctx, RuleSetCatalog::new())
+    }
+
+    /// Create a QualitySession storing rule sets in `rule_sets`, e.g. to share them
+    /// with the `dfq_validate` table function
+    pub fn with_catalog(ctx: SessionContext, rule_sets: RuleSetCatalog) -> Self {
+        Self { ctx, rule_sets }
+    }
+
+    /// The wrapped SessionContext
+    pub fn context(&self) -> &SessionContext {
+        &self.ctx
+    }
+
+    /// The rule sets created with `CREATE RULESET`
+    pub fn catalog(&self) -> &RuleSetCatalog {
+        &self.rule_sets
+    }
+
+    /// Run a statement.
+    ///
+    /// * `CREATE RULESET` registers a RuleSet and returns an empty DataFrame
+    /// * `VALIDATE` returns the annotated DataFrame, or with `REPORT` one row per rule
+    ///   with its pass/fail counts
+    /// * Anything else is run by the wrapped SessionContext
+    pub async fn sql(&self, sql: &str) -> Result<DataFrame, ValidationError> {
+        match parse_statement(sql)? {
+            QualityStatement::CreateRuleSet { name, checks } => {
+                let rule_set = self.build_rule_set(&checks)?;
+                self.rule_sets.register(name, rule_set);
+                self.ctx.read_empty().context(DataFusionSnafu)
+            }
+            QualityStatement::Validate {
+                table,
+                rule_set,
+                report,
+            } => {
+                let rules = self.rule_sets.get(&rule_set).ok_or_else(|| {
+                    ValidationError::Configuration {
+                        message: format!("Unknown rule set '{}'", rule_set),
+                    }
+                })?;
+                let df = self.ctx.table(table.as_str()).await?;
+                if report {
+                    let report = rules.report(&df).await?;
+                    self.ctx
+                        .read_batch(report.to_record_batch()?)
+                        .context(DataFusionSnafu)
+                } else {
+                    rules.apply(&df).await
+                }
+            }
+            QualityStatement::Sql(sql) => self.ctx.sql(&sql).await.context(DataFusionSnafu),
+        }
+    }
+
+    /// Translate checks into rules: checks using an aggregate function become table
+    /// rules, anything else becomes a column rule on the first column it references
+    fn build_rule_set(&self, checks: &[SqlExpr]) -> Result<RuleSet, ValidationError> {
+        let state = self.ctx.state();
+        let aggregate_names: HashSet<String> = state
+            .aggregate_functions()
+            .values()
+            .flat_map(|f| std::iter::once(f.name()).chain(f.aliases().iter().map(String::as_str)))
+            .map(str::to_lowercase)
+            .collect();
+
+        let mut rule_set = RuleSet::new();
+        let mut check_columns = HashSet::new();
+        for check in checks {
+            let sql = check.to_string();
+            let column = first_column(check);
+            let mut rule_name = check_rule_name(&sql, column.as_deref());
+
+            // Distinct checks can get the same name, e.g. `name = 'A'` and `name = 'a'`
+            let prefix = column.as_deref().unwrap_or("table");
+            let base_name = rule_name.clone();
+            let mut suffix = 1;
+            while !check_columns.insert(format!("{}_{}", prefix, rule_name)) {
+                suffix += 1;
+                rule_name = format!("{}_{}", base_name, suffix);
+            }
+
+            if contains_aggregate(check, &aggregate_names) {
+                let column = column.unwrap_or_else(|| "table".to_string());
+                rule_set.with_table_rule(
+                    column,
+                    dfq_custom_agg_sql(&sql, &rule_name),
+                    Some(dfq_eq(lit(true))),
+                );
+            } else {
+                let column = column.ok_or_else(|| ValidationError::Configuration {
+                    message: format!("Check '{}' does not reference a column", sql),
+                })?;
+                rule_set.with_column_rule(column, dfq_custom_sql(&rule_name, &sql));
+            }
+        }
+
+        Ok(rule_set)
+    }
+}
+
+fn normalize_ident(ident: &Ident) -> String {
+    if ident.quote_style.is_some() {
+        ident.value.clone()
+    } else {
+        ident.value.to_lowercase()
+    }
+}
+
+fn first_column(expr: &SqlExpr) -> Option<String> {
+    let found = visit_expressions(expr, |e| match e {
+        SqlExpr::Identifier(ident) => ControlFlow::Break(normalize_ident(ident)),
+        SqlExpr::CompoundIdentifier(idents) => ControlFlow::Break(
+            idents
+                .iter()
+                .map(normalize_ident)
+                .collect::<Vec<_>>()
+                .join("."),
+        ),
+        _ => ControlFlow::Continue(()),
+    });
+    match found {
+        ControlFlow::Break(column) => Some(column),
+        ControlFlow::Continue(()) => None,
+    }
+}
+
+fn contains_aggregate(expr: &SqlExpr, aggregate_names: &HashSet<String>) -> bool {
+    visit_expressions(expr, |e| match e {
+        SqlExpr::Function(f) if aggregate_names.contains(&f.name.to_string().to_lowercase()) => {
+            ControlFlow::Break(())
+        }
+        _ => ControlFlow::Continue(()),
+    })
+    .is_break()
+}
+
+/// Derive a column-name friendly rule name from the check text, e.g.
+/// `score BETWEEN 0 AND 100` becomes `between_0_and_100` for column `score`
+fn check_rule_name(sql: &str, column: Option<&str>) -> String {
+    let mut text = sql.to_lowercase();
+    for (op, word) in [
+        (">=", " gte "),
+        ("<=", " lte "),
+        ("<>", " ne "),
+        ("!=", " ne "),
+        (">", " gt "),
+        ("<", " lt "),
+        ("=", " eq "),
+        ("*", " star "),
+    ] {
+        text = text.replace(op, word);
+    }
+
+    let name = text
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+
+    match column.and_then(|column| name.strip_prefix(&format!("{}_", column.replace('.', "_")))) {
+        Some(rest) => rest.to_string(),
+        None => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Float64Array, Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion::assert_batches_eq;
+    use std::sync::Arc;
+
+    fn create_test_session() -> QualitySession {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true),
+            Field::new("score", DataType::Float64, true),
+        ]);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![
+                    Some("Alice"),
+                    None,
+                    Some("Charlie"),
+                ])),
+                Arc::new(Float64Array::from(vec![
+                    Some(85.5),
+                    Some(92.0),
+                    Some(140.0),
+                ])),
+            ],
+        )
+        .unwrap();
+
+        let ctx = SessionContext::new();
+        ctx.register_batch("orders", batch).unwrap();
+        QualitySession::new(ctx)
+    }
+
+    #[test]
+    fn test_parse_statement() {
+        let statement =
+            parse_statement("CREATE RULESET checks (name NOT NULL, score BETWEEN 0 AND 100);")
+                .unwrap();
+        let QualityStatement::CreateRuleSet { name, checks } = statement else {
+            panic!("expected CREATE RULESET");
+        };
+        assert_eq!(name, "checks");
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].to_string(), "name IS NOT NULL");
+
+        assert_eq!(
+            parse_statement("validate orders using checks report").unwrap(),
+            QualityStatement::Validate {
+                table: "orders".to_string(),
+                rule_set: "checks".to_string(),
+                report: true,
+            }
+        );
+
+        assert_eq!(
+            parse_statement("SELECT 1").unwrap(),
+            QualityStatement::Sql("SELECT 1".to_string())
+        );
+
+        assert!(parse_statement("VALIDATE orders checks").is_err());
+        assert!(parse_statement("CREATE RULESET checks (name NOT NULL").is_err());
+    }
+
+    #[test]
+    fn test_check_rule_name() {
+        assert_eq!(
+            check_rule_name("score BETWEEN 0 AND 100", Some("score")),
+            "between_0_and_100"
+        );
+        assert_eq!(
+            check_rule_name("COUNT(*) > 1000", None),
+            "count_star_gt_1000"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_and_validate() {
+        let session = create_test_session();
+
+        session
+            .sql(
+                "CREATE RULESET orders_checks \
+                 (name NOT NULL, score BETWEEN 0 AND 100, COUNT(*) > 2)",
+            )
+            .await
+            .unwrap();
+
+        let result = session
+            .sql("VALIDATE orders USING orders_checks")
+            .await
+            .unwrap()
+            .select_columns(&[
+                "id",
+                "name_is_not_null",
+                "score_between_0_and_100",
+                "table_count_star_gt_2_equals",
+                "dfq_pass",
+            ])
+            .unwrap()
+            .sort(vec![col("id").sort(true, false)])
+            .unwrap();
+
+        let expected = vec![
+            "+----+------------------+-------------------------+------------------------------+----------+",
+            "| id | name_is_not_null | score_between_0_and_100 | table_count_star_gt_2_equals | dfq_pass |",
+            "+----+------------------+-------------------------+------------------------------+----------+",
+            "| 1  | true             | true                    | true                         | true     |",
+            "| 2  | false            | true                    | true                         | false    |",
+            "| 3  | true             | false                   | true                         | false    |",
+            "+----+------------------+-------------------------+------------------------------+----------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        let report = session
+            .sql("VALIDATE orders USING orders_checks REPORT")
+            .await
+            .unwrap();
+
+        let expected = vec![
+            "+-----------------------+------------+------------------------------+--------+--------+---------+",
+            "| column_name           | rule_name  | check_column                 | passed | failed | skipped |",
+            "+-----------------------+------------+------------------------------+--------+--------+---------+",
+            "| name                  | custom_sql | name_is_not_null             | 2      | 1      | false   |",
+            "| score                 | custom_sql | score_between_0_and_100      | 2      | 1      | false   |",
+            "| table_count_star_gt_2 | equals     | table_count_star_gt_2_equals | 3      | 0      | false   |",
+            "+-----------------------+------------+------------------------------+--------+--------+---------+",
+        ];
+
+        assert_batches_eq!(&expected, &report.collect().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_passthrough_and_errors() {
+        let session = create_test_session();
+
+        let result = session
+            .sql("SELECT count(*) AS n FROM orders")
+            .await
+            .unwrap();
+        let expected = vec!["+---+", "| n |", "+---+", "| 3 |", "+---+"];
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        let err = session
+            .sql("VALIDATE orders USING missing")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ValidationError::Configuration { .. }));
+
+        let err = session.sql("CREATE RULESET bad (1 = 1)").await.unwrap_err();
+        assert!(matches!(err, ValidationError::Configuration { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_check_names() {
+        let session = create_test_session();
+
+        session
+            .sql(
+                "CREATE RULESET duplicates \
+                 (name = 'Alice', name = 'alice', score - id > 0, score + id > 0)",
+            )
+            .await
+            .unwrap();
+
+        let report = session
+            .sql("VALIDATE orders USING duplicates REPORT")
+            .await
+            .unwrap();
+
+        let expected = vec![
+            "+-------------+------------+-----------------+--------+--------+---------+",
+            "| column_name | rule_name  | check_column    | passed | failed | skipped |",
+            "+-------------+------------+-----------------+--------+--------+---------+",
+            "| name        | custom_sql | name_eq_alice   | 1      | 2      | false   |",
+            "| name        | custom_sql | name_eq_alice_2 | 0      | 3      | false   |",
+            "| score       | custom_sql | score_id_gt_0   | 3      | 0      | false   |",
+            "| score       | custom_sql | score_id_gt_0_2 | 3      | 0      | false   |",
+            "+-------------+------------+-----------------+--------+--------+---------+",
+        ];
+
+        assert_batches_eq!(&expected, &report.collect().await.unwrap());
+    }
+}
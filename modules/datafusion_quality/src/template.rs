@@ -0,0 +1,298 @@
+use crate::{RuleSet, ValidationError, spec::RuleSetSpec};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// The type of a [`TemplateParameter`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ParameterType {
+    Integer,
+    /// Any number, including integers
+    Float,
+    String,
+    Boolean,
+    /// A non-empty column name or nested path
+    Column,
+}
+
+impl ParameterType {
+    fn accepts(self, value: &Value) -> bool {
+        match self {
+            ParameterType::Integer => value.is_i64() || value.is_u64(),
+            ParameterType::Float => value.is_number(),
+            ParameterType::String => value.is_string(),
+            ParameterType::Boolean => value.is_boolean(),
+            ParameterType::Column => value.as_str().is_some_and(|s| !s.is_empty()),
+        }
+    }
+}
+
+impl std::fmt::Display for ParameterType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ParameterType::Integer => "integer",
+            ParameterType::Float => "float",
+            ParameterType::String => "string",
+            ParameterType::Boolean => "boolean",
+            ParameterType::Column => "column",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A named parameter of a [`RuleTemplate`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateParameter {
+    #[serde(rename = "type")]
+    pub kind: ParameterType,
+    /// The value used when the parameter is not bound
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<Value>,
+}
+
+impl TemplateParameter {
+    /// Create a required parameter
+    pub fn new(kind: ParameterType) -> Self {
+        Self {
+            kind,
+            default: None,
+        }
+    }
+
+    /// Make the parameter optional, defaulting to `default`
+    pub fn with_default(mut self, default: impl Into<Value>) -> Self {
+        self.default = Some(default.into());
+        self
+    }
+}
+
+/// The values bound to the parameters of a [`RuleTemplate`]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct TemplateArguments(BTreeMap<String, Value>);
+
+impl TemplateArguments {
+    /// Create empty arguments
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind `value` to the parameter `name`
+    pub fn with_argument(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.0.insert(name.into(), value.into());
+        self
+    }
+}
+
+/// A [`RuleSetSpec`] with `${name}` placeholders for named, typed parameters
+///
+/// A string that is exactly one placeholder is replaced by the typed value, so
+/// `gte: "${min_rows}"` becomes a number. Placeholders inside longer strings are
+/// replaced by the value's text. The `${run_date}` and `${environment}` placeholders
+/// are left for the [`RuleContext`](crate::RuleContext); any other placeholder must
+/// be a declared parameter. YAML flow
+/// mappings such as `{ gte: "${min_rows}" }` need the placeholder quoted.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::template::{RuleTemplate, TemplateArguments};
+///
+/// let template = RuleTemplate::from_yaml(r#"
+/// parameters:
+///   id_column: { type: column }
+///   min_rows: { type: integer, default: 1 }
+/// columns:
+///   - column: ${id_column}
+///     rules: [not_null]
+/// table:
+///   - column: ${id_column}
+///     rule: count
+///     check: { gte: "${min_rows}" }
+/// "#).unwrap();
+///
+/// let orders = template
+///     .instantiate(&TemplateArguments::new().with_argument("id_column", "order_id").with_argument("min_rows", 1000))
+///     .unwrap();
+/// let customers = template
+///     .instantiate(&TemplateArguments::new().with_argument("id_column", "customer_id"))
+///     .unwrap();
+///
+/// // Unbound parameters and arguments of the wrong type are rejected
+/// assert!(template.instantiate(&TemplateArguments::new()).is_err());
+/// assert!(template
+///     .instantiate(&TemplateArguments::new().with_argument("id_column", "id").with_argument("min_rows", "many"))
+///     .is_err());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleTemplate {
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub parameters: BTreeMap<String, TemplateParameter>,
+    /// The fields of a [`RuleSetSpec`], parsed once the parameters are bound
+    #[serde(flatten)]
+    pub rules: Map<String, Value>,
+}
+
+impl RuleTemplate {
+    /// Create a template from the fields of a [`RuleSetSpec`], e.g. parsed from JSON
+    pub fn new(rules: Map<String, Value>) -> Self {
+        Self {
+            parameters: BTreeMap::new(),
+            rules,
+        }
+    }
+
+    /// Add a parameter
+    pub fn with_parameter(mut self, name: impl Into<String>, parameter: TemplateParameter) -> Self {
+        self.parameters.insert(name.into(), parameter);
+        self
+    }
+
+    /// Parse a template from YAML
+    pub fn from_yaml(yaml: &str) -> Result<Self, ValidationError> {
+        let value: Value =
+            serde_yaml::from_str(yaml).map_err(|e| ValidationError::Configuration {
+                message: format!("Invalid YAML rule template: {}", e),
+            })?;
+        serde_json::from_value(value).map_err(|e| ValidationError::Configuration {
+            message: format!("Invalid YAML rule template: {}", e),
+        })
+    }
+
+    /// Parse a template from JSON
+    pub fn from_json(json: &str) -> Result<Self, ValidationError> {
+        serde_json::from_str(json).map_err(|e| ValidationError::Configuration {
+            message: format!("Invalid JSON rule template: {}", e),
+        })
+    }
+
+    /// Bind the parameters, returning the resulting spec
+    ///
+    /// Returns a configuration error when a parameter without default is not bound,
+    /// an argument does not match its parameter's type or names no parameter, or a
+    /// placeholder names neither a parameter nor a context variable.
+    pub fn bind(&self, arguments: &TemplateArguments) -> Result<RuleSetSpec, ValidationError> {
+        let rules = Value::Object(self.rules.clone());
+        if let Some(name) = unknown_placeholder(&rules, &self.parameters) {
+            return Err(ValidationError::Configuration {
+                message: format!("Unknown template placeholder '${{{}}}'", name),
+            });
+        }
+
+        if let Some(name) = arguments
+            .0
+            .keys()
+            .find(|name| !self.parameters.contains_key(*name))
+        {
+            return Err(ValidationError::Configuration {
+                message: format!("Unknown template parameter '{}'", name),
+            });
+        }
+
+        let mut values = BTreeMap::new();
+        for (name, parameter) in &self.parameters {
+            let value = arguments
+                .0
+                .get(name)
+                .or(parameter.default.as_ref())
+                .ok_or_else(|| ValidationError::Configuration {
+                    message: format!("Template parameter '{}' is not bound", name),
+                })?;
+            if !parameter.kind.accepts(value) {
+                return Err(ValidationError::Configuration {
+                    message: format!(
+                        "Template parameter '{}' expects a {}, got {}",
+                        name, parameter.kind, value
+                    ),
+                });
+            }
+            values.insert(format!("${{{}}}", name), value);
+        }
+
+        let rules = substitute(&rules, &values);
+        serde_json::from_value(rules).map_err(|e| ValidationError::Configuration {
+            message: format!("Invalid rule template: {}", e),
+        })
+    }
+
+    /// Bind the parameters and build the resulting RuleSet
+    pub fn instantiate(&self, arguments: &TemplateArguments) -> Result<RuleSet, ValidationError> {
+        self.bind(arguments)?.to_rule_set()
+    }
+}
+
+/// A use of a named template in a [`RuleSetSpec`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateInstance {
+    pub template: String,
+    #[serde(default)]
+    pub arguments: TemplateArguments,
+    /// Tags for every rule of the instance, see [`RuleSet::tag_all`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// The [`RuleContext`](crate::RuleContext) variables a template may leave unbound
+const CONTEXT_VARIABLES: [&str; 2] = ["run_date", "environment"];
+
+/// The first `${name}` placeholder in `value` that is neither a parameter nor a
+/// context variable
+fn unknown_placeholder<'a>(
+    value: &'a Value,
+    parameters: &BTreeMap<String, TemplateParameter>,
+) -> Option<&'a str> {
+    match value {
+        Value::String(s) => {
+            let mut rest = s.as_str();
+            while let Some(start) = rest.find("${") {
+                let end = rest[start..].find('}')?;
+                let name = &rest[start + 2..start + end];
+                if !parameters.contains_key(name) && !CONTEXT_VARIABLES.contains(&name) {
+                    return Some(name);
+                }
+                rest = &rest[start + end + 1..];
+            }
+            None
+        }
+        Value::Array(items) => items
+            .iter()
+            .find_map(|v| unknown_placeholder(v, parameters)),
+        Value::Object(fields) => fields
+            .values()
+            .find_map(|v| unknown_placeholder(v, parameters)),
+        _ => None,
+    }
+}
+
+/// Replace the placeholders in every string of `value`, keyed by `${name}`
+fn substitute(value: &Value, values: &BTreeMap<String, &Value>) -> Value {
+    match value {
+        Value::String(s) => {
+            if let Some(value) = values.get(s.as_str()) {
+                return (*value).clone();
+            }
+            let mut s = s.clone();
+            for (placeholder, value) in values {
+                if s.contains(placeholder.as_str()) {
+                    let text = match value {
+                        Value::String(text) => text.clone(),
+                        value => value.to_string(),
+                    };
+                    s = s.replace(placeholder.as_str(), &text);
+                }
+            }
+            Value::String(s)
+        }
+        Value::Array(items) => Value::Array(items.iter().map(|v| substitute(v, values)).collect()),
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(k, v)| (k.clone(), substitute(v, values)))
+                .collect(),
+        ),
+        value => value.clone(),
+    }
+}
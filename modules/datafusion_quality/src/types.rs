@@ -0,0 +1,126 @@
+use crate::ValidationError;
+use arrow::datatypes::DataType;
+use datafusion::common::{Column, DFSchema};
+
+/// The Arrow types of the column a rule supports
+///
+/// Declared by [`ColumnRule::supported_types`](crate::ColumnRule::supported_types)
+/// and the other rule traits, and checked by [`RuleSet::check_types`](crate::RuleSet::check_types)
+/// before a rule is applied. Dictionary encoded columns are checked by their value type,
+/// and untyped `Null` columns are always supported.
+///
+/// # Examples
+///
+/// ```
+/// use arrow::datatypes::DataType;
+/// use datafusion_quality::SupportedTypes;
+///
+/// assert!(SupportedTypes::String.supports(&DataType::LargeUtf8));
+/// assert!(!SupportedTypes::Numeric.supports(&DataType::Utf8));
+/// assert_eq!(SupportedTypes::Temporal.to_string(), "a date or timestamp column");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum SupportedTypes {
+    #[default]
+    Any,
+    /// Utf8, LargeUtf8 and Utf8View
+    String,
+    /// Integers, floats and decimals
+    Numeric,
+    /// Dates and timestamps of any unit and time zone
+    Temporal,
+    /// List, LargeList and FixedSizeList
+    List,
+    /// Exactly the given types
+    Types(Vec<DataType>),
+    /// The types supported by every one of several rules applied to the column
+    All(Vec<SupportedTypes>),
+}
+
+impl SupportedTypes {
+    /// Whether a column of `data_type` is supported
+    pub fn supports(&self, data_type: &DataType) -> bool {
+        match (self, data_type) {
+            (SupportedTypes::Any, _) | (_, DataType::Null) => true,
+            (SupportedTypes::All(types), data_type) => {
+                types.iter().all(|types| types.supports(data_type))
+            }
+            (SupportedTypes::Types(types), data_type) if types.contains(data_type) => true,
+            (types, DataType::Dictionary(_, value_type)) => types.supports(value_type),
+            (SupportedTypes::String, data_type) => matches!(
+                data_type,
+                DataType::Utf8 | DataType::LargeUtf8 | DataType::Utf8View
+            ),
+            (SupportedTypes::Numeric, data_type) => data_type.is_numeric(),
+            (SupportedTypes::Temporal, data_type) => matches!(
+                data_type,
+                DataType::Date32 | DataType::Date64 | DataType::Timestamp(_, _)
+            ),
+            (SupportedTypes::List, data_type) => matches!(
+                data_type,
+                DataType::List(_) | DataType::LargeList(_) | DataType::FixedSizeList(_, _)
+            ),
+            (SupportedTypes::Types(_), _) => false,
+        }
+    }
+
+    /// Check the type of `column_name` in `schema`, naming `rule_name` in the error
+    ///
+    /// Columns that are not in the schema, such as nested paths, are left to the
+    /// rule itself.
+    pub fn check(
+        &self,
+        rule_name: &str,
+        column_name: &str,
+        schema: &DFSchema,
+    ) -> Result<(), ValidationError> {
+        let Ok((_, field)) = schema.qualified_field_from_column(&Column::from(column_name)) else {
+            return Ok(());
+        };
+        if self.supports(field.data_type()) {
+            return Ok(());
+        }
+        Err(ValidationError::TypeMismatch {
+            message: format!(
+                "Rule '{}' on column '{}' expects {}, got {}",
+                rule_name,
+                column_name,
+                self,
+                field.data_type()
+            ),
+        })
+    }
+}
+
+impl std::fmt::Display for SupportedTypes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SupportedTypes::Any => f.write_str("a column of any type"),
+            SupportedTypes::String => f.write_str("a string column"),
+            SupportedTypes::Numeric => f.write_str("a numeric column"),
+            SupportedTypes::Temporal => f.write_str("a date or timestamp column"),
+            SupportedTypes::List => f.write_str("a list column"),
+            SupportedTypes::Types(types) => {
+                let types: Vec<String> = types.iter().map(ToString::to_string).collect();
+                write!(f, "a column of type {}", types.join(" or "))
+            }
+            SupportedTypes::All(types) => {
+                let mut names: Vec<String> = Vec::new();
+                for name in types
+                    .iter()
+                    .filter(|types| **types != SupportedTypes::Any)
+                    .map(ToString::to_string)
+                {
+                    if !names.contains(&name) {
+                        names.push(name);
+                    }
+                }
+                if names.is_empty() {
+                    SupportedTypes::Any.fmt(f)
+                } else {
+                    f.write_str(&names.join(" and "))
+                }
+            }
+        }
+    }
+}
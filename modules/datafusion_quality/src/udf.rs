@@ -0,0 +1,750 @@
+use crate::{RuleSet, json_schema::JsonSchema, rules::column::StringFormat};
+use arrow::{
+    array::{Array, AsArray, BooleanArray, Int64Array},
+    compute::kernels::{
+        boolean::and_kleene,
+        cmp::{gt_eq, lt_eq},
+        regexp::regexp_is_match_scalar,
+    },
+    datatypes::{DataType, Schema, SchemaRef},
+};
+use async_trait::async_trait;
+use datafusion::{
+    catalog::{Session, TableFunctionImpl, TableProvider},
+    common::{
+        ScalarValue, exec_datafusion_err, exec_err, plan_datafusion_err, plan_err,
+        utils::take_function_args,
+    },
+    datasource::TableType,
+    error::{DataFusionError, Result},
+    execution::SessionState,
+    logical_expr::{
+        ColumnarValue, ScalarFunctionArgs, ScalarUDF, ScalarUDFImpl, Signature, Volatility,
+        type_coercion::binary::comparison_coercion,
+    },
+    physical_plan::ExecutionPlan,
+    prelude::*,
+};
+use serde::de::IgnoredAny;
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::{Arc, PoisonError, RwLock},
+};
+
+/// Named RuleSets that can be applied from SQL with the `dfq_validate` table function
+#[derive(Debug, Clone, Default)]
+pub struct RuleSetCatalog {
+    rule_sets: Arc<RwLock<HashMap<String, RuleSet>>>,
+}
+
+impl RuleSetCatalog {
+    /// Create an empty RuleSetCatalog
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a RuleSet under `name`, replacing any previous RuleSet with that name
+    pub fn register(&self, name: impl AsRef<str>, rule_set: RuleSet) -> &Self {
+        self.rule_sets
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(name.as_ref().to_string(), rule_set);
+        self
+    }
+
+    /// Get a copy of the RuleSet registered under `name`
+    pub fn get(&self, name: &str) -> Option<RuleSet> {
+        self.rule_sets
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(name)
+            .cloned()
+    }
+}
+
+/// Register the data quality scalar UDFs and the `dfq_validate` table function.
+///
+/// Scalar UDFs, all returning a nullable boolean:
+///
+/// * `dfq_in_range(value, min, max)` - `value` is between `min` and `max` (inclusive)
+/// * `dfq_like(value, pattern)` / `dfq_ilike(value, pattern)` - SQL LIKE pattern match
+/// * `dfq_str_length(value, min, max)` - character length is between `min` and `max`
+/// * `dfq_is_email(value)`, `dfq_is_uuid(value)`, `dfq_is_ipv4(value)` - format checks
+/// * `dfq_is_json(value)` - the string parses as JSON
+/// * `dfq_json_schema(value, schema)` - the string is JSON conforming to the literal
+///   JSON Schema `schema`, see [`JsonSchema`]
+///
+/// and `dfq_hash(value)`, a stable Int64 hash of any value (see [`dfq_hash`]).
+///
+/// The table function `dfq_validate('<rule set>', '<table>')` applies a RuleSet from
+/// `rule_sets` to a registered table and returns the annotated result, including
+/// `dfq_pass`. It resolves the table against a snapshot of the session taken here,
+/// so register any UDFs the rules depend on before calling this function. The rules
+/// are applied when the result is scanned, so rules that run queries, e.g. to check
+/// prerequisites, are awaited on the session's runtime.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::RuleSet;
+/// use datafusion_quality::rules::column::dfq_not_null;
+/// use datafusion_quality::udf::{RuleSetCatalog, register_udfs};
+/// use datafusion::prelude::*;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let ctx = SessionContext::new();
+/// let mut rules = RuleSet::new();
+/// rules.with_column_rule("name", dfq_not_null());
+///
+/// let catalog = RuleSetCatalog::new();
+/// catalog.register("orders_rules", rules);
+/// register_udfs(&ctx, &catalog);
+///
+/// let bad_emails = ctx.sql("SELECT * FROM orders WHERE NOT dfq_is_email(email)").await?;
+/// let validated = ctx.sql("SELECT * FROM dfq_validate('orders_rules', 'orders')").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn register_udfs(ctx: &SessionContext, rule_sets: &RuleSetCatalog) {
+    for (name, check) in [
+        ("dfq_in_range", Check::InRange),
+        (
+            "dfq_like",
+            Check::Like {
+                case_sensitive: true,
+            },
+        ),
+        (
+            "dfq_ilike",
+            Check::Like {
+                case_sensitive: false,
+            },
+        ),
+        ("dfq_str_length", Check::StrLength),
+        ("dfq_is_email", Check::Format(StringFormat::Email)),
+        ("dfq_is_uuid", Check::Format(StringFormat::Uuid)),
+        ("dfq_is_ipv4", Check::Format(StringFormat::Ipv4)),
+        ("dfq_is_json", Check::Json),
+        ("dfq_json_schema", Check::JsonSchema),
+    ] {
+        ctx.register_udf(ScalarUDF::from(CheckUdf::new(name, check)));
+    }
+    ctx.register_udf(hash_udf());
+
+    ctx.register_udtf(
+        "dfq_validate",
+        Arc::new(ValidateFunction {
+            state: ctx.state(),
+            rule_sets: rule_sets.clone(),
+        }),
+    );
+}
+
+/// The column check performed by a [`CheckUdf`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Check {
+    InRange,
+    Like { case_sensitive: bool },
+    StrLength,
+    Format(StringFormat),
+    Json,
+    JsonSchema,
+}
+
+impl Check {
+    fn num_args(self) -> usize {
+        match self {
+            Self::InRange | Self::StrLength => 3,
+            Self::Like { .. } | Self::JsonSchema => 2,
+            Self::Format(_) | Self::Json => 1,
+        }
+    }
+}
+
+/// The `dfq_is_json` or `dfq_json_schema` UDF, for rules that run them without
+/// registering
+pub(crate) fn json_udf(with_schema: bool) -> ScalarUDF {
+    if with_schema {
+        ScalarUDF::from(CheckUdf::new("dfq_json_schema", Check::JsonSchema))
+    } else {
+        ScalarUDF::from(CheckUdf::new("dfq_is_json", Check::Json))
+    }
+}
+
+/// Scalar UDF exposing a column rule check to SQL
+#[derive(Debug)]
+struct CheckUdf {
+    name: String,
+    check: Check,
+    signature: Signature,
+}
+
+impl CheckUdf {
+    fn new(name: &str, check: Check) -> Self {
+        Self {
+            name: name.to_string(),
+            check,
+            signature: Signature::user_defined(Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for CheckUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn coerce_types(&self, arg_types: &[DataType]) -> Result<Vec<DataType>> {
+        if arg_types.len() != self.check.num_args() {
+            return plan_err!(
+                "{} expects {} arguments, got {}",
+                self.name,
+                self.check.num_args(),
+                arg_types.len()
+            );
+        }
+
+        match self.check {
+            Check::InRange => {
+                let common = arg_types[1..]
+                    .iter()
+                    .try_fold(arg_types[0].clone(), |acc, t| comparison_coercion(&acc, t))
+                    .ok_or_else(|| {
+                        plan_datafusion_err!(
+                            "{} cannot compare argument types {:?}",
+                            self.name,
+                            arg_types
+                        )
+                    })?;
+                Ok(vec![common; 3])
+            }
+            Check::Like { .. } => Ok(vec![DataType::Utf8; 2]),
+            Check::StrLength => Ok(vec![DataType::Utf8, DataType::Int64, DataType::Int64]),
+            Check::Format(_) | Check::Json => Ok(vec![DataType::Utf8]),
+            Check::JsonSchema => Ok(vec![DataType::Utf8; 2]),
+        }
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let arrays = args
+            .args
+            .iter()
+            .map(|arg| arg.to_array(args.number_rows))
+            .collect::<Result<Vec<_>>>()?;
+
+        let result = match self.check {
+            Check::InRange => {
+                let [value, min, max] = take_function_args(&self.name, &arrays)?;
+                and_kleene(&gt_eq(value, min)?, &lt_eq(value, max)?)?
+            }
+            Check::Like { case_sensitive } => {
+                let [value, pattern] = take_function_args(&self.name, &arrays)?;
+                if case_sensitive {
+                    arrow::compute::like(value, pattern)?
+                } else {
+                    arrow::compute::ilike(value, pattern)?
+                }
+            }
+            Check::StrLength => {
+                let [value, min, max] = take_function_args(&self.name, &arrays)?;
+                str_length_between(
+                    value.as_string::<i32>(),
+                    min.as_primitive(),
+                    max.as_primitive(),
+                )
+            }
+            Check::Format(format) => {
+                let [value] = take_function_args(&self.name, &arrays)?;
+                regexp_is_match_scalar(value.as_string::<i32>(), format.pattern(), None)?
+            }
+            Check::Json => {
+                let [value] = take_function_args(&self.name, &arrays)?;
+                value
+                    .as_string::<i32>()
+                    .iter()
+                    .map(|v| v.map(|v| serde_json::from_str::<IgnoredAny>(v).is_ok()))
+                    .collect()
+            }
+            Check::JsonSchema => {
+                // The schema is compiled once per batch, so it must be a literal
+                let schema = match args.args.get(1) {
+                    Some(ColumnarValue::Scalar(ScalarValue::Utf8(Some(schema)))) => {
+                        JsonSchema::parse(schema).map_err(|e| exec_datafusion_err!("{}", e))?
+                    }
+                    _ => return exec_err!("{} expects a literal JSON Schema string", self.name),
+                };
+                let [value, _] = take_function_args(&self.name, &arrays)?;
+                value
+                    .as_string::<i32>()
+                    .iter()
+                    .map(|v| {
+                        v.map(|v| serde_json::from_str(v).is_ok_and(|json| schema.is_valid(&json)))
+                    })
+                    .collect()
+            }
+        };
+
+        Ok(ColumnarValue::Array(Arc::new(result)))
+    }
+}
+
+/// Character length of each value is between min and max (inclusive), null if any input is null
+fn str_length_between(
+    values: &arrow::array::StringArray,
+    min: &Int64Array,
+    max: &Int64Array,
+) -> BooleanArray {
+    (0..values.len())
+        .map(|i| {
+            if values.is_null(i) || min.is_null(i) || max.is_null(i) {
+                return None;
+            }
+            let length = i64::try_from(values.value(i).chars().count()).unwrap_or(i64::MAX);
+            Some(min.value(i) <= length && length <= max.value(i))
+        })
+        .collect()
+}
+
+/// The `dfq_hash` scalar UDF
+pub fn hash_udf() -> ScalarUDF {
+    ScalarUDF::from(HashUdf {
+        signature: Signature::any(1, Volatility::Immutable),
+    })
+}
+
+/// A stable Int64 hash of a value, null for null values.
+///
+/// Values are hashed (FNV-1a) through their string representation, so the hash is
+/// the same across sessions and processes, but differs between e.g. `1` and `1.0`.
+/// Summed as a decimal it gives an order-independent checksum of a column; unlike
+/// `bit_xor`, the sum does not cancel out duplicated values.
+///
+/// # Examples
+///
+/// ```
+/// use datafusion_quality::udf::dfq_hash;
+/// use arrow::datatypes::DataType;
+/// use datafusion::functions_aggregate::expr_fn::sum;
+/// use datafusion::prelude::*;
+///
+/// let checksum = sum(cast(dfq_hash(col("name")), DataType::Decimal128(38, 0)));
+/// ```
+pub fn dfq_hash(value: Expr) -> Expr {
+    hash_udf().call(vec![value])
+}
+
+#[derive(Debug)]
+struct HashUdf {
+    signature: Signature,
+}
+
+impl ScalarUDFImpl for HashUdf {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "dfq_hash"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Int64)
+    }
+
+    fn invoke_with_args(&self, args: ScalarFunctionArgs) -> Result<ColumnarValue> {
+        let [value] = take_function_args("dfq_hash", &args.args)?;
+        let values = arrow::compute::cast(&value.to_array(args.number_rows)?, &DataType::Utf8)?;
+        let hashes: Int64Array = values
+            .as_string::<i32>()
+            .iter()
+            .map(|v| v.map(fnv1a))
+            .collect();
+        Ok(ColumnarValue::Array(Arc::new(hashes)))
+    }
+}
+
+fn fnv1a(value: &str) -> i64 {
+    let hash = value.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    i64::from_ne_bytes(hash.to_ne_bytes())
+}
+
+/// Table function applying a named RuleSet to a registered table
+#[derive(Debug)]
+struct ValidateFunction {
+    state: SessionState,
+    rule_sets: RuleSetCatalog,
+}
+
+impl TableFunctionImpl for ValidateFunction {
+    fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+        let [rule_set_name, table_name] = take_function_args("dfq_validate", args)?;
+        let rule_set_name = string_literal(rule_set_name)?;
+        let table_name = string_literal(table_name)?;
+
+        let rule_set = self.rule_sets.get(&rule_set_name).ok_or_else(|| {
+            plan_datafusion_err!("dfq_validate: unknown rule set '{}'", rule_set_name)
+        })?;
+
+        // Looking the table up does not execute anything. The rules may run queries,
+        // e.g. to check prerequisites, so they are applied when the table is scanned.
+        let ctx = SessionContext::new_with_state(self.state.clone());
+        let table = futures::executor::block_on(ctx.table(table_name.as_str()))?;
+        let planned = rule_set
+            .plan(&table)
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        // Which rules are skipped is only known once applied, so any column may be null
+        let schema = Arc::new(Schema::new(
+            planned
+                .schema()
+                .fields()
+                .iter()
+                .map(|field| field.as_ref().clone().with_nullable(true))
+                .collect::<Vec<_>>(),
+        ));
+
+        Ok(Arc::new(ValidateTable {
+            rule_set,
+            table,
+            schema,
+        }))
+    }
+}
+
+/// The result of `dfq_validate`, applying the RuleSet when it is scanned
+#[derive(Debug)]
+struct ValidateTable {
+    rule_set: RuleSet,
+    table: DataFrame,
+    /// The schema planned by `RuleSet::plan`, with every column nullable
+    schema: SchemaRef,
+}
+
+#[async_trait]
+impl TableProvider for ValidateTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::View
+    }
+
+    async fn scan(
+        &self,
+        state: &dyn Session,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let annotated = self
+            .rule_set
+            .apply(&self.table)
+            .await
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        // Skipped rules have untyped placeholders, so match the planned types
+        let columns = self
+            .schema
+            .fields()
+            .iter()
+            .map(|field| cast(ident(field.name()), field.data_type().clone()).alias(field.name()))
+            .collect::<Vec<_>>();
+        annotated
+            .select(columns)?
+            .into_view()
+            .scan(state, projection, filters, limit)
+            .await
+    }
+}
+
+fn string_literal(expr: &Expr) -> Result<String> {
+    match expr {
+        Expr::Literal(
+            ScalarValue::Utf8(Some(s))
+            | ScalarValue::LargeUtf8(Some(s))
+            | ScalarValue::Utf8View(Some(s)),
+        ) => Ok(s.clone()),
+        _ => plan_err!(
+            "dfq_validate expects string literal arguments, got {}",
+            expr
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::{
+        column::*, dataset::dfq_mostly, schema::dfq_column_exists, table::dfq_null_count,
+    };
+    use arrow::array::{Float64Array, Int32Array, StringArray};
+    use arrow::datatypes::Field;
+    use arrow::record_batch::RecordBatch;
+    use datafusion::{assert_batches_eq, datasource::MemTable};
+
+    fn create_test_ctx() -> SessionContext {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("email", DataType::Utf8, true),
+            Field::new("score", DataType::Float64, true),
+        ]);
+
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![
+                    Some("alice@example.com"),
+                    Some("bob"),
+                    None,
+                ])),
+                Arc::new(Float64Array::from(vec![
+                    Some(85.5),
+                    Some(120.0),
+                    Some(40.0),
+                ])),
+            ],
+        )
+        .unwrap();
+
+        let ctx = SessionContext::new();
+        ctx.register_batch("orders", batch).unwrap();
+        ctx
+    }
+
+    #[tokio::test]
+    async fn test_scalar_udfs() {
+        let ctx = create_test_ctx();
+        register_udfs(&ctx, &RuleSetCatalog::new());
+
+        let result = ctx
+            .sql(
+                "SELECT id, dfq_in_range(score, 0, 100) AS in_range, \
+                 dfq_like(email, '%@%') AS has_at, \
+                 dfq_str_length(email, 3, 5) AS short, \
+                 dfq_is_email(email) AS is_email \
+                 FROM orders ORDER BY id",
+            )
+            .await
+            .unwrap();
+
+        let expected = vec![
+            "+----+----------+--------+-------+----------+",
+            "| id | in_range | has_at | short | is_email |",
+            "+----+----------+--------+-------+----------+",
+            "| 1  | true     | true   | false | true     |",
+            "| 2  | false    | false  | true  | false    |",
+            "| 3  | true     |        |       |          |",
+            "+----+----------+--------+-------+----------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        let result = ctx
+            .sql("SELECT id FROM orders WHERE NOT dfq_is_email(email)")
+            .await
+            .unwrap();
+
+        let expected = vec!["+----+", "| id |", "+----+", "| 2  |", "+----+"];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        assert!(
+            ctx.sql("SELECT dfq_in_range(score) FROM orders")
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hash_udf() {
+        let ctx = create_test_ctx();
+        register_udfs(&ctx, &RuleSetCatalog::new());
+
+        let result = ctx
+            .sql(
+                "SELECT dfq_hash(1) = dfq_hash('1') AS same, \
+                 dfq_hash('a') = dfq_hash('b') AS collides, \
+                 dfq_hash(NULL) IS NULL AS null_hash",
+            )
+            .await
+            .unwrap();
+
+        let expected = vec![
+            "+------+----------+-----------+",
+            "| same | collides | null_hash |",
+            "+------+----------+-----------+",
+            "| true | false    | true      |",
+            "+------+----------+-----------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+        assert_eq!(
+            fnv1a("a"),
+            i64::from_ne_bytes(0xaf63_dc4c_8601_ec8c_u64.to_ne_bytes())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_table_function() {
+        let ctx = create_test_ctx();
+
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_column_rule("email", dfq_is_email())
+            .with_column_rule("score", dfq_in_range(0.0, 100.0));
+
+        let catalog = RuleSetCatalog::new();
+        catalog.register("orders_rules", rule_set);
+        register_udfs(&ctx, &catalog);
+
+        let result = ctx
+            .sql(
+                "SELECT id, email_is_email, score_in_range, dfq_pass \
+                 FROM dfq_validate('orders_rules', 'orders') ORDER BY id",
+            )
+            .await
+            .unwrap();
+
+        let expected = vec![
+            "+----+----------------+----------------+----------+",
+            "| id | email_is_email | score_in_range | dfq_pass |",
+            "+----+----------------+----------------+----------+",
+            "| 1  | true           | true           | true     |",
+            "| 2  | false          | false          | false    |",
+            "| 3  |                | true           |          |",
+            "+----+----------------+----------------+----------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        assert!(
+            ctx.sql("SELECT * FROM dfq_validate('missing', 'orders')")
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_with_dependencies() {
+        let ctx = SessionContext::new();
+        let batches: Vec<Vec<RecordBatch>> = (0..8)
+            .map(|i| {
+                let batch = RecordBatch::try_new(
+                    Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, true)])),
+                    vec![Arc::new(Int32Array::from(vec![Some(i), None]))],
+                )
+                .unwrap();
+                vec![batch]
+            })
+            .collect();
+        let table = MemTable::try_new(batches[0][0].schema(), batches).unwrap();
+        ctx.register_table("t", Arc::new(table)).unwrap();
+
+        // Checking the prerequisite and the dataset outcome runs queries
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_dataset_rule("id", dfq_mostly(dfq_not_null(), 0.5))
+            .with_column_rule("id", dfq_gt(lit(3)))
+            .with_depends_on(["id_not_null_pass_rate"])
+            .with_table_rule("id", dfq_null_count(), None)
+            .with_column_rule("id", dfq_lt(lit(6)))
+            .with_depends_on(["id_greater_than"]);
+
+        let catalog = RuleSetCatalog::new();
+        catalog.register("checks", rule_set);
+        register_udfs(&ctx, &catalog);
+
+        let result = ctx
+            .sql(
+                "SELECT count(*) AS rows, count(id_greater_than) AS checked, \
+                 count(id_less_than) AS skipped, max(id_null_count) AS nulls, \
+                 sum(CAST(dfq_pass AS INT)) AS passed \
+                 FROM dfq_validate('checks', 't')",
+            )
+            .await
+            .unwrap();
+
+        let expected = vec![
+            "+------+---------+---------+-------+--------+",
+            "| rows | checked | skipped | nulls | passed |",
+            "+------+---------+---------+-------+--------+",
+            "| 16   | 8       | 0       | 8     | 4      |",
+            "+------+---------+---------+-------+--------+",
+        ];
+
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_validate_skipped_rules() {
+        let ctx = SessionContext::new();
+        ctx.sql("CREATE TABLE t (id INT) AS VALUES (1), (2)")
+            .await
+            .unwrap();
+
+        // The rules on the missing column are skipped, as in RuleSet::apply
+        let mut rule_set = RuleSet::new();
+        rule_set
+            .with_schema_rule(dfq_column_exists("nickname"))
+            .with_table_rule("nickname", dfq_null_count(), None)
+            .with_depends_on(["column_exists"])
+            .with_column_rule("nickname", dfq_not_null())
+            .with_depends_on(["column_exists"])
+            .with_column_rule("id", dfq_gt(lit(1)));
+
+        let catalog = RuleSetCatalog::new();
+        catalog.register("checks", rule_set.clone());
+        register_udfs(&ctx, &catalog);
+
+        let result = ctx
+            .sql(
+                "SELECT id, nickname_null_count, nickname_not_null, id_greater_than, dfq_pass \
+                 FROM dfq_validate('checks', 't') ORDER BY id",
+            )
+            .await
+            .unwrap();
+
+        let expected = vec![
+            "+----+---------------------+-------------------+-----------------+----------+",
+            "| id | nickname_null_count | nickname_not_null | id_greater_than | dfq_pass |",
+            "+----+---------------------+-------------------+-----------------+----------+",
+            "| 1  |                     |                   | false           | false    |",
+            "| 2  |                     |                   | true            | true     |",
+            "+----+---------------------+-------------------+-----------------+----------+",
+        ];
+        assert_batches_eq!(&expected, &result.collect().await.unwrap());
+
+        // Errors of rules that are not skipped are reported when planning
+        let mut rule_set = RuleSet::new();
+        rule_set.with_column_rule("id", dfq_custom_sql("broken", "missing > 1"));
+        catalog.register("broken", rule_set);
+        assert!(
+            ctx.sql("SELECT * FROM dfq_validate('broken', 't')")
+                .await
+                .is_err()
+        );
+    }
+}
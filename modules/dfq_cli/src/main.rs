@@ -0,0 +1,528 @@
+use clap::{Parser, ValueEnum};
+use datafusion::{
+    arrow::{record_batch::RecordBatch, util::pretty::pretty_format_batches},
+    execution::options::ArrowReadOptions,
+    prelude::*,
+};
+use datafusion_quality::{
+    ApplyOptions, RuleContext,
+    error::ValidationError,
+    report::ValidationReport,
+    sink::{OutputFormat, PartitionSink},
+    spec::RuleSetSpec,
+};
+use std::{
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+/// Validate data files against a data quality rule spec
+///
+/// Exits with 0 when every row passes, 1 when validation fails and 2 on any other error.
+#[derive(Debug, Parser)]
+#[command(name = "dfq", version)]
+struct Args {
+    /// Data files, directories or globs, e.g. `data/*.parquet`
+    #[arg(required = true)]
+    data: Vec<String>,
+
+    /// Rule spec, parsed as JSON for a `.json` extension and YAML otherwise
+    #[arg(short, long)]
+    rules: PathBuf,
+
+    /// Format of the data files, inferred from the first path's extension if omitted
+    #[arg(long, value_enum)]
+    input_format: Option<FileFormat>,
+
+    /// Write the report to this file instead of stdout
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Format of the report
+    #[arg(long, value_enum, default_value_t = ReportFormat::Json)]
+    report_format: ReportFormat,
+
+    /// Write the passing rows to this path
+    #[arg(long, requires = "fail_output")]
+    pass_output: Option<String>,
+
+    /// Write the failing rows to this path
+    #[arg(long, requires = "pass_output")]
+    fail_output: Option<String>,
+
+    /// Format of the pass/fail outputs
+    #[arg(long, value_enum, default_value_t = FileFormat::Parquet)]
+    output_format: FileFormat,
+
+    /// Run identifier added to the fail output as `dfq_run_id`
+    #[arg(long, requires = "pass_output")]
+    run_id: Option<String>,
+
+    /// Only run rules with at least one of these tags, e.g. `--tags smoke`
+    #[arg(long, value_delimiter = ',')]
+    tags: Option<Vec<String>>,
+
+    /// Skip rules with any of these tags, e.g. `--exclude-tags expensive`
+    #[arg(long, value_delimiter = ',')]
+    exclude_tags: Vec<String>,
+
+    /// Variable substituted for `${KEY}` in SQL rules, e.g. `--var region=eu`
+    #[arg(long = "var", value_name = "KEY=VALUE", value_parser = parse_variable)]
+    variables: Vec<(String, String)>,
+
+    /// Execution date of the run, substituted for `${run_date}`
+    #[arg(long)]
+    run_date: Option<String>,
+
+    /// Environment of the run, substituted for `${environment}`
+    #[arg(long)]
+    environment: Option<String>,
+
+    /// Resolve the rules against the input schema and print the plan without validating
+    #[arg(long)]
+    explain: bool,
+}
+
+fn parse_variable(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected KEY=VALUE, got '{}'", value))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum FileFormat {
+    Parquet,
+    Csv,
+    /// Newline delimited JSON
+    Json,
+    /// Arrow IPC file format
+    Arrow,
+}
+
+impl FileFormat {
+    fn from_path(path: &str) -> Option<Self> {
+        match Path::new(path)
+            .extension()?
+            .to_str()?
+            .to_lowercase()
+            .as_str()
+        {
+            "parquet" => Some(FileFormat::Parquet),
+            "csv" => Some(FileFormat::Csv),
+            "json" | "ndjson" | "jsonl" => Some(FileFormat::Json),
+            "arrow" | "ipc" | "feather" => Some(FileFormat::Arrow),
+            _ => None,
+        }
+    }
+
+    fn output_format(self) -> OutputFormat {
+        match self {
+            FileFormat::Parquet => OutputFormat::Parquet,
+            FileFormat::Csv => OutputFormat::Csv,
+            FileFormat::Json => OutputFormat::NdJson,
+            FileFormat::Arrow => OutputFormat::ArrowIpc,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ReportFormat {
+    Json,
+    Table,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args = Args::parse();
+
+    match run(&args).await {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::from(1),
+        Err(e) if is_validation_failure(&e) => {
+            eprintln!("dfq: validation failed: {}", e);
+            ExitCode::from(1)
+        }
+        Err(e) => {
+            eprintln!("dfq: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Schema rule failures are reported as errors by `RuleSet::apply`, but they are
+/// still a failed validation rather than a failed run
+fn is_validation_failure(e: &ValidationError) -> bool {
+    matches!(
+        e,
+        ValidationError::Schema { .. }
+            | ValidationError::ColumnNotFound { .. }
+            | ValidationError::TypeMismatch { .. }
+            | ValidationError::ColumnNullabilityMismatch { .. }
+    )
+}
+
+/// Validate the data and write the report, returning whether every row passed
+async fn run(args: &Args) -> Result<bool, ValidationError> {
+    let mut options = ApplyOptions::new().without_tags(&args.exclude_tags);
+    if let Some(tags) = &args.tags {
+        options = options.with_tags(tags);
+    }
+    let ctx = SessionContext::new();
+    let df = read_data(&ctx, &args.data, args.input_format).await?;
+
+    // Tables registered in the session are available as reference tables
+    let mut context = RuleContext::new().with_session_state(ctx.state());
+    if let Some(run_date) = &args.run_date {
+        context = context.with_run_date(run_date);
+    }
+    if let Some(environment) = &args.environment {
+        context = context.with_environment(environment);
+    }
+    for (key, value) in &args.variables {
+        context = context.with_variable(key, value);
+    }
+    let mut rule_set = RuleSetSpec::from_path(&args.rules)?
+        .to_rule_set()?
+        .select(&options);
+    rule_set.with_context(context);
+
+    if args.explain {
+        let explanation = rule_set.explain(&df).await?;
+        print!("{}", explanation);
+        return Ok(explanation.is_valid());
+    }
+
+    // Evaluate once for both the report and the pass/fail outputs
+    let mut evaluation = rule_set.evaluate(&df).await?;
+    if args.pass_output.is_some() {
+        evaluation.df = evaluation.df.cache().await?;
+    }
+    let report = rule_set.report_evaluation(&evaluation).await?;
+    write_report(&report, args.report_format, args.output.as_deref())?;
+
+    if let (Some(pass), Some(fail)) = (&args.pass_output, &args.fail_output) {
+        let mut sink = PartitionSink::new(pass, fail, args.output_format.output_format());
+        if let Some(run_id) = &args.run_id {
+            sink = sink.with_run_id(run_id);
+        }
+        let counts = sink.write(&df, evaluation.df).await?;
+        eprintln!(
+            "dfq: wrote {} passing rows to {} and {} failing rows to {}",
+            counts.pass_rows, pass, counts.fail_rows, fail
+        );
+    }
+
+    Ok(report.success())
+}
+
+async fn read_data(
+    ctx: &SessionContext,
+    paths: &[String],
+    format: Option<FileFormat>,
+) -> Result<DataFrame, ValidationError> {
+    let first = paths
+        .first()
+        .ok_or_else(|| ValidationError::Configuration {
+            message: "No data paths given".to_string(),
+        })?;
+    let format = format
+        .or_else(|| FileFormat::from_path(first))
+        .ok_or_else(|| ValidationError::Configuration {
+            message: format!(
+                "Unable to infer the format of '{}', pass --input-format",
+                first
+            ),
+        })?;
+
+    // Listing tables filter files by extension, so honour the one given on the
+    // command line (e.g. `.ndjson`) instead of the format's default
+    let extension = Path::new(first)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| format!(".{}", ext));
+
+    let paths = paths.to_vec();
+    let df = match format {
+        FileFormat::Parquet => {
+            let mut options = ParquetReadOptions::default();
+            if let Some(extension) = &extension {
+                options.file_extension = extension;
+            }
+            ctx.read_parquet(paths, options).await
+        }
+        FileFormat::Csv => {
+            let mut options = CsvReadOptions::new();
+            if let Some(extension) = &extension {
+                options = options.file_extension(extension);
+            }
+            ctx.read_csv(paths, options).await
+        }
+        FileFormat::Json => {
+            let mut options = NdJsonReadOptions::default();
+            if let Some(extension) = &extension {
+                options = options.file_extension(extension);
+            }
+            ctx.read_json(paths, options).await
+        }
+        FileFormat::Arrow => {
+            let mut options = ArrowReadOptions::default();
+            if let Some(extension) = &extension {
+                options.file_extension = extension;
+            }
+            ctx.read_arrow(paths, options).await
+        }
+    }?;
+
+    Ok(df)
+}
+
+fn write_report(
+    report: &ValidationReport,
+    format: ReportFormat,
+    output: Option<&Path>,
+) -> Result<(), ValidationError> {
+    let contents = match format {
+        ReportFormat::Json => {
+            serde_json::to_string_pretty(report).map_err(|e| ValidationError::Validation {
+                message: format!("Error serializing report: {}", e),
+            })?
+        }
+        ReportFormat::Table => {
+            let format_batch = |batch: RecordBatch| {
+                pretty_format_batches(&[batch]).map_err(|e| ValidationError::Validation {
+                    message: format!("Error formatting report: {}", e),
+                })
+            };
+            let mut contents = format_batch(report.to_record_batch()?)?.to_string();
+            if !report.dataset_rules.is_empty() {
+                contents = format!(
+                    "{}\n{}",
+                    contents,
+                    format_batch(report.dataset_record_batch()?)?
+                );
+            }
+            format!(
+                "{}\n{} rows, {} passed, {} failed",
+                contents, report.total_rows, report.passed_rows, report.failed_rows
+            )
+        }
+    };
+
+    match output {
+        Some(path) => {
+            std::fs::write(path, contents + "\n").map_err(|e| ValidationError::Configuration {
+                message: format!("Unable to write report to '{}': {}", path.display(), e),
+            })
+        }
+        None => {
+            println!("{}", contents);
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("dfq_cli_{}_{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn args(dir: &Path, extra: &[&str]) -> Args {
+        let data = dir.join("data.csv");
+        let rules = dir.join("rules.yaml");
+        let mut argv = vec![
+            "dfq".to_string(),
+            data.display().to_string(),
+            "--rules".to_string(),
+            rules.display().to_string(),
+        ];
+        argv.extend(extra.iter().map(|s| s.to_string()));
+        Args::parse_from(argv)
+    }
+
+    #[test]
+    fn test_run_id_requires_outputs() {
+        let argv = |extra: &[&'static str]| {
+            ["dfq", "data.csv", "--rules", "rules.yaml", "--run-id", "r1"]
+                .into_iter()
+                .chain(extra.iter().copied())
+                .collect::<Vec<_>>()
+        };
+        assert!(Args::try_parse_from(argv(&[])).is_err());
+        assert!(
+            Args::try_parse_from(argv(&["--pass-output", "pass/", "--fail-output", "fail/"]))
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_file_format_from_path() {
+        assert_eq!(
+            FileFormat::from_path("data/*.parquet"),
+            Some(FileFormat::Parquet)
+        );
+        assert_eq!(
+            FileFormat::from_path("events.ndjson"),
+            Some(FileFormat::Json)
+        );
+        assert_eq!(FileFormat::from_path("data/"), None);
+    }
+
+    #[tokio::test]
+    async fn test_run() {
+        let dir = test_dir("run");
+        std::fs::write(
+            dir.join("data.csv"),
+            "id,name,score\n1,Alice,85.5\n2,,92.0\n3,Charlie,40.0\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("rules.yaml"),
+            "columns:\n  - column: name\n    rules:\n      - not_null\n",
+        )
+        .unwrap();
+
+        let report_path = dir.join("report.json");
+        let pass = format!("{}/pass/", dir.display());
+        let fail = format!("{}/fail/", dir.display());
+        let args = args(
+            &dir,
+            &[
+                "--output",
+                &report_path.display().to_string(),
+                "--pass-output",
+                &pass,
+                "--fail-output",
+                &fail,
+                "--output-format",
+                "csv",
+            ],
+        );
+
+        assert!(!run(&args).await.unwrap());
+
+        let report: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&report_path).unwrap()).unwrap();
+        assert_eq!(report["total_rows"], 3);
+        assert_eq!(report["failed_rows"], 1);
+        assert_eq!(report["rules"][0]["check_column"], "name_not_null");
+
+        let ctx = SessionContext::new();
+        let passed = ctx
+            .read_csv(pass, CsvReadOptions::new())
+            .await
+            .unwrap()
+            .count()
+            .await
+            .unwrap();
+        assert_eq!(passed, 2);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_run_with_tags() {
+        let dir = test_dir("tags");
+        std::fs::write(dir.join("data.csv"), "id,name\n1,Alice\n2,\n").unwrap();
+        std::fs::write(
+            dir.join("rules.yaml"),
+            "columns:\n  - column: id\n    rules: [not_null]\n    tags: [smoke]\n  - column: name\n    rules: [not_null]\n",
+        )
+        .unwrap();
+
+        let report_path = dir.join("report.json");
+        let report_arg = report_path.display().to_string();
+        assert!(
+            run(&args(&dir, &["--output", &report_arg, "--tags", "smoke"]))
+                .await
+                .unwrap()
+        );
+        assert!(
+            !run(&args(
+                &dir,
+                &["--output", &report_arg, "--exclude-tags", "smoke"]
+            ))
+            .await
+            .unwrap()
+        );
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_run_with_variables() {
+        let dir = test_dir("variables");
+        std::fs::write(dir.join("data.csv"), "id,day\n1,2024-01-30\n2,2024-01-31\n").unwrap();
+        std::fs::write(
+            dir.join("rules.yaml"),
+            "columns:\n  - column: day\n    rules:\n      - sql: { name: before_run, expr: \"day < DATE '${run_date}' AND id < ${max_id}\" }\n",
+        )
+        .unwrap();
+
+        let report_path = dir.join("report.json");
+        let report_arg = report_path.display().to_string();
+        let run_with = |run_date: &str| {
+            args(
+                &dir,
+                &[
+                    "--output",
+                    &report_arg,
+                    "--run-date",
+                    run_date,
+                    "--var",
+                    "max_id=3",
+                ],
+            )
+        };
+        assert!(run(&run_with("2024-02-01")).await.unwrap());
+        assert!(!run(&run_with("2024-01-31")).await.unwrap());
+        assert!(run(&args(&dir, &["--output", &report_arg])).await.is_err());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_run_explain() {
+        let dir = test_dir("explain");
+        std::fs::write(dir.join("data.csv"), "id,name\n1,Alice\n").unwrap();
+        std::fs::write(
+            dir.join("rules.yaml"),
+            "columns:\n  - column: name\n    rules: [is_email]\n",
+        )
+        .unwrap();
+        assert!(run(&args(&dir, &["--explain"])).await.unwrap());
+
+        std::fs::write(
+            dir.join("rules.yaml"),
+            "columns:\n  - column: email\n    rules: [is_email]\n",
+        )
+        .unwrap();
+        assert!(!run(&args(&dir, &["--explain"])).await.unwrap());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_run_schema_failure() {
+        let dir = test_dir("schema");
+        std::fs::write(dir.join("data.csv"), "id,name\n1,Alice\n").unwrap();
+        std::fs::write(
+            dir.join("rules.yaml"),
+            "schema:\n  - column_exists: score\n",
+        )
+        .unwrap();
+
+        let err = run(&args(&dir, &["--report-format", "table"]))
+            .await
+            .unwrap_err();
+        assert!(is_validation_failure(&err));
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}